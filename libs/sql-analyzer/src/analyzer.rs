@@ -1,125 +1,279 @@
+use crate::catalog::{self, Catalog, FunctionKind, Overload, ParamType, ResolveError};
 use crate::schema_info::SchemaInfo;
-use crate::{err, utils, AnalyseError, DataType, Table};
-use sql_parser::grammar::{ast::*, Term};
+use crate::{err, grouping, pattern, utils, AnalyseError, DataType, Table};
+use sql_parser::dialect::Dialect;
+use sql_parser::function::{Function, FunctionArgs, FunctionCall, SubstringArgs};
+use sql_parser::grammar::{ast::*, Case, Term};
+use sql_parser::grammar::{Name, Value};
+use sql_parser::utils::suggest;
+use sql_parser::window::{FrameBound, FrameUnits, NameOrSpec, WindowSpec};
 use sql_parser::GetSpan;
-use sql_parser::{function::FunctionKind, grammar::Value};
 
 pub trait Analyser {
-    fn analyse_or_expr(&mut self, expr: &OrExpr) -> Result<DataType, AnalyseError> {
-        let left = self.analyse_and_expr(&expr.left)?;
-        if let Some((_, expr_right)) = &expr.right {
-            err::expect_bool(&left, &expr.left)?;
-            let right = &self.analyse_or_expr(expr_right)?;
-            err::expect_bool(&right, &expr_right)?;
+    /// Type of a subexpression, whose error doesn't stop the analysis of the enclosing one: it is
+    /// kept with `push_err` and the subexpression poisoned with `DataType::Error`.
+    fn analyse_operand(&mut self, expr: &Expr) -> DataType {
+        match self.analyse_expr(expr) {
+            Ok(ty) => ty,
+            Err(err) => {
+                self.push_err(err);
+                DataType::Error
+            }
         }
-        Ok(left)
     }
 
-    fn analyse_and_expr(&mut self, expr: &AndExpr) -> Result<DataType, AnalyseError> {
-        let left = self.analyse_condition(&expr.left)?;
-        if let Some((_, expr_right)) = &expr.right {
-            err::expect_bool(&left, &expr.left)?;
-            let right = self.analyse_and_expr(expr_right)?;
-            err::expect_bool(&right, &expr_right)?;
-        }
-        Ok(left)
-    }
+    fn push_err(&mut self, err: AnalyseError);
 
-    fn analyse_condition(&mut self, cond: &Condition) -> Result<DataType, AnalyseError> {
-        match cond {
-            Condition::Not(cond) => {
-                let ty = self.analyse_condition(cond)?;
-                err::expect_bool(&ty, cond)?;
-                Ok(ty)
-            }
-            Condition::Operand {
-                left: expr_left,
-                right,
-            } => {
-                let left = self.analyse_operand(expr_left)?;
-                if let Some(rhs) = right {
-                    match rhs {
-                        RightHandSide::Comparison(_, expr_right) => {
-                            err::expect_numeric(&left, expr_left)?;
-                            let right = self.analyse_operand(expr_right)?;
-                            if left.is_unknown() && right.is_unknown() {
-                                return err::msg(expr_left, "unknown type");
-                            }
-                            err::expect_numeric(&right, expr_right)?;
+    fn analyse_expr(&mut self, expr: &Expr) -> Result<DataType, AnalyseError> {
+        match expr {
+            Expr::Term(term) => self.analyse_term(term),
+            Expr::Unary { op, expr } => {
+                let ty = self.analyse_operand(expr);
+                match op.kind {
+                    UnaryOperatorKind::Not => {
+                        err::expect_bool(&ty, expr)?;
+                        Ok(DataType::Boolean)
+                    }
+                    UnaryOperatorKind::Minus | UnaryOperatorKind::Plus => {
+                        if ty != DataType::Interval {
+                            err::expect_numeric(&ty, expr)?;
                         }
+                        Ok(ty)
+                    }
+                    UnaryOperatorKind::BitwiseNot => {
+                        err::expect_integer(&ty, expr)?;
+                        Ok(ty)
                     }
                 }
-                Ok(left)
             }
+            Expr::Binary { left, op, right } => self.analyse_binary(left, op, right),
+            Expr::Predicate { expr, predicate } => self.analyse_predicate(expr, predicate),
         }
     }
 
-    fn analyse_operand(&mut self, expr: &Operand) -> Result<DataType, AnalyseError> {
-        let left = self.analyse_arithmetic(&expr.left)?;
-        if let Some((_, expr_right)) = &expr.right {
-            let right = self.analyse_operand(expr_right)?;
-            if left.is_text() || right.is_text() {
-                return Ok(DataType::Text);
+    fn analyse_binary(
+        &mut self,
+        expr_left: &Expr,
+        op: &BinaryOperator,
+        expr_right: &Expr,
+    ) -> Result<DataType, AnalyseError> {
+        use BinaryOperatorKind::*;
+        let left = self.analyse_operand(expr_left);
+        let right = self.analyse_operand(expr_right);
+        if left.is_error() || right.is_error() {
+            return Ok(match op.kind {
+                Or
+                | And
+                | Match { .. }
+                | Equal
+                | NotEqual
+                | LessThan
+                | LessThanOrEqual
+                | GreaterThan
+                | GreaterThanOrEqual => DataType::Boolean,
+                _ => DataType::Error,
+            });
+        }
+        match op.kind {
+            Or | And => {
+                err::expect_bool(&left, expr_left)?;
+                err::expect_bool(&right, expr_right)?;
+                Ok(DataType::Boolean)
+            }
+            Match { .. } => {
+                err::expect_text(&left, expr_left)?;
+                err::expect_text(&right, expr_right)?;
+                pattern::check_regex(expr_right)?;
+                Ok(DataType::Boolean)
             }
-            if let (DataType::Array { ty }, DataType::Array { ty: _ty }) = (&left, &right) {
-                if ty.is_numeric() && _ty.is_numeric() {
-                    return Ok(DataType::Numeric);
+            Equal | NotEqual | LessThan | LessThanOrEqual | GreaterThan | GreaterThanOrEqual => {
+                if left.is_unknown() && right.is_unknown() {
+                    return err::msg(expr_left, "unknown type");
                 }
-                // TODO
+                expect_comparable(expr_right, &op.kind.to_string(), &left, &right)?;
+                Ok(DataType::Boolean)
             }
-            return err::msg(
-                &expr.left,
-                format!("mismatch type, left: `{left:?}`, right: `{right:?}`"),
-            );
-        }
-        Ok(left)
-    }
-
-    fn analyse_arithmetic(&mut self, expr: &Arithmetic) -> Result<DataType, AnalyseError> {
-        let left = self.analyse_factorial(&expr.left)?;
-        if let Some((symbol, expr_right)) = &expr.right {
-            err::expect_numeric(&left, &expr.left)?;
-
-            let right = self.analyse_arithmetic(expr)?;
-            if left.is_unknown() && right.is_unknown() {
-                return err::msg(symbol, "unknown type");
+            Concat => {
+                let elements = match (&left, &right) {
+                    (DataType::Array { ty: a }, DataType::Array { ty: b }) => Some((&**a, &**b)),
+                    (DataType::Array { ty: a }, b) | (b, DataType::Array { ty: a }) => {
+                        Some((&**a, b))
+                    }
+                    _ => None,
+                };
+                let ty = match elements {
+                    Some((a, b)) => a.unify(b).map(|ty| DataType::Array { ty: Box::new(ty) }),
+                    None if left.is_text() || right.is_text() => Some(DataType::Text),
+                    None => None,
+                };
+                match ty {
+                    Some(ty) => Ok(ty),
+                    None => err::msg(
+                        op,
+                        format!("operator does not exist: `{left:?}` || `{right:?}`"),
+                    ),
+                }
+            }
+            BitwiseAnd | BitwiseOr | BitwiseXor | ShiftLeft | ShiftRight => {
+                err::expect_integer(&left, expr_left)?;
+                err::expect_integer(&right, expr_right)?;
+                if left.is_unknown() && right.is_unknown() {
+                    return err::msg(op, "unknown type");
+                }
+                Ok(match op.kind {
+                    // The shift amount is always an `integer`.
+                    ShiftLeft | ShiftRight if !left.is_unknown() => left,
+                    _ => left.unify(&right).unwrap_or(DataType::Integer),
+                })
+            }
+            Plus | Minus | Multiply | Divide | Modulo | Exponent => {
+                if left.is_unknown() && right.is_unknown() {
+                    return err::msg(op, "unknown type");
+                }
+                if let Some(ty) = arithmetic_type(op.kind, &left, &right) {
+                    return Ok(ty);
+                }
+                if !left.is_datetime() {
+                    err::expect_numeric(&left, expr_left)?;
+                }
+                if !right.is_datetime() {
+                    err::expect_numeric(&right, expr_right)?;
+                }
+                err::msg(
+                    op,
+                    format!(
+                        "operator does not exist: `{left:?}` {} `{right:?}`",
+                        op.kind
+                    ),
+                )
             }
-            err::expect_numeric(&right, expr_right)?;
         }
-        Ok(left)
     }
 
-    fn analyse_factorial(&mut self, expr: &Factorial) -> Result<DataType, AnalyseError> {
-        let left = self.analyse_term(&expr.left)?;
-        if let Some((symbol, expr_right)) = &expr.right {
-            err::expect_numeric(&left, &expr.left)?;
-
-            let right = self.analyse_factorial(expr_right)?;
-            if left.is_unknown() && right.is_unknown() {
-                return err::msg(symbol, "unknown type");
+    fn analyse_predicate(
+        &mut self,
+        expr_left: &Expr,
+        predicate: &Predicate,
+    ) -> Result<DataType, AnalyseError> {
+        let left = self.analyse_operand(expr_left);
+        match predicate {
+            Predicate::In { in_kw, list, .. } => {
+                let mut ty = left;
+                for expr in list {
+                    let right = self.analyse_operand(expr);
+                    ty = match ty.unify(&right) {
+                        Some(ty) => ty,
+                        None => {
+                            return err::msg(
+                                expr,
+                                format!(
+                                    "{} types `{ty:?}` and `{right:?}` cannot be matched",
+                                    in_kw.to_string().to_uppercase()
+                                ),
+                            )
+                        }
+                    };
+                }
+            }
+            Predicate::Like {
+                kind,
+                pattern,
+                escape,
+                ..
+            } => {
+                err::expect_text(&left, expr_left)?;
+                let ty = self.analyse_operand(pattern);
+                err::expect_text(&ty, pattern)?;
+                let escape = escape.as_ref().map(|(_, escape)| escape);
+                if let Some(escape) = escape {
+                    let ty = self.analyse_operand(escape);
+                    err::expect_text(&ty, escape)?;
+                }
+                pattern::check_like(*kind, pattern, escape)?;
+            }
+            Predicate::Between { low, high, .. } => {
+                for bound in [low, high] {
+                    let right = self.analyse_operand(bound);
+                    expect_comparable(bound, ">=", &left, &right)?;
+                }
             }
-            err::expect_numeric(&right, expr_right)?;
+            Predicate::Is { predicate, .. } => match predicate {
+                IsPredicate::Null(_) => {}
+                IsPredicate::True(_) | IsPredicate::False(_) | IsPredicate::Unknown(_) => {
+                    err::expect_bool(&left, expr_left)?
+                }
+                IsPredicate::DistinctFrom { expr, .. } => {
+                    let right = self.analyse_operand(expr);
+                    expect_comparable(expr, "IS DISTINCT FROM", &left, &right)?;
+                }
+            },
         }
-        Ok(left)
+        Ok(DataType::Boolean)
     }
 
     fn analyse_term(&mut self, term: &Term) -> Result<DataType, AnalyseError>;
 }
 
+/// Result type of an arithmetic operator, `None` if Postgres has no such operator.
+///
+/// See: https://www.postgresql.org/docs/current/functions-datetime.html
+fn arithmetic_type(op: BinaryOperatorKind, left: &DataType, right: &DataType) -> Option<DataType> {
+    use BinaryOperatorKind::*;
+    use DataType::*;
+    // An untyped literal takes the type of the other operand.
+    let (left, right) = match (left, right) {
+        (Unknown, ty) | (ty, Unknown) => (ty, ty),
+        _ => (left, right),
+    };
+    let is_int = |ty: &DataType| matches!(ty, SmallInt | Integer);
+    Some(match (op, left, right) {
+        (Plus, Date, n) | (Plus, n, Date) | (Minus, Date, n) if is_int(n) => Date,
+        (Minus, Date, Date) => Integer,
+        (Plus, Date, Time) | (Plus, Time, Date) => Timestamp,
+        (Plus, Date | Timestamp, Interval)
+        | (Plus, Interval, Date | Timestamp)
+        | (Minus, Date | Timestamp, Interval) => Timestamp,
+        (Plus, TimestampWithTimeZone, Interval)
+        | (Plus, Interval, TimestampWithTimeZone)
+        | (Minus, TimestampWithTimeZone, Interval) => TimestampWithTimeZone,
+        (Minus, Timestamp, Timestamp)
+        | (Minus, TimestampWithTimeZone, TimestampWithTimeZone)
+        | (Minus, Time, Time)
+        | (Plus | Minus, Interval, Interval) => Interval,
+        (Plus | Minus, Time, Interval) | (Plus, Interval, Time) => Time,
+        (Multiply | Divide, Interval, n) | (Multiply, n, Interval) if n.is_numeric() => Interval,
+        (Exponent, a, b) if a.is_numeric() && b.is_numeric() => match (a, b) {
+            (Numeric, _) | (_, Numeric) => Numeric,
+            _ => DoublePrecision,
+        },
+        (_, a, b) if a.is_numeric() && b.is_numeric() => a.unify(b)?,
+        _ => return None,
+    })
+}
+
 pub struct AnalyseExpr<'t> {
-    pub _schema: &'t SchemaInfo,
-    pub table: Option<&'t Table>,
+    pub schema: &'t SchemaInfo,
+    pub dialect: &'t dyn Dialect,
+    pub tables: Vec<(String, &'t Table)>,
+    /// Windows defined by the `WINDOW` clause of the enclosing `SELECT`.
+    pub windows: Vec<(String, &'t WindowSpec)>,
+    /// Errors of subexpressions, to be added to `Ctx::errs` with `Ctx::add_errs`.
+    pub errs: Vec<AnalyseError>,
 }
 
 impl Analyser for AnalyseExpr<'_> {
+    fn push_err(&mut self, err: AnalyseError) {
+        self.errs.push(err);
+    }
+
     fn analyse_term(&mut self, term: &Term) -> Result<DataType, AnalyseError> {
         Ok(match term {
             Term::Value(val) => match val {
                 Value::String(_) => DataType::Text,
                 Value::Int(int) => {
-                    if let Ok(_) = int.base10_parse::<i32>() {
+                    if int.base10_parse::<i32>().is_ok() {
                         DataType::Integer
-                    } else if let Ok(_) = int.base10_parse::<i64>() {
+                    } else if int.base10_parse::<i64>().is_ok() {
                         DataType::BigInt
                     } else {
                         DataType::Numeric
@@ -129,10 +283,10 @@ impl Analyser for AnalyseExpr<'_> {
                 Value::Boolean { .. } => DataType::Boolean,
                 Value::ARRAY(exprs) => match exprs.first() {
                     Some(expr) => {
-                        let ty = self.analyse_or_expr(expr)?;
-                        for expr in &exprs.value {
-                            let rest = self.analyse_or_expr(expr)?;
-                            if ty != rest {
+                        let ty = self.analyse_operand(expr);
+                        for expr in exprs.value.iter().skip(1) {
+                            let rest = self.analyse_operand(expr);
+                            if ty != rest && !ty.is_error() && !rest.is_error() {
                                 return err::msg(
                                     expr,
                                     format!("expected `{ty:?}` type, found `{rest:?}`"),
@@ -145,119 +299,869 @@ impl Analyser for AnalyseExpr<'_> {
                 },
                 Value::Null { .. } => DataType::Unknown,
             },
-            Term::Column(name) => match self.table {
-                Some(table) => match utils::get_column(table, &name.alias) {
-                    Ok(column) => column.data_type.clone(),
-                    Err(_) => DataType::Unknown,
-                },
-                None => DataType::Unknown,
-            },
-            Term::Func(func) => match &func.value {
-                FunctionKind::PI() | FunctionKind::SIGN(_) => DataType::DoublePrecision,
-                FunctionKind::ABS(e)
-                | FunctionKind::CEIL(e)
-                | FunctionKind::FLOOR(e)
-                | FunctionKind::ROUND(e) => self.expect_numeric(e)?,
-
-                FunctionKind::ACOS(e)
-                | FunctionKind::ASIN(e)
-                | FunctionKind::ATAN(e)
-                | FunctionKind::COS(e)
-                | FunctionKind::COSH(e)
-                | FunctionKind::COT(e)
-                | FunctionKind::SIN(e)
-                | FunctionKind::SINH(e)
-                | FunctionKind::TAN(e)
-                | FunctionKind::TANH(e)
-                | FunctionKind::DEGREES(e)
-                | FunctionKind::EXP(e)
-                | FunctionKind::LN(e)
-                | FunctionKind::LOG(_, e)
-                | FunctionKind::LOG10(e)
-                | FunctionKind::RADIANS(e)
-                | FunctionKind::SQRT(e)
-                | FunctionKind::POWER(e, _) => {
-                    self.expect_numeric(e)?;
-                    DataType::DoublePrecision
-                }
-                FunctionKind::ATAN2(e1, e2) => {
-                    self.expect_numeric(e1)?;
-                    self.expect_numeric(e2)?;
-                    DataType::DoublePrecision
-                }
-
-                FunctionKind::BITAND(e1, e2)
-                | FunctionKind::BITOR(e1, e2)
-                | FunctionKind::BITXOR(e1, e2)
-                | FunctionKind::BITNAND(e1, e2)
-                | FunctionKind::BITNOR(e1, e2)
-                | FunctionKind::BITXNOR(e1, e2) => {
-                    let lhs_ty = self.get_bitwise_ty(e1)?;
-                    let rhs_ty = self.analyse_or_expr(e2)?;
-                    check_same_type(e2, &lhs_ty, &rhs_ty)?;
-                    lhs_ty
-                }
-
-                FunctionKind::BITNOT(e)
-                | FunctionKind::BITCOUNT(e)
-                | FunctionKind::BITGET(e, _)
-                | FunctionKind::LSHIFT(e, _)
-                | FunctionKind::RSHIFT(e, _)
-                | FunctionKind::ULSHIFT(e, _)
-                | FunctionKind::URSHIFT(e, _)
-                | FunctionKind::ROTATELEFT(e, _)
-                | FunctionKind::ROTATERIGHT(e, _) => self.get_bitwise_ty(e)?,
-
-                FunctionKind::MOD(e1, e2) => {
-                    let lhs_ty = self.analyse_arithmetic(e1)?;
-                    let rhs_ty = self.analyse_arithmetic(e2)?;
-                    check_same_type(e2, &lhs_ty, &rhs_ty)?;
-                    lhs_ty
+            Term::Column(name) => utils::resolve_column(&self.tables, self.dialect, name)?
+                .data_type
+                .clone(),
+            Term::Func(call) => self.analyse_function_call(call)?,
+            Term::Expr(expr) => self.analyse_expr(expr)?,
+            Term::Case(case) => self.analyse_case(case)?,
+            Term::Cast(cast) => {
+                let ty = match self.analyse_term(&cast.expr) {
+                    Ok(ty) => ty,
+                    Err(err) => {
+                        self.push_err(err);
+                        DataType::Error
+                    }
+                };
+                let target = utils::resolve_type(self.schema, self.dialect, &cast.type_name)?;
+                if ty.cast_context(&target).is_none() {
+                    return err::msg(
+                        cast.as_ref(),
+                        format!("cannot cast type `{ty:?}` to `{target:?}`"),
+                    );
                 }
-                // String Functions
-                FunctionKind::ASCII(_) | FunctionKind::CHAR_LENGTH(_) => DataType::Integer,
-                FunctionKind::CONCAT(_)
-                | FunctionKind::LOWER(_)
-                | FunctionKind::UPPER(_)
-                | FunctionKind::LEFT(_, _)
-                | FunctionKind::RIGHT(_, _)
-                | FunctionKind::REPEAT(_, _)
-                | FunctionKind::SPACE(_) => DataType::Text,
-
-                FunctionKind::AVG() => todo!(),
-                FunctionKind::UnknownFunc(name, _) => return err::msg(name.span(), "unknown function"),
-            },
-            Term::OrExpr(expr) => self.analyse_or_expr(expr)?,
+                target
+            }
+            // Like `NULL`, the type is inferred from where the parameter is used.
+            Term::Placeholder(_) => DataType::Unknown,
         })
     }
 }
 
-fn check_same_type(
+/// Values of the same type category can be compared, `integer = bigint` is fine but `text = integer` is not.
+fn expect_comparable(
     span: impl GetSpan,
-    lhs_ty: &DataType,
-    rhs_ty: &DataType,
+    operator: &str,
+    left: &DataType,
+    right: &DataType,
 ) -> Result<(), AnalyseError> {
-    if *lhs_ty != *rhs_ty {
+    if left.unify(right).is_none() {
         return err::msg(
             span,
-            format!("expected data type: {lhs_ty:?}, got: {rhs_ty:?}"),
+            format!("operator does not exist: `{left:?}` {operator} `{right:?}`"),
         );
     }
     Ok(())
 }
 
-impl AnalyseExpr<'_> {
-    fn expect_numeric(&mut self, e: &Arithmetic) -> Result<DataType, AnalyseError> {
-        let ty = self.analyse_arithmetic(e)?;
-        err::expect_numeric(&ty, e)?;
+impl<'t> AnalyseExpr<'t> {
+    fn analyse_function_call(&mut self, call: &FunctionCall) -> Result<DataType, AnalyseError> {
+        let func = &call.function;
+        // An unknown function or a procedure is reported by `analyse_function`.
+        let kind = self
+            .overloads(&func.name())
+            .iter()
+            .map(|overload| overload.kind)
+            .find(|kind| *kind != FunctionKind::Procedure)
+            .unwrap_or(FunctionKind::Aggregate);
+        if let Some(filter) = &call.filter {
+            if kind != FunctionKind::Aggregate {
+                return err::msg(
+                    filter.filter_kw.span(),
+                    format!(
+                        "FILTER specified, but `{}` is not an aggregate function",
+                        func.name()
+                    ),
+                );
+            }
+            let ty = self.analyse_operand(&filter.condition);
+            err::expect_bool(&ty, &filter.condition)?;
+            grouping::expect_no_aggregates(&filter.condition, "FILTER")?;
+        }
+        match &call.over {
+            Some(over) => {
+                if kind == FunctionKind::Scalar {
+                    return err::msg(
+                        over.over_kw.span(),
+                        format!(
+                            "OVER specified, but `{}` is not a window function nor an aggregate function",
+                            func.name()
+                        ),
+                    );
+                }
+                match &over.name_or_spec {
+                    NameOrSpec::Name(name) => {
+                        self.get_window(name)?;
+                    }
+                    NameOrSpec::Spec(spec) => self.analyse_window_spec(spec)?,
+                }
+            }
+            None if kind == FunctionKind::Window => {
+                return err::msg(
+                    func,
+                    format!("window function `{}` requires an OVER clause", func.name()),
+                )
+            }
+            None => {}
+        }
+        self.analyse_function(func)
+    }
+
+    /// Overloads of the function `name`, the user defined ones of the schema then the built-in ones.
+    fn overloads(&self, name: &str) -> Vec<&'t Overload> {
+        let builtins = Catalog::builtins();
+        self.schema
+            .functions
+            .get(name)
+            .iter()
+            .chain(builtins.get(name))
+            .collect()
+    }
+
+    fn analyse_function(&mut self, func: &Function) -> Result<DataType, AnalyseError> {
+        let name = func.name();
+        let (procedures, functions): (Vec<_>, Vec<_>) = self
+            .overloads(&name)
+            .into_iter()
+            .partition(|overload| overload.kind == FunctionKind::Procedure);
+        if functions.is_empty() && !procedures.is_empty() {
+            return err::msg(
+                func,
+                format!("`{name}` is a procedure, use CALL to call it"),
+            );
+        }
+        self.resolve_function(func, &functions)
+    }
+
+    /// `CALL <procedure>(..)`
+    pub fn analyse_procedure(&mut self, procedure: &Function) -> Result<(), AnalyseError> {
+        let name = procedure.name();
+        let (procedures, functions): (Vec<_>, Vec<_>) = self
+            .overloads(&name)
+            .into_iter()
+            .partition(|overload| overload.kind == FunctionKind::Procedure);
+        if !procedures.is_empty() {
+            return self.resolve_function(procedure, &procedures).map(|_| ());
+        }
+        if !functions.is_empty() {
+            return err::msg(
+                procedure,
+                format!("`{name}` is not a procedure, use SELECT to call a function"),
+            );
+        }
+        let functions = &self.schema.functions;
+        let names = functions
+            .names()
+            .filter(|name| functions.kind(name) == Some(FunctionKind::Procedure));
+        err::msg(
+            procedure,
+            format!(
+                "procedure `{name}` does not exist \nsuggest: {}",
+                suggest(&name, names)
+            ),
+        )
+    }
+
+    /// Type of a call to one of `overloads` of the function.
+    fn resolve_function(
+        &mut self,
+        func: &Function,
+        overloads: &[&Overload],
+    ) -> Result<DataType, AnalyseError> {
+        let name = func.name();
+        let mut args = func.args();
+        if let Some(list) = func.arg_list() {
+            if overloads
+                .first()
+                .is_some_and(|overload| overload.kind == FunctionKind::Aggregate)
+            {
+                for expr in func.exprs() {
+                    if let Some(call) = grouping::find_call(expr, &grouping::is_aggregate_call) {
+                        return err::msg(call, "aggregate function calls cannot be nested");
+                    }
+                    if let Some(call) = grouping::find_call(expr, &grouping::is_window_call) {
+                        return err::msg(
+                            call,
+                            "aggregate function calls cannot contain window function calls",
+                        );
+                    }
+                }
+                if let Some(order_by) = &list.order_by {
+                    for spec in order_by.sort_specs.iter() {
+                        self.analyse_operand(&spec.expr);
+                    }
+                }
+            } else if let Some(kw) = &list.quantifier {
+                return err::msg(
+                    kw.span(),
+                    format!(
+                        "{} specified, but `{name}` is not an aggregate function",
+                        kw.to_string().to_uppercase()
+                    ),
+                );
+            } else if let Some(order_by) = &list.order_by {
+                return err::msg(
+                    order_by.order_by_kws.0.span(),
+                    format!("ORDER BY specified, but `{name}` is not an aggregate function"),
+                );
+            }
+        }
+        // `count(*)` is the only aggregate without arguments, and it can't be called without `*`.
+        match (name.as_str(), func.is_wildcard()) {
+            ("count", true) => return Ok(DataType::BigInt),
+            (_, true) => return err::msg(func, format!("function {name}(*) does not exist")),
+            ("count", false) if args.is_empty() => {
+                return err::msg(
+                    func,
+                    "count(*) must be used to call a parameterless aggregate function",
+                )
+            }
+            _ => {}
+        }
+        let mut types: Vec<_> = args.iter().map(|arg| self.analyse_operand(arg)).collect();
+        // `SUBSTRING(s FOR n)` is `substring(s, 1, n)`, the count is neither a start nor a pattern.
+        if let FunctionArgs::Substring(SubstringArgs {
+            start: None,
+            count: Some(count),
+            ..
+        }) = &func.args
+        {
+            err::expect_integer(&types[1], count)?;
+            args.insert(1, count);
+            types.insert(1, DataType::Integer);
+        }
+        let (overload, ty) = match catalog::resolve(overloads, &types) {
+            Ok(resolved) => resolved,
+            Err(err) => return self.resolve_error(func, &args, &types, err),
+        };
+        // `SUBSTRING(<string> FROM <pattern>)` extracts the part matching a regex.
+        match (name.as_str(), overload.params.get(1)) {
+            ("regexp_replace", _) | ("substring", Some(ParamType::Type(DataType::Text))) => {
+                pattern::check_regex(args[1])?
+            }
+            _ => {}
+        }
         Ok(ty)
     }
 
-    fn get_bitwise_ty(&mut self, e: &OrExpr) -> Result<DataType, AnalyseError> {
-        let ty = self.analyse_or_expr(e)?;
-        match ty {
-            DataType::TINYINT | DataType::SmallInt | DataType::Integer | DataType::BigInt => Ok(ty),
-            _ => err::msg(e,  format!("arguments should have TINYINT, SMALLINT, INTEGER, BIGINT, BINARY, or BINARY VARYING data type, but got: {ty:?}"))
+    /// Reports why a call doesn't resolve, the error of each argument that doesn't fit if there
+    /// is a single overload to blame.
+    fn resolve_error(
+        &mut self,
+        func: &Function,
+        args: &[&Expr],
+        types: &[DataType],
+        err: ResolveError,
+    ) -> Result<DataType, AnalyseError> {
+        let name = func.name();
+        let signature = || {
+            let types: Vec<_> = types.iter().map(|ty| format!("`{ty:?}`")).collect();
+            format!("{name}({})", types.join(", "))
+        };
+        match err {
+            ResolveError::NotFound => err::msg(
+                func,
+                format!(
+                    "function `{name}` does not exist \nsuggest: {}",
+                    suggest(
+                        &name,
+                        self.schema
+                            .functions
+                            .names()
+                            .chain(Catalog::builtins().names())
+                    )
+                ),
+            ),
+            ResolveError::Arity { min, max } => {
+                let expected = match (min, max) {
+                    (1, usize::MAX) => "at least 1 argument".to_string(),
+                    (min, usize::MAX) => format!("at least {min} arguments"),
+                    (1, 1) => "1 argument".to_string(),
+                    (min, max) if min == max => format!("{min} arguments"),
+                    (min, max) => format!("{min} to {max} arguments"),
+                };
+                err::msg(func, format!("function `{name}` requires {expected}"))
+            }
+            ResolveError::Args(mismatches) => {
+                let mut errs: Vec<_> = mismatches
+                    .into_iter()
+                    .map(|(i, expected)| {
+                        let ty = &types[i];
+                        (
+                            args[i].span(),
+                            format!("expected `{expected}` type, found `{ty:?}`"),
+                        )
+                    })
+                    .collect();
+                let last = errs.pop();
+                errs.into_iter().for_each(|err| self.push_err(err));
+                match last {
+                    Some(err) => Err(err),
+                    None => Ok(DataType::Error),
+                }
+            }
+            ResolveError::NoMatch => {
+                err::msg(func, format!("function {} does not exist", signature()))
+            }
+            ResolveError::Ambiguous => {
+                err::msg(func, format!("function {} is not unique", signature()))
+            }
+        }
+    }
+
+    /// Postgres expands set-returning functions in the select list only, into a row per value.
+    pub fn expect_no_set_returning(&self, expr: &Expr, clause: &str) -> Result<(), AnalyseError> {
+        let returns_set = |call: &FunctionCall| {
+            self.overloads(&call.function.name())
+                .iter()
+                .any(|overload| overload.returns_set)
+        };
+        if let Some(call) = grouping::find_call(expr, &returns_set) {
+            return err::msg(
+                call,
+                format!("set-returning functions are not allowed in {clause}"),
+            );
+        }
+        Ok(())
+    }
+
+    fn get_window(&self, name: &Name) -> Result<&'t WindowSpec, AnalyseError> {
+        let value = name.value(self.dialect);
+        match self.windows.iter().find(|(n, _)| *n == value) {
+            Some((_, spec)) => Ok(spec),
+            None => err::msg(name, format!("window `{value}` does not exist")),
+        }
+    }
+
+    /// Ref: https://www.postgresql.org/docs/current/sql-select.html#SQL-WINDOW
+    pub fn analyse_window_spec(&mut self, spec: &WindowSpec) -> Result<(), AnalyseError> {
+        let mut order_by_len = spec.order_by.as_ref().map_or(0, |o| o.sort_specs.len());
+        if let Some(name) = &spec.existing_window_name {
+            let existing = self.get_window(name)?;
+            if let Some(partition_by) = &spec.partition_by {
+                return err::msg(
+                    partition_by.partition_by_kws.0.span(),
+                    format!("cannot override PARTITION BY clause of window `{name}`"),
+                );
+            }
+            match (&spec.order_by, &existing.order_by) {
+                (Some(order_by), Some(_)) => {
+                    return err::msg(
+                        order_by.order_by_kws.0.span(),
+                        format!("cannot override ORDER BY clause of window `{name}`"),
+                    )
+                }
+                (None, Some(order_by)) => order_by_len = order_by.sort_specs.len(),
+                _ => {}
+            }
+            if existing.frame.is_some() {
+                return err::msg(
+                    name,
+                    format!("cannot copy window `{name}` because it has a frame clause"),
+                );
+            }
+        }
+        if let Some(partition_by) = &spec.partition_by {
+            for expr in partition_by.exprs.iter() {
+                self.analyse_operand(expr);
+            }
+        }
+        if let Some(order_by) = &spec.order_by {
+            for sort_spec in order_by.sort_specs.iter() {
+                self.analyse_operand(&sort_spec.expr);
+            }
+        }
+        let Some(frame) = &spec.frame else {
+            return Ok(());
+        };
+        if frame.units == FrameUnits::Groups && order_by_len == 0 {
+            return err::msg(
+                frame.units_kw.span(),
+                "GROUPS mode requires an ORDER BY clause",
+            );
         }
+        for bound in std::iter::once(&frame.start).chain(&frame.end) {
+            let (FrameBound::Preceding(offset) | FrameBound::Following(offset)) = bound else {
+                continue;
+            };
+            let ty = self.analyse_operand(offset);
+            match frame.units {
+                FrameUnits::Rows | FrameUnits::Groups => err::expect_integer(&ty, offset)?,
+                FrameUnits::Range if order_by_len != 1 => return err::msg(
+                    offset,
+                    "RANGE with offset PRECEDING/FOLLOWING requires exactly one ORDER BY column",
+                ),
+                FrameUnits::Range => {}
+            }
+        }
+        Ok(())
+    }
+
+    fn analyse_case(&mut self, case: &Case) -> Result<DataType, AnalyseError> {
+        let operand = case
+            .operand
+            .as_ref()
+            .map(|operand| self.analyse_operand(operand));
+        for clause in &case.when_clauses {
+            let ty = self.analyse_operand(&clause.condition);
+            match &operand {
+                Some(operand) => expect_comparable(&clause.condition, "=", operand, &ty)?,
+                None => err::expect_bool(&ty, &clause.condition)?,
+            }
+        }
+        let mut ty = DataType::Unknown;
+        for result in case.results() {
+            let rest = self.analyse_operand(result);
+            ty = match ty.unify(&rest) {
+                Some(ty) => ty,
+                None => {
+                    return err::msg(
+                        result,
+                        format!("CASE types `{ty:?}` and `{rest:?}` cannot be matched"),
+                    )
+                }
+            };
+        }
+        // Postgres resolves a CASE of only untyped literals (e.g. `NULL`) to text.
+        Ok(match ty {
+            DataType::Unknown => DataType::Text,
+            ty => ty,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{analyse, columns};
+    use crate::DataType;
+
+    #[test]
+    fn operators() {
+        let types = |sql| -> Vec<DataType> { columns(sql).into_iter().map(|c| c.1).collect() };
+        assert_eq!(
+            types("SELECT id - 1 - 2, 10 / 2 / 5, -age, 2 ^ 3, id & 3 << 1, id + 1.5 FROM users"),
+            [
+                DataType::Integer,
+                DataType::Integer,
+                DataType::SmallInt,
+                DataType::DoublePrecision,
+                DataType::Integer,
+                DataType::Numeric,
+            ]
+        );
+        assert_eq!(
+            types(
+                r#"SELECT ARRAY[1] || 2, DATE "2024-01-01" + 1, TIMESTAMP "2024-01-02" - TIMESTAMP "2024-01-01""#
+            ),
+            [
+                DataType::Array {
+                    ty: Box::new(DataType::Integer)
+                },
+                DataType::Date,
+                DataType::Interval,
+            ]
+        );
+
+        let errs = analyse("SELECT -name FROM users");
+        assert_eq!(errs, ["expected `numeric` type, found `Text`"]);
+
+        let errs = analyse("SELECT name & 1 FROM users");
+        assert_eq!(errs, ["expected `integer` type, found `Text`"]);
+
+        let errs = analyse("SELECT TRUE + 1");
+        assert_eq!(errs, ["expected `numeric` type, found `Boolean`"]);
+
+        let errs = analyse(r#"SELECT DATE "2024-01-01" * 2"#);
+        assert_eq!(errs, ["operator does not exist: `Date` * `Integer`"]);
+    }
+
+    #[test]
+    fn predicates() {
+        assert!(analyse(
+            r#"SELECT id FROM users WHERE name IS NOT NULL AND age BETWEEN 18 AND 60
+                AND id NOT IN (1, 2, 3) AND email IN ("a", "b") AND id <= 10"#
+        )
+        .is_empty());
+        assert!(analyse("SELECT id FROM posts WHERE (score > 1) IS NOT TRUE").is_empty());
+        assert!(analyse("SELECT id FROM posts WHERE score IS DISTINCT FROM NULL").is_empty());
+
+        let errs = analyse(r#"SELECT id FROM users WHERE id IN (1, "a")"#);
+        assert_eq!(errs, ["IN types `Integer` and `Text` cannot be matched"]);
+
+        let errs = analyse(r#"SELECT id FROM users WHERE age BETWEEN 1 AND "a""#);
+        assert_eq!(errs, ["operator does not exist: `SmallInt` >= `Text`"]);
+
+        let errs = analyse("SELECT id FROM users WHERE name = id");
+        assert_eq!(errs, ["operator does not exist: `Text` = `Integer`"]);
+
+        let errs = analyse("SELECT id FROM users WHERE age IS TRUE");
+        assert_eq!(errs, ["expected `boolean` type, found `SmallInt`"]);
+    }
+
+    #[test]
+    fn recovery() {
+        let errs = analyse(r#"SELECT id FROM users WHERE id = "x" AND age = 1 AND name = TRUE"#);
+        assert_eq!(
+            errs,
+            [
+                "operator does not exist: `Integer` = `Text`",
+                "operator does not exist: `Text` = `Boolean`",
+            ]
+        );
+
+        let errs = analyse(r#"SELECT LPAD(age, "a"), -(name + 1), id FROM users"#);
+        assert_eq!(
+            errs,
+            [
+                "expected `text` type, found `SmallInt`",
+                "expected `integer` type, found `Text`",
+                "expected `numeric` type, found `Text`",
+            ]
+        );
+    }
+
+    #[test]
+    fn pattern_matching() {
+        assert!(analyse(
+            r#"SELECT id FROM users WHERE name LIKE "a%" AND email NOT ILIKE "%!_x" ESCAPE "!"
+                AND name SIMILAR TO "(a|b)%" AND email ~* "^[a-z]+@(x|y)\\.com$" AND name !~ email"#
+        )
+        .is_empty());
+
+        let errs = analyse(r#"SELECT id FROM users WHERE age LIKE "1%""#);
+        assert_eq!(errs, ["expected `text` type, found `SmallInt`"]);
+
+        let errs = analyse(r#"SELECT id FROM users WHERE name ~ "(a|b""#);
+        assert_eq!(errs, ["invalid regular expression: unclosed group"]);
+
+        let errs = analyse(r#"SELECT id FROM users WHERE name LIKE "a\\""#);
+        assert_eq!(errs, ["LIKE pattern must not end with escape character"]);
+
+        let errs = analyse(r#"SELECT id FROM users WHERE name LIKE "a" ESCAPE "ab""#);
+        assert_eq!(
+            errs,
+            ["invalid escape string, escape string must be empty or one character"]
+        );
+
+        let errs = analyse(r#"SELECT id FROM users WHERE name SIMILAR TO "(a|b%""#);
+        assert_eq!(errs, ["invalid regular expression: unclosed group"]);
+
+        let errs = analyse(r#"SELECT id FROM users WHERE name SIMILAR TO "%[ab""#);
+        assert_eq!(
+            errs,
+            ["invalid regular expression: unclosed character class"]
+        );
+
+        // Escaped, `(` is a plain char.
+        assert!(
+            analyse(r##"SELECT id FROM users WHERE name SIMILAR TO "#(a%" ESCAPE "#""##).is_empty()
+        );
+        assert!(analyse(r#"SELECT id FROM users WHERE name SIMILAR TO "a.b$^%""#).is_empty());
+    }
+
+    #[test]
+    fn case() {
+        assert!(analyse(
+            r#"SELECT id FROM users WHERE CASE WHEN age > 18 THEN TRUE ELSE name IS NULL END"#
+        )
+        .is_empty());
+        assert!(
+            analyse(r#"SELECT CASE age WHEN 1 THEN 1.5 WHEN 2 THEN id END FROM users"#).is_empty()
+        );
+
+        let errs = analyse(r#"SELECT CASE WHEN age THEN 1 END FROM users"#);
+        assert_eq!(errs, ["expected `boolean` type, found `SmallInt`"]);
+
+        let errs = analyse(r#"SELECT CASE name WHEN 1 THEN 1 END FROM users"#);
+        assert_eq!(errs, ["operator does not exist: `Text` = `Integer`"]);
+
+        let errs = analyse(r#"SELECT CASE WHEN age > 1 THEN 1 ELSE "x" END FROM users"#);
+        assert_eq!(errs, ["CASE types `Integer` and `Text` cannot be matched"]);
+    }
+
+    #[test]
+    fn casts() {
+        assert_eq!(
+            analyse(
+                r#"SELECT id FROM users WHERE name::varchar(10) = email AND CAST(age AS bigint) > 1
+                AND "2024-01-01"::date < TIMESTAMP "2024-01-02" AND (age > 1)::int = 1
+                AND ARRAY[1, 2]::numeric[] IS NOT NULL AND pg_catalog.int4 "1" = 1"#
+            ),
+            Vec::<String>::new()
+        );
+
+        let errs = analyse("SELECT TRUE::date");
+        assert_eq!(errs, ["cannot cast type `Boolean` to `Date`"]);
+
+        let errs = analyse("SELECT age::mood FROM users");
+        assert_eq!(errs, ["type `mood` does not exist"]);
+
+        let errs = analyse("SELECT name::varchar(0) FROM users");
+        assert_eq!(
+            errs,
+            ["type modifier of `varchar` must be between 1 and 10485760"]
+        );
+
+        let errs = analyse("SELECT age::int(4) FROM users");
+        assert_eq!(errs, ["invalid type modifier for type `int`"]);
+
+        let errs = analyse("SELECT id FROM users WHERE age::int[] IS NULL");
+        assert_eq!(
+            errs,
+            ["cannot cast type `SmallInt` to `Array { ty: Integer }`"]
+        );
+    }
+
+    #[test]
+    fn window_functions() {
+        assert_eq!(
+            columns(
+                r#"SELECT ROW_NUMBER() OVER w, PERCENT_RANK() OVER (ORDER BY age),
+                    LAG(age, 1, 0) OVER (w ROWS BETWEEN 2 PRECEDING AND CURRENT ROW),
+                    NTH_VALUE(name, 2) OVER (PARTITION BY age ORDER BY id RANGE 1 PRECEDING EXCLUDE TIES)
+                FROM users WINDOW w AS (PARTITION BY email ORDER BY id)"#
+            ),
+            [
+                ("row_number".to_string(), DataType::BigInt, false),
+                ("percent_rank".to_string(), DataType::DoublePrecision, false),
+                ("lag".to_string(), DataType::Integer, true),
+                ("nth_value".to_string(), DataType::Text, true),
+            ]
+        );
+
+        let errs = analyse("SELECT RANK() FROM users");
+        assert_eq!(errs, ["window function `rank` requires an OVER clause"]);
+
+        let errs = analyse("SELECT ABS(age) OVER () FROM users");
+        assert_eq!(
+            errs,
+            ["OVER specified, but `abs` is not a window function nor an aggregate function"]
+        );
+
+        let errs = analyse("SELECT RANK() OVER w FROM users");
+        assert_eq!(errs, ["window `w` does not exist"]);
+
+        let errs = analyse(r#"SELECT LEAD(age, "a") OVER () FROM users"#);
+        assert_eq!(errs, ["expected `integer` type, found `Text`"]);
+
+        let errs = analyse("SELECT LAG() OVER () FROM users");
+        assert_eq!(errs, ["function `lag` requires 1 to 3 arguments"]);
+
+        let errs = analyse("SELECT RANK() OVER (ORDER BY id ROWS 1.5 PRECEDING) FROM users");
+        assert_eq!(errs, ["expected `integer` type, found `Numeric`"]);
+
+        let errs = analyse("SELECT RANK() OVER (ORDER BY id, age RANGE 1 PRECEDING) FROM users");
+        assert_eq!(
+            errs,
+            ["RANGE with offset PRECEDING/FOLLOWING requires exactly one ORDER BY column"]
+        );
+
+        let errs = analyse("SELECT RANK() OVER (GROUPS CURRENT ROW) FROM users");
+        assert_eq!(errs, ["GROUPS mode requires an ORDER BY clause"]);
+
+        let errs = analyse(
+            "SELECT RANK() OVER (w ORDER BY age) FROM users WINDOW w AS (ORDER BY id), w AS ()",
+        );
+        assert_eq!(
+            errs,
+            [
+                "window `w` is already defined",
+                "cannot override ORDER BY clause of window `w`"
+            ]
+        );
+    }
+
+    #[test]
+    fn aggregates() {
+        assert_eq!(
+            columns(
+                r#"SELECT COUNT(*), COUNT(DISTINCT age), SUM(age), SUM(id::bigint), AVG(age), AVG(1.5::real),
+                    MAX(name), STRING_AGG(name, ", " ORDER BY id), ARRAY_AGG(email), BOOL_AND(age > 18),
+                    SUM(age) FILTER (WHERE name IS NOT NULL)
+                FROM users"#
+            ),
+            [
+                ("count".to_string(), DataType::BigInt, false),
+                ("count".to_string(), DataType::BigInt, false),
+                ("sum".to_string(), DataType::BigInt, true),
+                ("sum".to_string(), DataType::Numeric, true),
+                ("avg".to_string(), DataType::Numeric, true),
+                ("avg".to_string(), DataType::DoublePrecision, true),
+                ("max".to_string(), DataType::Text, true),
+                ("string_agg".to_string(), DataType::Text, true),
+                (
+                    "array_agg".to_string(),
+                    DataType::Array {
+                        ty: Box::new(DataType::CharacterVarying)
+                    },
+                    true
+                ),
+                ("bool_and".to_string(), DataType::Boolean, true),
+                ("sum".to_string(), DataType::BigInt, true),
+            ]
+        );
+
+        let errs = analyse("SELECT SUM(name) FROM users");
+        assert_eq!(errs, ["function sum(`Text`) does not exist"]);
+
+        let errs = analyse("SELECT MAX(age > 1) FROM users");
+        assert_eq!(errs, ["function max(`Boolean`) does not exist"]);
+
+        let errs = analyse("SELECT AVG(*) FROM users");
+        assert_eq!(errs, ["function avg(*) does not exist"]);
+
+        let errs = analyse("SELECT STRING_AGG(name) FROM users");
+        assert_eq!(errs, ["function `string_agg` requires 2 arguments"]);
+
+        let errs = analyse("SELECT COUNT() FROM users");
+        assert_eq!(
+            errs,
+            ["count(*) must be used to call a parameterless aggregate function"]
+        );
+
+        let errs = analyse("SELECT SUM(*) FROM users");
+        assert_eq!(errs, ["function sum(*) does not exist"]);
+
+        let errs = analyse("SELECT SUM(COUNT(*)) FROM users");
+        assert_eq!(errs, ["aggregate function calls cannot be nested"]);
+
+        let errs = analyse("SELECT COUNT(*) FILTER (WHERE age) FROM users");
+        assert_eq!(errs, ["expected `boolean` type, found `SmallInt`"]);
+
+        let errs = analyse("SELECT ABS(age) FILTER (WHERE TRUE) FROM users");
+        assert_eq!(
+            errs,
+            ["FILTER specified, but `abs` is not an aggregate function"]
+        );
+    }
+
+    #[test]
+    fn overloads() {
+        assert_eq!(
+            columns(
+                r#"SELECT ROUND(1.5, 2), ROUND(age), LOG(id::numeric), LOG(2.0, 8), ABS(age),
+                    COALESCE(age, NULL, id), NOW(), MOD(id, 2::bigint)
+                FROM users"#
+            )
+            .into_iter()
+            .map(|c| c.1)
+            .collect::<Vec<_>>(),
+            [
+                DataType::Numeric,
+                DataType::DoublePrecision,
+                DataType::Numeric,
+                DataType::Numeric,
+                DataType::SmallInt,
+                DataType::Integer,
+                DataType::TimestampWithTimeZone,
+                DataType::BigInt,
+            ]
+        );
+
+        let errs = analyse("SELECT LOWR(name) FROM users");
+        assert_eq!(errs.len(), 1);
+        assert!(errs[0].starts_with("function `lowr` does not exist \nsuggest: `lower`"));
+
+        let (_, abs, _) = &columns("SELECT ABS(NULL)")[0];
+        assert_eq!(abs, &DataType::DoublePrecision);
+        let (_, round, _) = &columns("SELECT ROUND(NULL)")[0];
+        assert_eq!(round, &DataType::DoublePrecision);
+
+        let errs = analyse("SELECT ROUND(name, 2) FROM users");
+        assert_eq!(errs, ["expected `numeric` type, found `Text`"]);
+
+        let errs = analyse("SELECT ROUND(name) FROM users");
+        assert_eq!(errs, ["function round(`Text`) does not exist"]);
+
+        let errs = analyse(r#"SELECT NULLIF(id, "a"::text) FROM users"#);
+        assert_eq!(errs, ["function nullif(`Integer`, `Text`) does not exist"]);
+
+        let errs = analyse("SELECT CONCAT() FROM users");
+        assert_eq!(errs, ["function `concat` requires at least 1 argument"]);
+
+        let errs = analyse("SELECT LOWER(DISTINCT name) FROM users");
+        assert_eq!(
+            errs,
+            ["DISTINCT specified, but `lower` is not an aggregate function"]
+        );
+    }
+
+    #[test]
+    fn user_functions() {
+        assert_eq!(
+            columns(
+                r#"SELECT full_name(name), full_name(name, email), similarity(name, "x"), active_user_ids()
+                FROM users"#
+            )
+            .into_iter()
+            .map(|c| c.1)
+            .collect::<Vec<_>>(),
+            [
+                DataType::Text,
+                DataType::Text,
+                DataType::Real,
+                DataType::Integer,
+            ]
+        );
+
+        let errs = analyse("SELECT full_name(id) FROM users");
+        assert_eq!(errs, ["expected `text` type, found `Integer`"]);
+
+        let errs = analyse("SELECT full_nam(name) FROM users");
+        assert_eq!(errs.len(), 1);
+        assert!(errs[0].starts_with("function `full_nam` does not exist \nsuggest: `full_name`"));
+
+        let errs = analyse("SELECT transfer(1, 2)");
+        assert_eq!(errs, ["`transfer` is a procedure, use CALL to call it"]);
+
+        let errs = analyse("SELECT id FROM users WHERE id = active_user_ids()");
+        assert_eq!(errs, ["set-returning functions are not allowed in WHERE"]);
+    }
+
+    #[test]
+    fn string_functions() {
+        assert_eq!(
+            columns(
+                r#"SELECT LOWER(email), LENGTH(name), CONCAT(name, id, NULL), CONCAT_WS(", ", name, age),
+                    SUBSTRING(name FROM 2 FOR 3), SUBSTRING(email FROM "@(.*)$"), TRIM(BOTH "x" FROM name),
+                    POSITION("@" IN email), REPLACE(name, "a", "b"), LPAD(name, 10, "*"),
+                    SPLIT_PART(email, "@", 2), REGEXP_REPLACE(name, "\\s+", " ", "g"), LEFT(UPPER(name), 1)
+                FROM users"#
+            )
+            .into_iter()
+            .map(|c| c.1)
+            .collect::<Vec<_>>(),
+            [
+                DataType::Text,
+                DataType::Integer,
+                DataType::Text,
+                DataType::Text,
+                DataType::Text,
+                DataType::Text,
+                DataType::Text,
+                DataType::Integer,
+                DataType::Text,
+                DataType::Text,
+                DataType::Text,
+                DataType::Text,
+                DataType::Text,
+            ]
+        );
+
+        let errs = analyse("SELECT LOWER(age) FROM users");
+        assert_eq!(errs, ["expected `text` type, found `SmallInt`"]);
+
+        let errs = analyse(r#"SELECT SUBSTRING(name FROM "a" FOR 2) FROM users"#);
+        assert_eq!(errs, ["expected `integer` type, found `Text`"]);
+
+        // `FOR` alone is the count from the first char, not the start.
+        assert_eq!(
+            columns("SELECT SUBSTRING(name FOR 2), SUBSTR(name, 1, 2) FROM users")
+                .into_iter()
+                .map(|c| c.1)
+                .collect::<Vec<_>>(),
+            [DataType::Text, DataType::Text]
+        );
+        let errs = analyse(r#"SELECT SUBSTRING(name FOR "x") FROM users"#);
+        assert_eq!(errs, ["expected `integer` type, found `Text`"]);
+
+        let errs = analyse(r#"SELECT LPAD(name) FROM users"#);
+        assert_eq!(errs, ["function `lpad` requires 2 to 3 arguments"]);
+
+        let errs = analyse(r#"SELECT REGEXP_REPLACE(name, "(a", "b") FROM users"#);
+        assert_eq!(errs, ["invalid regular expression: unclosed group"]);
+
+        let errs = analyse("SELECT POSITION(1 IN name) FROM users");
+        assert_eq!(errs, ["expected `text` type, found `Integer`"]);
     }
 }
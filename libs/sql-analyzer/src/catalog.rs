@@ -0,0 +1,760 @@
+//! Functions by name, with the overloads of each, resolved the way Postgres does.
+//!
+//! Ref: https://www.postgresql.org/docs/current/typeconv-func.html
+use crate::schema_info::{CastContext, DataType};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::sync::OnceLock;
+
+/// One signature per line, see [`parse_signature`]. A trailing `for <type>, ..` repeats the line
+/// with `T` standing for each of the types.
+const BUILTINS: &str = r#"
+# Ref: https://www.postgresql.org/docs/current/functions-math.html
+abs(T) -> T for smallint, integer, bigint, real, double precision, numeric
+ceil(T) -> T for double precision, numeric
+ceiling(T) -> T for double precision, numeric
+floor(T) -> T for double precision, numeric
+round(T) -> T for double precision, numeric
+round(numeric, integer) -> numeric
+trunc(T) -> T for double precision, numeric
+trunc(numeric, integer) -> numeric
+sign(T) -> T for double precision, numeric
+mod(T, T) -> T for smallint, integer, bigint, numeric
+sqrt(T) -> T for double precision, numeric
+exp(T) -> T for double precision, numeric
+ln(T) -> T for double precision, numeric
+log(T) -> T for double precision, numeric
+log(numeric, numeric) -> numeric
+log10(T) -> T for double precision, numeric
+power(T, T) -> T for double precision, numeric
+degrees(double precision) -> double precision
+radians(double precision) -> double precision
+pi() -> double precision
+volatile random() -> double precision
+acos(double precision) -> double precision
+asin(double precision) -> double precision
+atan(double precision) -> double precision
+atan2(double precision, double precision) -> double precision
+cos(double precision) -> double precision
+cosh(double precision) -> double precision
+cot(double precision) -> double precision
+sin(double precision) -> double precision
+sinh(double precision) -> double precision
+tan(double precision) -> double precision
+tanh(double precision) -> double precision
+
+# Ref: https://www.h2database.com/html/functions.html#bitand
+bitand(T, T) -> T for tinyint, smallint, integer, bigint
+bitor(T, T) -> T for tinyint, smallint, integer, bigint
+bitxor(T, T) -> T for tinyint, smallint, integer, bigint
+bitnand(T, T) -> T for tinyint, smallint, integer, bigint
+bitnor(T, T) -> T for tinyint, smallint, integer, bigint
+bitxnor(T, T) -> T for tinyint, smallint, integer, bigint
+bitnot(T) -> T for tinyint, smallint, integer, bigint
+bitget(bigint, integer) -> boolean
+bitcount(bigint) -> bigint
+lshift(T, integer) -> T for tinyint, smallint, integer, bigint
+rshift(T, integer) -> T for tinyint, smallint, integer, bigint
+ulshift(T, integer) -> T for tinyint, smallint, integer, bigint
+urshift(T, integer) -> T for tinyint, smallint, integer, bigint
+rotateleft(T, integer) -> T for tinyint, smallint, integer, bigint
+rotateright(T, integer) -> T for tinyint, smallint, integer, bigint
+
+# Ref: https://www.postgresql.org/docs/current/functions-string.html
+ascii(text) -> integer
+char_length(text) -> integer
+character_length(text) -> integer
+length(text) -> integer
+concat(variadic "any") -> text
+concat_ws(text, variadic "any") -> text
+lower(text) -> text
+lcase(text) -> text
+upper(text) -> text
+ucase(text) -> text
+left(text, integer) -> text
+right(text, integer) -> text
+lpad(text, integer) -> text
+lpad(text, integer, text) -> text
+rpad(text, integer) -> text
+rpad(text, integer, text) -> text
+ltrim(text) -> text
+ltrim(text, text) -> text
+rtrim(text) -> text
+rtrim(text, text) -> text
+btrim(text) -> text
+btrim(text, text) -> text
+trim(text) -> text
+trim(text, text) -> text
+position(text, text) -> integer
+strpos(text, text) -> integer
+regexp_replace(text, text, text) -> text
+regexp_replace(text, text, text, text) -> text
+replace(text, text, text) -> text
+repeat(text, integer) -> text
+reverse(text) -> text
+space(integer) -> text
+split_part(text, text, integer) -> text
+substring(text, integer) -> text
+substring(text, integer, integer) -> text
+substring(text, text) -> text
+substr(text, integer) -> text
+substr(text, integer, integer) -> text
+md5(text) -> text
+
+# Ref: https://www.postgresql.org/docs/current/functions-datetime.html
+stable now() -> timestamptz
+volatile clock_timestamp() -> timestamptz
+date_trunc(text, T) -> T for timestamp, timestamptz, interval
+age(timestamp, timestamp) -> interval
+age(timestamptz, timestamptz) -> interval
+
+# Ref: https://www.postgresql.org/docs/current/functions-conditional.html
+coalesce(variadic anycompatible) -> anycompatible
+nullif(anycompatible, anycompatible) -> anycompatible
+greatest(variadic anycompatible) -> anycompatible
+least(variadic anycompatible) -> anycompatible
+
+volatile gen_random_uuid() -> uuid
+
+# Ref: https://www.postgresql.org/docs/current/functions-array.html
+array_append(anycompatiblearray, anycompatible) -> anycompatiblearray
+array_prepend(anycompatible, anycompatiblearray) -> anycompatiblearray
+array_cat(anycompatiblearray, anycompatiblearray) -> anycompatiblearray
+array_length(anyarray, integer) -> integer
+
+# Ref: https://www.postgresql.org/docs/current/functions-srf.html
+generate_series(integer, integer) -> setof integer
+generate_series(integer, integer, integer) -> setof integer
+generate_series(bigint, bigint) -> setof bigint
+generate_series(bigint, bigint, bigint) -> setof bigint
+generate_series(numeric, numeric) -> setof numeric
+generate_series(numeric, numeric, numeric) -> setof numeric
+stable generate_series(timestamptz, timestamptz, interval) -> setof timestamptz
+unnest(anyarray) -> setof anyelement
+
+# Ref: https://www.postgresql.org/docs/current/functions-window.html
+window row_number() -> bigint
+window rank() -> bigint
+window dense_rank() -> bigint
+window percent_rank() -> double precision
+window cume_dist() -> double precision
+window ntile(integer) -> integer
+window lag(anyelement) -> anyelement
+window lag(anyelement, integer) -> anyelement
+window lag(anycompatible, integer, anycompatible) -> anycompatible
+window lead(anyelement) -> anyelement
+window lead(anyelement, integer) -> anyelement
+window lead(anycompatible, integer, anycompatible) -> anycompatible
+window first_value(anyelement) -> anyelement
+window last_value(anyelement) -> anyelement
+window nth_value(anyelement, integer) -> anyelement
+
+# Ref: https://www.postgresql.org/docs/current/functions-aggregate.html
+aggregate count("any") -> bigint
+aggregate sum(smallint) -> bigint
+aggregate sum(integer) -> bigint
+aggregate sum(bigint) -> numeric
+aggregate sum(T) -> T for numeric, real, double precision, interval
+aggregate avg(T) -> numeric for smallint, integer, bigint, numeric
+aggregate avg(T) -> double precision for real, double precision
+aggregate avg(interval) -> interval
+aggregate min(T) -> T for smallint, integer, bigint, real, double precision, numeric, text, date, time, timestamp, timestamptz, interval
+aggregate max(T) -> T for smallint, integer, bigint, real, double precision, numeric, text, date, time, timestamp, timestamptz, interval
+aggregate string_agg(text, text) -> text
+aggregate string_agg(bytea, bytea) -> bytea
+aggregate array_agg(anynonarray) -> anyarray
+aggregate bool_and(boolean) -> boolean
+aggregate bool_or(boolean) -> boolean
+aggregate every(boolean) -> boolean
+"#;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FunctionKind {
+    Scalar,
+    Aggregate,
+    Window,
+    /// Called with `CALL`, rather than in an expression.
+    Procedure,
+}
+
+/// Ref: https://www.postgresql.org/docs/current/xfunc-volatility.html
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Volatility {
+    Immutable,
+    Stable,
+    Volatile,
+}
+
+/// Type of a parameter or of the result of an overload.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ParamType {
+    Type(DataType),
+    /// `"any"`, a value of any type, as is.
+    Any,
+    /// `anyelement`, the same type at every `anyelement` and within every `anyarray`.
+    AnyElement,
+    /// `anyenum`, an `anyelement` that is an enum.
+    AnyEnum,
+    /// `anyarray`, an array of the `anyelement` type.
+    AnyArray,
+    /// `anycompatible`, the common type of the arguments at every `anycompatible`.
+    AnyCompatible,
+    /// `anycompatiblearray`, an array of the `anycompatible` type.
+    AnyCompatibleArray,
+}
+
+#[derive(Debug, Clone)]
+pub struct Overload {
+    pub name: String,
+    pub kind: FunctionKind,
+    pub params: Vec<ParamType>,
+    /// The last parameter takes one or more arguments.
+    pub variadic: bool,
+    /// Number of trailing parameters with a default value, which may be left out.
+    pub defaults: usize,
+    pub result: ParamType,
+    /// `SETOF <result>`, a row per value.
+    pub returns_set: bool,
+    pub volatility: Volatility,
+}
+
+/// Why a call doesn't resolve to a single overload.
+#[derive(Debug, PartialEq)]
+pub enum ResolveError {
+    /// No function of that name.
+    NotFound,
+    /// No overload takes that many arguments.
+    Arity { min: usize, max: usize },
+    /// The only overload with that many arguments doesn't accept the arguments at these
+    /// indexes, which expect these types.
+    Args(Vec<(usize, ParamType)>),
+    /// Several overloads take that many arguments, none of them these types.
+    NoMatch,
+    /// More than one overload fits equally well.
+    Ambiguous,
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct Catalog {
+    functions: BTreeMap<String, Vec<Overload>>,
+}
+
+impl Catalog {
+    /// Functions every database has.
+    pub fn builtins() -> &'static Catalog {
+        static BUILTIN: OnceLock<Catalog> = OnceLock::new();
+        BUILTIN.get_or_init(|| {
+            let mut catalog = Catalog::default();
+            for line in BUILTINS.lines().map(str::trim) {
+                if line.is_empty() || line.starts_with('#') {
+                    continue;
+                }
+                for overload in parse_signatures(line).unwrap_or_else(|err| panic!("{err}")) {
+                    catalog.insert(overload);
+                }
+            }
+            catalog
+        })
+    }
+
+    pub fn insert(&mut self, overload: Overload) {
+        self.functions
+            .entry(overload.name.clone())
+            .or_default()
+            .push(overload);
+    }
+
+    /// Overloads of the function, by its lower case name.
+    pub fn get(&self, name: &str) -> &[Overload] {
+        self.functions.get(name).map_or(&[], Vec::as_slice)
+    }
+
+    pub fn names(&self) -> impl Iterator<Item = &String> + Clone {
+        self.functions.keys()
+    }
+
+    pub fn kind(&self, name: &str) -> Option<FunctionKind> {
+        self.get(name).first().map(|overload| overload.kind)
+    }
+
+    /// The overload a call with arguments of `args` types resolves to, with the type it returns.
+    pub fn resolve(
+        &self,
+        name: &str,
+        args: &[DataType],
+    ) -> Result<(&Overload, DataType), ResolveError> {
+        let overloads: Vec<_> = self.get(name).iter().collect();
+        resolve(&overloads, args)
+    }
+}
+
+/// The one of `overloads` of a function a call with arguments of `args` types resolves to, with
+/// the type it returns.
+///
+/// An argument of `DataType::Error` fits any parameter, its error is already reported.
+pub fn resolve<'o>(
+    overloads: &[&'o Overload],
+    args: &[DataType],
+) -> Result<(&'o Overload, DataType), ResolveError> {
+    {
+        if overloads.is_empty() {
+            return Err(ResolveError::NotFound);
+        }
+        let candidates: Vec<_> = overloads
+            .iter()
+            .filter(|overload| overload.takes(args.len()))
+            .collect();
+        if candidates.is_empty() {
+            let min = overloads
+                .iter()
+                .map(|o| o.params.len() - o.defaults)
+                .min()
+                .unwrap_or(0);
+            let max = match overloads.iter().any(|o| o.variadic) {
+                true => usize::MAX,
+                false => overloads.iter().map(|o| o.params.len()).max().unwrap_or(0),
+            };
+            return Err(ResolveError::Arity { min, max });
+        }
+        let mut matching: Vec<_> = candidates
+            .iter()
+            .filter_map(|&overload| Some((overload, overload.bind(args)?)))
+            .collect();
+        if matching.is_empty() {
+            let mismatches: Vec<_> = match candidates.as_slice() {
+                [overload] => (0..args.len())
+                    .filter(|&i| !overload.accepts(i, &args[i]))
+                    .map(|i| (i, overload.param(i).clone()))
+                    .collect(),
+                _ => vec![],
+            };
+            // Arguments that fit on their own may still not share a polymorphic type.
+            return match mismatches.is_empty() {
+                true => Err(ResolveError::NoMatch),
+                false => Err(ResolveError::Args(mismatches)),
+            };
+        }
+
+        // Keep the overloads that fit best: the most exact matches, then the most preferred
+        // types where a conversion is needed.
+        let rules: [&dyn Fn(&Overload) -> usize; 2] = [
+            &|o| {
+                (0..args.len())
+                    .filter(|&i| *o.param(i) == ParamType::Type(args[i].clone()))
+                    .count()
+            },
+            &|o| {
+                (0..args.len())
+                    .filter(|&i| !args[i].is_unknown() && !args[i].is_error())
+                    .filter(|&i| matches!(o.param(i), ParamType::Type(ty) if ty.is_preferred()))
+                    .count()
+            },
+        ];
+        for rule in rules {
+            let best = matching.iter().map(|(o, _)| rule(o)).max().unwrap_or(0);
+            matching.retain(|(o, _)| rule(o) == best);
+        }
+        // An untyped literal takes the string category if an overload accepts one there, else
+        // the category all overloads agree on, and then the preferred type of that category.
+        for i in (0..args.len()).filter(|&i| args[i].is_unknown()) {
+            let category = |o: &Overload| match o.param(i) {
+                ParamType::Type(ty) => Some(ty.category()),
+                _ => None,
+            };
+            let is_preferred =
+                |o: &Overload| matches!(o.param(i), ParamType::Type(ty) if ty.is_preferred());
+            let categories: Vec<_> = matching.iter().map(|(o, _)| category(o)).collect();
+            let chosen = match categories.contains(&Some('S')) {
+                true => Some('S'),
+                false if categories.iter().all(|c| *c == categories[0]) => categories[0],
+                false => continue,
+            };
+            matching.retain(|(o, _)| category(o) == chosen);
+            if matching.iter().any(|(o, _)| is_preferred(o)) {
+                matching.retain(|(o, _)| is_preferred(o));
+            }
+        }
+        match matching.as_slice() {
+            [(overload, ty)] => Ok((overload, ty.clone())),
+            // Which one is meant depends on the type of an argument that failed to analyse.
+            [(overload, _), ..] if args.iter().any(DataType::is_error) => {
+                Ok((overload, DataType::Error))
+            }
+            _ => Err(ResolveError::Ambiguous),
+        }
+    }
+}
+
+impl Overload {
+    fn takes(&self, count: usize) -> bool {
+        count + self.defaults >= self.params.len() && (self.variadic || count <= self.params.len())
+    }
+
+    /// Parameter of the argument at `index`, a variadic one repeats.
+    pub fn param(&self, index: usize) -> &ParamType {
+        &self.params[index.min(self.params.len() - 1)]
+    }
+
+    /// Whether the argument at `index` can be passed on its own, ignoring polymorphic types.
+    fn accepts(&self, index: usize, arg: &DataType) -> bool {
+        match self.param(index) {
+            ParamType::Type(ty) => {
+                arg.is_error() || arg.cast_context(ty) == Some(CastContext::Implicit)
+            }
+            ParamType::AnyArray | ParamType::AnyCompatibleArray => {
+                matches!(arg, DataType::Array { .. }) || arg.is_unknown() || arg.is_error()
+            }
+            // Enums are among the user defined types, which aren't told apart.
+            ParamType::AnyEnum => {
+                matches!(arg, DataType::UserDefined { .. }) || arg.is_unknown() || arg.is_error()
+            }
+            _ => true,
+        }
+    }
+
+    /// The result type for arguments of `args` types, `None` if they don't fit the parameters.
+    fn bind(&self, args: &[DataType]) -> Option<DataType> {
+        let mut element: Option<DataType> = None;
+        let mut compatible = DataType::Unknown;
+        let mut is_poisoned = false;
+        for (i, arg) in args.iter().enumerate() {
+            if !self.accepts(i, arg) {
+                return None;
+            }
+            let arg = match (self.param(i), arg) {
+                (_, DataType::Error) => {
+                    is_poisoned = true;
+                    continue;
+                }
+                (ParamType::AnyArray | ParamType::AnyCompatibleArray, DataType::Array { ty }) => {
+                    ty.as_ref()
+                }
+                (ParamType::Type(_) | ParamType::Any, _) => continue,
+                (_, arg) => arg,
+            };
+            if arg.is_unknown() {
+                continue;
+            }
+            match self.param(i) {
+                ParamType::AnyCompatible | ParamType::AnyCompatibleArray => {
+                    compatible = compatible.unify(arg)?
+                }
+                _ => match &element {
+                    Some(ty) if ty != arg => return None,
+                    _ => element = Some(arg.clone()),
+                },
+            }
+        }
+        // Untyped literals alone resolve to `text`.
+        let element = element.unwrap_or(DataType::Text);
+        if compatible.is_unknown() {
+            compatible = DataType::Text;
+        }
+        Some(match &self.result {
+            ParamType::Type(ty) => ty.clone(),
+            _ if is_poisoned => DataType::Error,
+            ParamType::AnyElement | ParamType::AnyEnum | ParamType::Any => element,
+            ParamType::AnyArray => DataType::Array {
+                ty: Box::new(element),
+            },
+            ParamType::AnyCompatible => compatible,
+            ParamType::AnyCompatibleArray => DataType::Array {
+                ty: Box::new(compatible),
+            },
+        })
+    }
+}
+
+impl ParamType {
+    /// Type for a name as `format_type` gives it. A name that isn't a built-in type is a user
+    /// defined one, or a pseudo type such as `void` or `record`.
+    ///
+    /// Range types aren't supported, nor are the polymorphic types that stand for them.
+    pub fn parse(name: &str) -> Result<Self, String> {
+        Ok(match name {
+            "\"any\"" => Self::Any,
+            "anyelement" | "anynonarray" => Self::AnyElement,
+            "anyenum" => Self::AnyEnum,
+            "anyarray" => Self::AnyArray,
+            "anycompatible" | "anycompatiblenonarray" => Self::AnyCompatible,
+            "anycompatiblearray" => Self::AnyCompatibleArray,
+            "anyrange" | "anymultirange" | "anycompatiblerange" | "anycompatiblemultirange" => {
+                return Err(format!("unsupported type `{name}`"))
+            }
+            _ => match name.strip_suffix("[]") {
+                Some(element) => match Self::parse(element)? {
+                    Self::Type(ty) => Self::Type(DataType::Array { ty: Box::new(ty) }),
+                    _ => return Err(format!("unsupported type `{name}`")),
+                },
+                None => Self::Type(DataType::from_sql_name(name).unwrap_or(
+                    DataType::UserDefined {
+                        name: name.to_string(),
+                    },
+                )),
+            },
+        })
+    }
+}
+
+impl fmt::Display for ParamType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Type(ty) => f.write_str(&ty.sql_name()),
+            Self::Any => f.write_str("\"any\""),
+            Self::AnyElement => f.write_str("anyelement"),
+            Self::AnyEnum => f.write_str("anyenum"),
+            Self::AnyArray => f.write_str("anyarray"),
+            Self::AnyCompatible => f.write_str("anycompatible"),
+            Self::AnyCompatibleArray => f.write_str("anycompatiblearray"),
+        }
+    }
+}
+
+/// The overloads of a line of `BUILTINS`, more than one with a `for` list.
+pub fn parse_signatures(line: &str) -> Result<Vec<Overload>, String> {
+    let (signature, types) = match line.split_once(" for ") {
+        Some((signature, types)) => (signature, types.split(", ").collect()),
+        None => (line, vec![]),
+    };
+    if types.is_empty() {
+        return Ok(vec![parse_signature(signature)?]);
+    }
+    types
+        .into_iter()
+        .map(|ty| {
+            let signature = signature
+                .replace("(T)", &format!("({ty})"))
+                .replace("(T,", &format!("({ty},"))
+                .replace(", T)", &format!(", {ty})"))
+                .replace("-> T", &format!("-> {ty}"));
+            parse_signature(&signature)
+        })
+        .collect()
+}
+
+/// ```text
+/// [aggregate | window | procedure] [stable | volatile]
+///     <name>( [[variadic] <type> [default], ..] ) -> [setof] <type>
+/// ```
+///
+/// A type is a type name, `"any"` or a polymorphic type. `variadic` before the last parameter
+/// takes any number of arguments, and `default` marks a parameter that may be left out.
+pub fn parse_signature(signature: &str) -> Result<Overload, String> {
+    let invalid = || format!("invalid function signature `{signature}`");
+    let mut rest = signature.trim();
+    let mut take = |word: &str| match rest.strip_prefix(word) {
+        Some(after) if after.starts_with(' ') => {
+            rest = after.trim_start();
+            true
+        }
+        _ => false,
+    };
+    let kind = match (take("aggregate"), take("window"), take("procedure")) {
+        (true, _, _) => FunctionKind::Aggregate,
+        (_, true, _) => FunctionKind::Window,
+        (_, _, true) => FunctionKind::Procedure,
+        _ => FunctionKind::Scalar,
+    };
+    let volatility = match (take("stable"), take("volatile")) {
+        (true, _) => Volatility::Stable,
+        (_, true) => Volatility::Volatile,
+        _ => Volatility::Immutable,
+    };
+    let (call, result) = rest.split_once(" -> ").ok_or_else(invalid)?;
+    let (name, params) = call
+        .strip_suffix(')')
+        .and_then(|call| call.split_once('('))
+        .ok_or_else(invalid)?;
+
+    let (mut variadic, mut defaults) = (false, 0);
+    let params = params
+        .split(',')
+        .map(str::trim)
+        .filter(|param| !param.is_empty())
+        .map(|param| {
+            let param = match param.strip_suffix(" default") {
+                Some(param) => {
+                    defaults += 1;
+                    param
+                }
+                None if defaults > 0 => return Err(invalid()),
+                None => param,
+            };
+            match param.strip_prefix("variadic ") {
+                Some(param) => {
+                    variadic = true;
+                    ParamType::parse(param)
+                }
+                None => ParamType::parse(param),
+            }
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    let result = result.trim();
+    let (result, returns_set) = match result.strip_prefix("setof ") {
+        Some(result) => (result, true),
+        None => (result, false),
+    };
+    Ok(Overload {
+        name: name.trim().to_lowercase(),
+        kind,
+        params,
+        variadic,
+        defaults,
+        result: ParamType::parse(result)?,
+        returns_set,
+        volatility,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn resolve(name: &str, args: &[DataType]) -> Result<DataType, ResolveError> {
+        Catalog::builtins().resolve(name, args).map(|(_, ty)| ty)
+    }
+
+    #[test]
+    fn builtins() {
+        let catalog = Catalog::builtins();
+        assert_eq!(catalog.get("abs").len(), 6);
+        assert_eq!(catalog.kind("count"), Some(FunctionKind::Aggregate));
+        assert_eq!(catalog.kind("lag"), Some(FunctionKind::Window));
+        assert_eq!(catalog.get("random")[0].volatility, Volatility::Volatile);
+        assert!(catalog.get("concat")[0].variadic);
+        assert!(catalog.get("unnest")[0].returns_set);
+        // A misspelled type would be taken for a user defined one.
+        let is_user_defined =
+            |param: &ParamType| matches!(param, ParamType::Type(DataType::UserDefined { .. }));
+        for overload in catalog.functions.values().flatten() {
+            assert!(
+                !overload.params.iter().any(is_user_defined) && !is_user_defined(&overload.result),
+                "{}",
+                overload.name
+            );
+        }
+    }
+
+    #[test]
+    fn overloads() {
+        use DataType::*;
+        assert_eq!(resolve("round", &[Numeric, Integer]), Ok(Numeric));
+        // `integer` converts to both `numeric` and `double precision`, the preferred type wins.
+        assert_eq!(resolve("round", &[Integer]), Ok(DoublePrecision));
+        assert_eq!(resolve("log", &[Numeric]), Ok(Numeric));
+        assert_eq!(resolve("abs", &[SmallInt]), Ok(SmallInt));
+        assert_eq!(resolve("max", &[Unknown]), Ok(Text));
+        // All of `abs` take numbers, an untyped literal is taken for the preferred one.
+        assert_eq!(resolve("abs", &[Unknown]), Ok(DoublePrecision));
+        assert_eq!(resolve("round", &[Unknown]), Ok(DoublePrecision));
+        assert_eq!(resolve("max", &[CharacterVarying]), Ok(Text));
+        assert_eq!(
+            resolve("coalesce", &[SmallInt, Unknown, BigInt]),
+            Ok(BigInt)
+        );
+        assert_eq!(resolve("concat", &[Text, Integer, Boolean]), Ok(Text));
+        assert_eq!(
+            resolve("array_agg", &[Date]),
+            Ok(Array { ty: Box::new(Date) })
+        );
+
+        assert_eq!(resolve("nope", &[]), Err(ResolveError::NotFound));
+        assert_eq!(
+            resolve("lpad", &[Text]),
+            Err(ResolveError::Arity { min: 2, max: 3 })
+        );
+        assert_eq!(
+            resolve("lpad", &[Text, Text]),
+            Err(ResolveError::Args(vec![(1, ParamType::Type(Integer))]))
+        );
+        assert_eq!(resolve("sum", &[Text]), Err(ResolveError::NoMatch));
+        assert_eq!(
+            resolve("nullif", &[Integer, Text]),
+            Err(ResolveError::NoMatch)
+        );
+        assert_eq!(resolve("abs", &[Error]), Ok(Error));
+        assert_eq!(resolve("unnest", &[Array { ty: Box::new(Uuid) }]), Ok(Uuid));
+    }
+
+    #[test]
+    fn pseudo_types() {
+        use DataType::*;
+        let array = |ty| Array { ty: Box::new(ty) };
+        assert_eq!(
+            resolve("array_append", &[array(Integer), BigInt]),
+            Ok(array(BigInt))
+        );
+        assert_eq!(
+            resolve("array_prepend", &[Unknown, array(Date)]),
+            Ok(array(Date))
+        );
+        assert_eq!(
+            resolve("array_cat", &[array(Integer), Integer]),
+            Err(ResolveError::Args(vec![(1, ParamType::AnyCompatibleArray)]))
+        );
+
+        let mut catalog = Catalog::default();
+        catalog.insert(parse_signature("enum_first(anyenum) -> anyenum").unwrap());
+        let mood = UserDefined {
+            name: "mood".to_string(),
+        };
+        assert_eq!(
+            catalog
+                .resolve("enum_first", std::slice::from_ref(&mood))
+                .map(|(_, ty)| ty),
+            Ok(mood)
+        );
+        assert_eq!(
+            catalog.resolve("enum_first", &[Text]).map(|(_, ty)| ty),
+            Err(ResolveError::Args(vec![(0, ParamType::AnyEnum)]))
+        );
+
+        assert_eq!(
+            ParamType::parse("anyrange"),
+            Err("unsupported type `anyrange`".to_string())
+        );
+        assert_eq!(
+            ParamType::parse("anyelement[]"),
+            Err("unsupported type `anyelement[]`".to_string())
+        );
+    }
+
+    #[test]
+    fn defaults() {
+        let mut catalog = Catalog::default();
+        catalog
+            .insert(parse_signature("greet(text, text default, integer default) -> text").unwrap());
+        catalog.insert(parse_signature("procedure greet(integer) -> void").unwrap());
+        let resolve = |args: &[DataType]| catalog.resolve("greet", args).map(|(o, _)| o.kind);
+        assert_eq!(resolve(&[DataType::Text]), Ok(FunctionKind::Scalar));
+        assert_eq!(
+            resolve(&[DataType::Text, DataType::Unknown, DataType::Integer]),
+            Ok(FunctionKind::Scalar)
+        );
+        assert_eq!(resolve(&[DataType::Integer]), Ok(FunctionKind::Procedure));
+        assert_eq!(resolve(&[]), Err(ResolveError::Arity { min: 1, max: 3 }));
+    }
+
+    #[test]
+    fn signatures() {
+        let overload =
+            parse_signature("window stable f(text, variadic integer[]) -> anyarray").unwrap();
+        assert_eq!(overload.kind, FunctionKind::Window);
+        assert_eq!(overload.volatility, Volatility::Stable);
+        assert!(overload.variadic);
+        assert_eq!(
+            overload.params[1],
+            ParamType::Type(DataType::Array {
+                ty: Box::new(DataType::Integer)
+            })
+        );
+        assert!(parse_signature("f(integer)").is_err());
+        assert!(parse_signature("f(integer default, text) -> text").is_err());
+        assert_eq!(
+            parse_signature("f(mood) -> setof void").unwrap().result,
+            ParamType::Type(DataType::UserDefined {
+                name: "void".to_string()
+            })
+        );
+    }
+}
@@ -1,10 +1,14 @@
+mod call;
 mod delete;
 mod insert;
 mod select;
 mod update;
 
 use crate::*;
+use analyzer::{AnalyseExpr, Analyser};
+use sql_parser::command::insert::InsertExpr;
 use sql_parser::command::*;
+use sql_parser::grammar::{Term, Value};
 
 impl SqlAnalyzer for Command {
     fn analyse(&self, ctx: &mut Ctx) -> AnalyseResult {
@@ -13,6 +17,44 @@ impl SqlAnalyzer for Command {
             Command::Insert(c) => c.analyse(ctx),
             Command::Delete(c) => c.analyse(ctx),
             Command::Update(c) => c.analyse(ctx),
+            Command::Call(c) => c.analyse(ctx),
         }
     }
 }
+
+/// Checks that `value` can be stored in `column`: `DEFAULT` and `NULL` must not
+/// violate a NOT NULL constraint, and the expression type must be assignable to the column type.
+fn analyse_value(
+    ctx: &mut Ctx,
+    analyser: &mut AnalyseExpr,
+    name: &str,
+    column: &Column,
+    value: &InsertExpr,
+) {
+    let result = match value {
+        InsertExpr::Default(kw) if !column.is_nullable && !column.has_default() => err::msg(
+            kw.span(),
+            format!("column `{name}` has no default value and is declared NOT NULL"),
+        ),
+        InsertExpr::Default(_) => Ok(()),
+        InsertExpr::Insert(expr) => match utils::as_term(expr) {
+            Some(Term::Value(Value::Null { span })) if !column.is_nullable => err::msg(
+                span,
+                format!("null value in column `{name}` violates not-null constraint"),
+            ),
+            _ => analyser.analyse_expr(expr).and_then(|ty| {
+                if ty.is_assignable_to(&column.data_type) {
+                    return Ok(());
+                }
+                err::msg(
+                    expr,
+                    format!(
+                        "column `{name}` is of type `{:?}` but expression is of type `{ty:?}`",
+                        column.data_type
+                    ),
+                )
+            }),
+        },
+    };
+    ctx.add_errs(analyser, result);
+}
@@ -0,0 +1,49 @@
+use super::*;
+use sql_parser::command::call::*;
+
+impl SqlAnalyzer for Call {
+    fn analyse(&self, ctx: &mut Ctx) -> AnalyseResult {
+        let mut analyser = AnalyseExpr {
+            schema: ctx.info,
+            dialect: ctx.dialect,
+            tables: vec![],
+            windows: vec![],
+            errs: vec![],
+        };
+        let result = analyser.analyse_procedure(&self.procedure);
+        ctx.add_errs(&mut analyser, result);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::analyse;
+
+    #[test]
+    fn call() {
+        assert!(analyse("CALL transfer(1, 2, 10.5)").is_empty());
+        assert!(analyse("CALL transfer(1, 2)").is_empty());
+
+        let errs = analyse(r#"CALL transfer(1, "a")"#);
+        assert_eq!(errs, ["expected `integer` type, found `Text`"]);
+
+        let errs = analyse("CALL transfr(1, 2)");
+        assert_eq!(
+            errs,
+            ["procedure `transfr` does not exist \nsuggest: `transfer`"]
+        );
+
+        let errs = analyse(r#"CALL full_name("a")"#);
+        assert_eq!(
+            errs,
+            ["`full_name` is not a procedure, use SELECT to call a function"]
+        );
+
+        let errs = analyse("CALL transfer(id, 2)");
+        assert_eq!(errs, ["column does not exist: `id` \nsuggest: "]);
+
+        let errs = analyse("CALL transfer(1, 2, TRUE)");
+        assert_eq!(errs, ["expected `numeric` type, found `Boolean`"]);
+    }
+}
@@ -1,19 +1,82 @@
 use super::*;
-use analyzer::{AnalyseExpr, Analyser};
 use sql_parser::command::delete::*;
 
 impl SqlAnalyzer for Delete {
     fn analyse(&self, ctx: &mut Ctx) -> AnalyseResult {
-        if let Some(table) = ctx.get_table(&self.table_name.alias) {
-            if let Some(expr) = &self.where_expr {
-                let result = AnalyseExpr {
-                    _schema: ctx.info,
-                    table: Some(table),
+        let Some(table) = ctx.get_table(&self.table_name.alias) else {
+            return Ok(());
+        };
+        let mut tables = vec![(self.visible_name().value(ctx.dialect), table)];
+        if let Some((_, using)) = &self.using {
+            let mut has_error = false;
+            for table_ref in using.iter() {
+                let name = table_ref.visible_name();
+                let value = name.value(ctx.dialect);
+                if tables
+                    .iter()
+                    .any(|(visible_name, _)| *visible_name == value)
+                {
+                    has_error = true;
+                    ctx.add_err::<()>(err::msg(
+                        name,
+                        format!("table name `{name}` specified more than once"),
+                    ));
+                    continue;
                 }
-                .analyse_or_expr(expr);
-                ctx.add_err(result);
+                match ctx.get_table(&table_ref.table_name.alias) {
+                    Some(table) => tables.push((value, table)),
+                    None => has_error = true,
+                }
+            }
+            if has_error {
+                return Ok(());
+            }
+        }
+
+        // The condition and the returned columns can refer to the target table and every `USING` table.
+        let mut analyser = AnalyseExpr {
+            schema: ctx.info,
+            dialect: ctx.dialect,
+            tables,
+            windows: vec![],
+            errs: vec![],
+        };
+        if let Some(WhereClause::Expr(expr)) = &self.where_clause {
+            let result = analyser
+                .analyse_expr(expr)
+                .and_then(|ty| err::expect_bool(&ty, expr))
+                .and_then(|_| analyser.expect_no_set_returning(expr, "WHERE"));
+            ctx.add_errs(&mut analyser, result);
+        }
+        if let Some(returning) = &self.returning {
+            if let Some(columns) = select::analyse_projection(ctx, &mut analyser, &returning.exprs)
+            {
+                ctx.columns = columns;
             }
         }
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::analyse;
+
+    #[test]
+    fn using() {
+        assert!(analyse(
+            "DELETE FROM posts AS p USING users u WHERE p.user_id = u.id AND u.age > 60 RETURNING p.id, u.name"
+        )
+        .is_empty());
+        assert!(analyse("DELETE FROM posts WHERE CURRENT OF posts_cursor").is_empty());
+
+        let errs = analyse("DELETE FROM posts USING users WHERE id = 1");
+        assert_eq!(errs, ["column reference `id` is ambiguous"]);
+
+        let errs = analyse("DELETE FROM posts p USING users p WHERE p.id = 1");
+        assert_eq!(errs, ["table name `p` specified more than once"]);
+
+        let errs = analyse("DELETE FROM posts p USING users u WHERE posts.id = u.id");
+        assert_eq!(errs, ["missing FROM-clause entry for table `posts`"]);
+    }
+}
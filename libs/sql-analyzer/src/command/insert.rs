@@ -1,15 +1,428 @@
-use crate::*;
+use super::*;
+use schema_info::IdentityGeneration;
 use sql_parser::command::insert::*;
+use sql_parser::{utils::suggest, GetSpan};
 
 impl SqlAnalyzer for Insert {
     fn analyse(&self, ctx: &mut Ctx) -> AnalyseResult {
-        if let Some(table) = ctx.get_table(&self.table_name.alias) {
-            for column_name in &self.column_name {
-                if let Some(_c) = ctx.get_column(table, column_name) {
-                    // ...
+        let Some(table) = ctx.get_table(&self.table_name.alias) else {
+            return Ok(());
+        };
+        let Some(targets) = target_columns(ctx, self, table) else {
+            return Ok(());
+        };
+        let mut analyser = AnalyseExpr {
+            schema: ctx.info,
+            dialect: ctx.dialect,
+            tables: vec![],
+            windows: vec![],
+            errs: vec![],
+        };
+
+        let overriding = self.overriding.as_ref().map(|o| o.kind);
+        let is_implicit = self.column_name.is_empty();
+
+        // Number of target columns that actually receive a value.
+        let mut provided = targets.len();
+        match &self.values {
+            InsertKind::DefaultValues(_) => provided = 0,
+            InsertKind::Query(query) => {
+                let Some(columns) = select::analyse_query(ctx, query) else {
+                    return Ok(());
+                };
+                if columns.len() > targets.len() {
+                    ctx.add_err::<()>(err::msg(
+                        columns[targets.len()].span,
+                        "INSERT has more expressions than target columns",
+                    ));
+                    return Ok(());
+                }
+                if columns.len() < targets.len() {
+                    if !is_implicit {
+                        ctx.add_err::<()>(err::msg(
+                            query.body.first_select().select_kw.span(),
+                            "INSERT has more target columns than expressions",
+                        ));
+                        return Ok(());
+                    }
+                    provided = columns.len();
+                }
+                for ((name, column), output) in targets.iter().zip(&columns) {
+                    let result = check_generated(name, column, overriding, output.span);
+                    if ctx.add_err(result).is_none() {
+                        continue;
+                    }
+                    if !output.data_type.is_assignable_to(&column.data_type) {
+                        ctx.add_err::<()>(err::msg(
+                            output.span,
+                            format!(
+                                "column `{name}` is of type `{:?}` but expression is of type `{:?}`",
+                                column.data_type, output.data_type
+                            ),
+                        ));
+                    }
                 }
             }
+            InsertKind::Values { rows, .. } => {
+                for row in rows {
+                    let values = row.values();
+                    if values.len() > targets.len() {
+                        ctx.add_err::<()>(err::msg(
+                            values[targets.len()],
+                            "INSERT has more expressions than target columns",
+                        ));
+                        continue;
+                    }
+                    if values.len() < targets.len() {
+                        if !is_implicit {
+                            ctx.add_err::<()>(err::msg(
+                                row,
+                                "INSERT has more target columns than expressions",
+                            ));
+                            continue;
+                        }
+                        // Without a column list, the trailing columns are filled with defaults.
+                        provided = provided.min(values.len());
+                    }
+                    for ((name, column), value) in targets.iter().zip(values) {
+                        match value {
+                            InsertExpr::Default(kw) if !column.has_default() => {
+                                let message = format!("column `{name}` has no default value");
+                                ctx.add_err::<()>(err::msg(kw.span(), message));
+                                continue;
+                            }
+                            InsertExpr::Default(_) => {}
+                            InsertExpr::Insert(expr) => {
+                                let result = check_generated(name, column, overriding, expr.span());
+                                if ctx.add_err(result).is_none() {
+                                    continue;
+                                }
+                            }
+                        }
+                        analyse_value(ctx, &mut analyser, name, column, value);
+                    }
+                }
+            }
+        }
+
+        let mut required: Vec<_> = table
+            .iter()
+            .filter(|(name, column)| {
+                !column.is_nullable
+                    && !column.has_default()
+                    && !targets[..provided]
+                        .iter()
+                        .any(|(target, _)| target == *name)
+            })
+            .collect();
+
+        required.sort_by_key(|(_, column)| column.ordinal_position);
+        for (name, _) in required {
+            ctx.add_err::<()>(err::msg(
+                &self.table_name,
+                format!("missing value for NOT NULL column `{name}`, which has no default"),
+            ));
+        }
+        if let Some(on_conflict) = &self.on_conflict {
+            analyse_on_conflict(ctx, self, table, on_conflict);
+        }
+        if let Some(returning) = &self.returning {
+            let mut analyser = AnalyseExpr {
+                schema: ctx.info,
+                dialect: ctx.dialect,
+                tables: vec![(self.table_name.alias.value(ctx.dialect), table)],
+                windows: vec![],
+                errs: vec![],
+            };
+            if let Some(columns) = select::analyse_projection(ctx, &mut analyser, &returning.exprs)
+            {
+                ctx.columns = columns;
+            }
         }
         Ok(())
     }
 }
+
+fn analyse_on_conflict<'s>(
+    ctx: &mut Ctx<'s>,
+    insert: &Insert,
+    table: &'s Table,
+    on_conflict: &OnConflict,
+) {
+    let table_name = insert.table_name.alias.value(ctx.dialect);
+    let info = ctx.info;
+    let indexes = info.get_unique_indexes(&table_name);
+    let mut analyser = AnalyseExpr {
+        schema: ctx.info,
+        dialect: ctx.dialect,
+        tables: vec![(table_name.clone(), table)],
+        windows: vec![],
+        errs: vec![],
+    };
+
+    match &on_conflict.target {
+        Some(
+            target @ ConflictTarget::Columns {
+                columns,
+                where_expr,
+                ..
+            },
+        ) => {
+            let mut names = vec![];
+            for column in columns {
+                if ctx.get_column(table, column).is_some() {
+                    names.push(column.value(ctx.dialect));
+                }
+            }
+            if names.len() != columns.len() {
+                return;
+            }
+            if let Some(expr) = where_expr {
+                let result = analyser
+                    .analyse_expr(expr)
+                    .and_then(|ty| err::expect_bool(&ty, expr));
+                ctx.add_errs(&mut analyser, result);
+            }
+            // Postgres infers the arbiter index from the set of key columns, in any order.
+            let is_inferred = indexes.iter().any(|index| {
+                !index.has_expressions
+                    && (!index.is_partial || where_expr.is_some())
+                    && index.columns.len() == names.len()
+                    && index.columns.iter().all(|c| names.contains(c))
+            });
+            if !is_inferred {
+                let keys = indexes
+                    .iter()
+                    .filter(|index| !index.has_expressions)
+                    .map(|index| format!("({})", index.columns.join(", ")));
+
+                ctx.add_err::<()>(err::msg(
+                    target,
+                    format!(
+                        "there is no unique or exclusion constraint matching the ON CONFLICT specification \nsuggest: {}",
+                        suggest(&format!("({})", names.join(", ")), keys)
+                    ),
+                ));
+            }
+        }
+        Some(target @ ConflictTarget::Constraint { name, .. }) => {
+            let name = name.value(ctx.dialect);
+            let constraints = indexes
+                .iter()
+                .filter_map(|index| index.constraint_name.as_ref());
+            if !constraints.clone().any(|constraint| *constraint == name) {
+                ctx.add_err::<()>(err::msg(
+                    target,
+                    format!(
+                        "constraint `{name}` for table `{table_name}` does not exist \nsuggest: {}",
+                        suggest(&name, constraints)
+                    ),
+                ));
+            }
+        }
+        None => {
+            if let ConflictAction::Update { kws, .. } = &on_conflict.action {
+                ctx.add_err::<()>(err::msg(
+                    kws.0.span(),
+                    "ON CONFLICT DO UPDATE requires inference specification or constraint name",
+                ));
+            }
+        }
+    }
+
+    if let ConflictAction::Update {
+        assignments,
+        where_expr,
+        ..
+    } = &on_conflict.action
+    {
+        // The row proposed for insertion is visible as `EXCLUDED`.
+        analyser.tables.push(("excluded".to_string(), table));
+
+        let mut assigned = vec![];
+        for assignment in assignments.iter() {
+            update::analyse_assignment_item(ctx, &mut analyser, table, assignment, &mut assigned);
+        }
+        if let Some(expr) = where_expr {
+            let result = analyser
+                .analyse_expr(expr)
+                .and_then(|ty| err::expect_bool(&ty, expr));
+            ctx.add_errs(&mut analyser, result);
+        }
+    }
+}
+
+/// Explicit values for `GENERATED ALWAYS AS IDENTITY` columns need `OVERRIDING SYSTEM VALUE`,
+/// and generated columns can't be written at all.
+fn check_generated(
+    name: &str,
+    column: &Column,
+    overriding: Option<OverridingKind>,
+    span: Span,
+) -> Result<(), AnalyseError> {
+    if column.is_generated {
+        return err::msg(
+            span,
+            format!(
+                "cannot insert a non-DEFAULT value into column `{name}`, it is a generated column"
+            ),
+        );
+    }
+    // With `OVERRIDING USER VALUE` the supplied value is ignored.
+    if column.identity_generation == Some(IdentityGeneration::Always) && overriding.is_none() {
+        return err::msg(
+            span,
+            format!(
+                "cannot insert a non-DEFAULT value into column `{name}`, it is an identity column defined as GENERATED ALWAYS \nhelp: use OVERRIDING SYSTEM VALUE to override"
+            ),
+        );
+    }
+    Ok(())
+}
+
+/// The listed columns, or every column of the table in order when there is no column list.
+fn target_columns<'s>(
+    ctx: &mut Ctx<'s>,
+    insert: &Insert,
+    table: &'s Table,
+) -> Option<Vec<(String, &'s Column)>> {
+    if insert.column_name.is_empty() {
+        let mut columns: Vec<_> = table.iter().map(|(n, c)| (n.clone(), c)).collect();
+        columns.sort_by_key(|(_, column)| column.ordinal_position);
+        return Some(columns);
+    }
+    let mut columns: Vec<(String, &Column)> = vec![];
+    let mut has_error = false;
+    for column_name in &insert.column_name {
+        let name = column_name.value(ctx.dialect);
+        if columns.iter().any(|(n, _)| *n == name) {
+            has_error = true;
+            ctx.add_err::<()>(err::msg(
+                column_name,
+                format!("column `{name}` specified more than once"),
+            ));
+            continue;
+        }
+        match ctx.get_column(table, column_name) {
+            Some(column) => columns.push((name, column)),
+            None => has_error = true,
+        }
+    }
+    (!has_error).then_some(columns)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::analyse;
+
+    #[test]
+    fn values() {
+        assert!(
+            analyse(r#"INSERT INTO users (name, email) VALUES ("a", "a@x"), (NULL, "b@x")"#)
+                .is_empty()
+        );
+        assert!(analyse(r#"INSERT INTO posts VALUES (DEFAULT, 1, "title")"#).is_empty());
+
+        let errs = analyse(r#"INSERT INTO users (name, email, name) VALUES ("a", "a@x", "b")"#);
+        assert_eq!(errs, ["column `name` specified more than once"]);
+
+        let errs =
+            analyse(r#"INSERT INTO posts (user_id, title) VALUES (1, "a", 2), (1), ("x", NULL)"#);
+        assert_eq!(
+            errs,
+            [
+                "INSERT has more expressions than target columns",
+                "INSERT has more target columns than expressions",
+                "column `user_id` is of type `Integer` but expression is of type `Text`",
+                "null value in column `title` violates not-null constraint",
+            ]
+        );
+
+        let errs =
+            analyse(r#"INSERT INTO posts (user_id, title, score) VALUES (1, DEFAULT, DEFAULT)"#);
+        assert_eq!(
+            errs,
+            [
+                "column `title` has no default value",
+                "column `score` has no default value",
+            ]
+        );
+
+        let errs = analyse(r#"INSERT INTO posts (title) VALUES ("a")"#);
+        assert_eq!(
+            errs,
+            ["missing value for NOT NULL column `user_id`, which has no default"]
+        );
+
+        let errs = analyse("INSERT INTO posts DEFAULT VALUES");
+        assert_eq!(
+            errs,
+            [
+                "missing value for NOT NULL column `user_id`, which has no default",
+                "missing value for NOT NULL column `title`, which has no default",
+            ]
+        );
+    }
+
+    #[test]
+    fn overriding_and_query() {
+        let errs = analyse(r#"INSERT INTO users VALUES (1, "a", "a@x", 20)"#);
+        assert!(errs[0].starts_with("cannot insert a non-DEFAULT value into column `id`"));
+
+        assert!(
+            analyse(r#"INSERT INTO users OVERRIDING SYSTEM VALUE VALUES (1, "a", "a@x", 20)"#)
+                .is_empty()
+        );
+        assert!(
+            analyse(r#"INSERT INTO users OVERRIDING USER VALUE VALUES (1, "a", "a@x")"#).is_empty()
+        );
+        assert!(
+            analyse("INSERT INTO posts (user_id, title) SELECT id, email FROM users").is_empty()
+        );
+
+        let errs = analyse(
+            "INSERT INTO users OVERRIDING SYSTEM VALUE SELECT id, title, title, title FROM posts",
+        );
+        assert_eq!(
+            errs,
+            ["column `age` is of type `SmallInt` but expression is of type `Text`"]
+        );
+
+        let errs = analyse("INSERT INTO posts (user_id, title) SELECT id FROM users");
+        assert_eq!(errs, ["INSERT has more target columns than expressions"]);
+    }
+
+    #[test]
+    fn on_conflict() {
+        let sql = r#"INSERT INTO posts (user_id, title) VALUES (1, "a") ON CONFLICT"#;
+        assert!(analyse(&format!(
+            "{sql} (title, user_id) DO UPDATE SET score = excluded.score + 1"
+        ))
+        .is_empty());
+        assert!(analyse(&format!("{sql} ON CONSTRAINT posts_pkey DO NOTHING")).is_empty());
+        assert!(analyse(&format!("{sql} DO NOTHING")).is_empty());
+
+        let errs = analyse(&format!("{sql} (title) DO NOTHING"));
+        assert!(errs[0].starts_with("there is no unique or exclusion constraint matching"));
+
+        let errs = analyse(&format!("{sql} ON CONSTRAINT posts_key DO NOTHING"));
+        assert!(errs[0].starts_with("constraint `posts_key` for table `posts` does not exist"));
+
+        let errs = analyse(&format!("{sql} DO UPDATE SET score = 1"));
+        assert_eq!(
+            errs,
+            ["ON CONFLICT DO UPDATE requires inference specification or constraint name"]
+        );
+
+        let errs = analyse(&format!(
+            "{sql} (id) DO UPDATE SET user_id = excluded.title, score = excluded.rank, title = score"
+        ));
+        assert_eq!(errs.len(), 3);
+        assert_eq!(
+            errs[0],
+            "column `user_id` is of type `Integer` but expression is of type `Text`"
+        );
+        assert!(errs[1].starts_with("column does not exist: `rank`"));
+        assert_eq!(errs[2], "column reference `score` is ambiguous");
+    }
+}
@@ -1,8 +1,583 @@
 use crate::*;
+use analyzer::{AnalyseExpr, Analyser};
+use grouping::{Grouping, OutputItem};
 use sql_parser::command::select::*;
+use sql_parser::grammar::ast::Expr;
+use sql_parser::grammar::{self, Name, Term, Value};
+use sql_parser::GetSpan;
+use syn::punctuated::Punctuated;
+use syn::Token;
 
-impl SqlAnalyzer for Select {
-    fn analyse(&self, _: &mut Ctx) -> AnalyseResult {
+impl SqlAnalyzer for Query {
+    fn analyse(&self, ctx: &mut Ctx) -> AnalyseResult {
+        if let Some(columns) = analyse_query(ctx, self) {
+            ctx.columns = columns;
+        }
         Ok(())
     }
 }
+
+pub fn analyse_query(ctx: &mut Ctx, query: &Query) -> Option<Vec<OutputColumn>> {
+    let (columns, tables) = match &query.body {
+        SetExpr::Select(select) => {
+            let tables = from_tables(ctx, select)?;
+            (analyse_select(ctx, select, tables.clone())?, tables)
+        }
+        body => (analyse_set_expr(ctx, body)?, vec![]),
+    };
+    let is_set_operation = matches!(query.body, SetExpr::SetOperation { .. });
+
+    if let Some(order_by) = &query.order_by {
+        let mut analyser = AnalyseExpr {
+            schema: ctx.info,
+            dialect: ctx.dialect,
+            tables,
+            windows: vec![],
+            errs: vec![],
+        };
+        for spec in order_by.sort_specs.iter() {
+            let result = match utils::as_term(&spec.expr) {
+                Some(Term::Value(Value::Int(pos))) => match pos.base10_parse::<usize>() {
+                    Ok(n) if (1..=columns.len()).contains(&n) => Ok(()),
+                    _ => err::msg(
+                        pos.span(),
+                        format!("ORDER BY position {pos} is not in select list"),
+                    ),
+                },
+                Some(Term::Column(column))
+                    if column.table_name.is_none()
+                        && columns.iter().any(|c| c.name == column.alias.value(ctx.dialect)) =>
+                {
+                    Ok(())
+                }
+                _ if is_set_operation => err::msg(
+                    &spec.expr,
+                    "ORDER BY on a UNION/INTERSECT/EXCEPT result must be on one of the result columns",
+                ),
+                _ => analyser.analyse_expr(&spec.expr).map(|_| ()),
+            };
+            ctx.add_errs(&mut analyser, result);
+        }
+    }
+
+    let counts = [
+        query.limit.as_ref().and_then(|limit| limit.count.as_ref()),
+        query.offset.as_ref().map(|offset| &offset.count),
+    ];
+    for count in counts.into_iter().flatten() {
+        let mut analyser = AnalyseExpr {
+            schema: ctx.info,
+            dialect: ctx.dialect,
+            tables: vec![],
+            windows: vec![],
+            errs: vec![],
+        };
+        let result = analyser
+            .analyse_expr(count)
+            .and_then(|ty| err::expect_integer(&ty, count));
+        ctx.add_errs(&mut analyser, result);
+    }
+    Some(columns)
+}
+
+fn analyse_set_expr(ctx: &mut Ctx, expr: &SetExpr) -> Option<Vec<OutputColumn>> {
+    match expr {
+        SetExpr::Select(select) => {
+            let tables = from_tables(ctx, select)?;
+            analyse_select(ctx, select, tables)
+        }
+        SetExpr::Query { query, .. } => analyse_query(ctx, query),
+        SetExpr::SetOperation { left, op, right } => {
+            let (left_columns, right_columns) =
+                (analyse_set_expr(ctx, left), analyse_set_expr(ctx, right));
+            let (left_columns, right_columns) = (left_columns?, right_columns?);
+            let op_name = op.kw.to_string().to_uppercase();
+
+            if left_columns.len() != right_columns.len() {
+                let (len, rest) = (left_columns.len(), right_columns.len());
+                let mismatched = match len < rest {
+                    true => &right_columns[len],
+                    false => &left_columns[rest],
+                };
+                ctx.add_err::<()>(err::msg(
+                    mismatched.span,
+                    format!(
+                        "each {op_name} query must have the same number of columns, left has {len}, right has {rest}"
+                    ),
+                ));
+                return None;
+            }
+
+            let mut columns = Vec::with_capacity(left_columns.len());
+            let mut has_error = false;
+            for (left, right) in left_columns.into_iter().zip(right_columns) {
+                match left.data_type.unify(&right.data_type) {
+                    Some(data_type) => columns.push(OutputColumn {
+                        data_type,
+                        is_nullable: left.is_nullable || right.is_nullable,
+                        ..left
+                    }),
+                    None => {
+                        has_error = true;
+                        ctx.add_err::<()>(err::msg(
+                            right.span,
+                            format!(
+                                "{op_name} types `{:?}` and `{:?}` cannot be matched",
+                                left.data_type, right.data_type
+                            ),
+                        ));
+                    }
+                }
+            }
+            (!has_error).then_some(columns)
+        }
+    }
+}
+
+/// Returns `None` if a `FROM` table doesn't exist.
+fn from_tables<'s>(ctx: &mut Ctx<'s>, select: &Select) -> Option<Vec<(String, &'s Table)>> {
+    let mut tables = vec![];
+    if let Some(from) = &select.from {
+        let table = ctx.get_table(&from.table.table_name.alias)?;
+        tables.push((from.table.visible_name().value(ctx.dialect), table));
+    }
+    Some(tables)
+}
+
+fn analyse_select<'s>(
+    ctx: &mut Ctx<'s>,
+    select: &Select,
+    tables: Vec<(String, &'s Table)>,
+) -> Option<Vec<OutputColumn>> {
+    let mut analyser = AnalyseExpr {
+        schema: ctx.info,
+        dialect: ctx.dialect,
+        tables,
+        windows: vec![],
+        errs: vec![],
+    };
+    if let Some(window) = &select.window {
+        for definition in window.definitions.iter() {
+            let name = definition.name.value(ctx.dialect);
+            if analyser.windows.iter().any(|(n, _)| *n == name) {
+                ctx.add_err::<()>(err::msg(
+                    &definition.name,
+                    format!("window `{name}` is already defined"),
+                ));
+                continue;
+            }
+            let result = analyser.analyse_window_spec(&definition.spec);
+            // Keep it in scope even if invalid, so references to it aren't reported twice.
+            ctx.add_errs(&mut analyser, result);
+            analyser.windows.push((name, &definition.spec));
+        }
+    }
+    if let Some(expr) = &select.where_expr {
+        let result = analyser
+            .analyse_expr(expr)
+            .and_then(|ty| err::expect_bool(&ty, expr))
+            .and_then(|_| grouping::expect_no_aggregates(expr, "WHERE"))
+            .and_then(|_| analyser.expect_no_set_returning(expr, "WHERE"));
+        ctx.add_errs(&mut analyser, result);
+    }
+
+    // Positions and names in `GROUP BY` refer to these, each `*` counts as its columns.
+    let projection: Vec<_> = select
+        .exprs
+        .iter()
+        .flat_map(|select_expr| match select_expr {
+            SelectExpr::Expr { expr, alias } => vec![OutputItem::Expr(expr, alias.as_ref())],
+            SelectExpr::WildCard { symbol, except } => {
+                wildcard_columns(&analyser.tables, ctx.dialect, symbol, except)
+                    .into_iter()
+                    .map(OutputItem::Column)
+                    .collect()
+            }
+        })
+        .collect();
+    let mut group_by = vec![];
+    let mut group_by_columns = vec![];
+    let mut is_group_by_valid = true;
+    if let Some(clause) = &select.group_by {
+        for item in clause.exprs.iter() {
+            let result =
+                grouping::resolve_group_by_item(&analyser, &projection, item).and_then(|output| {
+                    if let OutputItem::Expr(expr, _) = output {
+                        // Output columns are analysed along with the projection.
+                        if std::ptr::eq(expr, item) {
+                            analyser.analyse_expr(expr)?;
+                        }
+                        grouping::expect_no_aggregates(expr, "GROUP BY")?;
+                    }
+                    Ok(output)
+                });
+            match ctx.add_errs(&mut analyser, result) {
+                Some(OutputItem::Expr(expr, _)) => group_by.push(expr),
+                Some(OutputItem::Column(column)) => group_by_columns.push(column),
+                // Checking against an incomplete `GROUP BY` would only report noise.
+                None => is_group_by_valid = false,
+            }
+        }
+    }
+    let mut having = None;
+    if let Some(expr) = &select.having {
+        let result = analyser
+            .analyse_expr(expr)
+            .and_then(|ty| err::expect_bool(&ty, expr))
+            .and_then(
+                |_| match grouping::find_call(expr, &grouping::is_window_call) {
+                    Some(call) => err::msg(call, "window functions are not allowed in HAVING"),
+                    None => Ok(expr),
+                },
+            );
+        having = ctx.add_errs(&mut analyser, result);
+    }
+
+    let columns = analyse_projection(ctx, &mut analyser, &select.exprs);
+
+    let is_grouped = select.group_by.is_some()
+        || select.having.is_some()
+        || projection.iter().any(|output| match output {
+            OutputItem::Expr(expr, _) => {
+                grouping::find_call(expr, &grouping::is_aggregate_call).is_some()
+            }
+            OutputItem::Column(_) => false,
+        });
+    if is_grouped && is_group_by_valid {
+        let grouping = Grouping {
+            info: ctx.info,
+            tables: &analyser.tables,
+            dialect: analyser.dialect,
+            group_by,
+            columns: group_by_columns,
+        };
+        for select_expr in select.exprs.iter() {
+            let result = match select_expr {
+                SelectExpr::Expr { expr, .. } => grouping.check_expr(expr),
+                SelectExpr::WildCard { symbol, except } => {
+                    let columns =
+                        wildcard_columns(&analyser.tables, analyser.dialect, symbol, except);
+                    grouping.check_columns(select_expr.span(), columns)
+                }
+            };
+            ctx.add_err(result);
+        }
+        if let Some(expr) = having {
+            ctx.add_err(grouping.check_expr(expr));
+        }
+    }
+    columns
+}
+
+/// Columns of a `*` in the order it outputs them, what doesn't resolve is left out.
+fn wildcard_columns<'s>(
+    tables: &[(String, &'s Table)],
+    dialect: &dyn Dialect,
+    symbol: &grammar::Column<Token![*]>,
+    except: &Punctuated<grammar::Column<Name>, Token![,]>,
+) -> Vec<&'s Column> {
+    let excluded: Vec<_> = except.iter().map(|c| c.alias.value(dialect)).collect();
+    tables
+        .iter()
+        .filter(|(name, _)| {
+            symbol
+                .table_name
+                .as_ref()
+                .is_none_or(|table_name| dialect.matches_name(name, &table_name.value(dialect)))
+        })
+        .flat_map(|(_, table)| {
+            let mut columns: Vec<_> = table
+                .iter()
+                .filter(|(name, _)| !excluded.contains(name))
+                .map(|(_, column)| column)
+                .collect();
+            columns.sort_by_key(|column| column.ordinal_position);
+            columns
+        })
+        .collect()
+}
+
+/// Resolves the columns of a `SELECT` list (or `RETURNING` clause) against the tables in scope.
+pub fn analyse_projection(
+    ctx: &mut Ctx,
+    analyser: &mut AnalyseExpr,
+    exprs: &[SelectExpr],
+) -> Option<Vec<OutputColumn>> {
+    let mut columns = vec![];
+    let mut has_error = false;
+    for select_expr in exprs {
+        match select_expr {
+            SelectExpr::WildCard { symbol, except } => {
+                let span = select_expr.span();
+                if analyser.tables.is_empty() {
+                    has_error = true;
+                    ctx.add_err::<()>(err::msg(span, "SELECT * with no tables specified"));
+                    continue;
+                }
+                let tables = match &symbol.table_name {
+                    None => analyser.tables.clone(),
+                    Some(name) => {
+                        let name = name.value(analyser.dialect);
+                        let table = analyser
+                            .tables
+                            .iter()
+                            .find(|(n, _)| analyser.dialect.matches_name(n, &name));
+                        match table {
+                            Some(table) => vec![table.clone()],
+                            None => {
+                                has_error = true;
+                                ctx.add_err::<()>(err::msg(
+                                    symbol.table_name.as_ref().unwrap(),
+                                    format!("missing FROM-clause entry for table `{name}`"),
+                                ));
+                                continue;
+                            }
+                        }
+                    }
+                };
+                let mut excluded = vec![];
+                for column in except.iter() {
+                    if ctx
+                        .add_err(utils::resolve_column(&tables, ctx.dialect, column))
+                        .is_some()
+                    {
+                        excluded.push(column.alias.value(ctx.dialect));
+                    }
+                }
+                for (_, table) in tables {
+                    let mut table_columns: Vec<_> = table
+                        .iter()
+                        .filter(|(name, _)| !excluded.contains(name))
+                        .collect();
+
+                    table_columns.sort_by_key(|(_, column)| column.ordinal_position);
+                    columns.extend(
+                        table_columns
+                            .into_iter()
+                            .map(|(name, column)| OutputColumn {
+                                name: name.clone(),
+                                data_type: column.data_type.clone(),
+                                is_nullable: column.is_nullable,
+                                span,
+                            }),
+                    );
+                }
+            }
+            SelectExpr::Expr { expr, alias } => {
+                let result = analyser.analyse_expr(expr);
+                match ctx.add_errs(analyser, result) {
+                    Some(data_type) if !data_type.is_error() => {
+                        let term = utils::as_term(expr);
+                        columns.push(OutputColumn {
+                            name: match alias {
+                                Some(alias) => alias.value(ctx.dialect),
+                                None => term
+                                    .and_then(|term| column_name(ctx.dialect, term))
+                                    .unwrap_or("?column?".to_string()),
+                            },
+                            data_type,
+                            is_nullable: is_nullable(analyser, expr),
+                            span: select_expr.span(),
+                        })
+                    }
+                    _ => has_error = true,
+                }
+            }
+        }
+    }
+    (!has_error).then_some(columns)
+}
+
+/// Name Postgres gives to an output column without an alias.
+fn column_name(dialect: &dyn Dialect, term: &Term) -> Option<String> {
+    match term {
+        Term::Column(column) => Some(column.alias.value(dialect)),
+        Term::Expr(expr) => utils::as_term(expr).and_then(|term| column_name(dialect, term)),
+        Term::Case(_) => Some("case".to_string()),
+        Term::Func(call) => Some(call.function.name()),
+        Term::Cast(cast) => column_name(dialect, &cast.expr).or_else(|| {
+            let name = cast.type_name.name();
+            Some(name.rsplit(' ').next().unwrap_or(&name).to_string())
+        }),
+        _ => None,
+    }
+}
+
+/// Conservative: an expression is nullable unless it's known not to produce `NULL`.
+fn is_nullable(analyser: &AnalyseExpr, expr: &Expr) -> bool {
+    utils::as_term(expr).is_none_or(|term| is_term_nullable(analyser, term))
+}
+
+fn is_term_nullable(analyser: &AnalyseExpr, term: &Term) -> bool {
+    match term {
+        Term::Column(column) => utils::resolve_column(&analyser.tables, analyser.dialect, column)
+            .map_or(true, |column| column.is_nullable),
+        Term::Value(value) => matches!(value, Value::Null { .. }),
+        Term::Expr(expr) => is_nullable(analyser, expr),
+        // Without `ELSE`, a `CASE` yields `NULL` when no condition matches.
+        Term::Case(case) => {
+            case.else_clause.is_none() || case.results().any(|result| is_nullable(analyser, result))
+        }
+        Term::Cast(cast) => is_term_nullable(analyser, &cast.expr),
+        // Ranking functions and `count` always produce a value.
+        Term::Func(call) => !matches!(
+            call.function.name().as_str(),
+            "count" | "row_number" | "rank" | "dense_rank" | "percent_rank" | "cume_dist" | "ntile"
+        ),        // Any value may be bound to it.
+        Term::Placeholder(_) => true,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{analyse, columns};
+    use crate::DataType;
+
+    #[test]
+    fn set_operations() {
+        assert!(
+            analyse("SELECT id, name FROM users UNION SELECT user_id, title FROM posts").is_empty()
+        );
+        assert!(
+            analyse("SELECT id FROM users UNION ALL SELECT id FROM posts ORDER BY id LIMIT 5")
+                .is_empty()
+        );
+        assert!(
+            analyse("SELECT * FROM users EXCEPT SELECT id, title, title, 1 FROM posts").is_empty()
+        );
+
+        let errs = analyse("SELECT id, name FROM users INTERSECT SELECT id FROM posts");
+        assert!(errs[0].starts_with("each INTERSECT query must have the same number of columns"));
+
+        let errs = analyse("SELECT id, name FROM users UNION SELECT id, score FROM posts");
+        assert_eq!(errs, ["UNION types `Text` and `Real` cannot be matched"]);
+
+        let errs = analyse("SELECT id FROM users UNION SELECT id FROM posts ORDER BY name");
+        assert_eq!(
+            errs,
+            ["ORDER BY on a UNION/INTERSECT/EXCEPT result must be on one of the result columns"]
+        );
+    }
+
+    #[test]
+    fn grouping() {
+        assert!(analyse(
+            r#"SELECT age, COUNT(*), MAX(name) FROM users GROUP BY age HAVING SUM(id) > 10 ORDER BY age"#
+        )
+        .is_empty());
+        assert!(
+            analyse("SELECT age / 10 AS decade, COUNT(*) FROM users GROUP BY decade").is_empty()
+        );
+        assert!(analyse("SELECT (age / 10) + 1 FROM users GROUP BY 1").is_empty());
+        assert!(analyse("SELECT u.id, u.name FROM users AS u GROUP BY id, name").is_empty());
+        assert!(analyse("SELECT * FROM users GROUP BY id, name, email, age").is_empty());
+        // The primary key determines the other columns of its table.
+        assert!(analyse("SELECT name, COUNT(*) FROM users GROUP BY id").is_empty());
+        assert!(analyse("SELECT * FROM users GROUP BY id").is_empty());
+        // Positions count the columns of a `*`, 1 is `users.id`.
+        assert!(analyse("SELECT *, COUNT(*) FROM users GROUP BY 1").is_empty());
+        assert!(
+            analyse("SELECT u.id FROM users AS u GROUP BY u.id HAVING u.name IS NULL").is_empty()
+        );
+        assert!(
+            analyse("SELECT age, RANK() OVER (ORDER BY COUNT(*)) FROM users GROUP BY age")
+                .is_empty()
+        );
+
+        let errs = analyse("SELECT name, COUNT(*) FROM users");
+        assert_eq!(
+            errs,
+            ["column `users.name` must appear in the GROUP BY clause or be used in an aggregate function"]
+        );
+
+        let errs = analyse("SELECT age, name FROM users GROUP BY age");
+        assert_eq!(
+            errs,
+            ["column `users.name` must appear in the GROUP BY clause or be used in an aggregate function"]
+        );
+
+        // Only a primary key determines the other columns, a unique one may be null.
+        let errs = analyse("SELECT * FROM users GROUP BY email");
+        assert_eq!(
+            errs,
+            ["column `users.id` must appear in the GROUP BY clause or be used in an aggregate function"]
+        );
+
+        let errs = analyse("SELECT age FROM users GROUP BY age HAVING name IS NULL");
+        assert_eq!(
+            errs,
+            ["column `users.name` must appear in the GROUP BY clause or be used in an aggregate function"]
+        );
+
+        let errs = analyse("SELECT age FROM users GROUP BY 2");
+        assert_eq!(errs, ["GROUP BY position 2 is not in select list"]);
+
+        let errs = analyse("SELECT id FROM users WHERE COUNT(*) > 1");
+        assert_eq!(errs, ["aggregate functions are not allowed in WHERE"]);
+
+        let errs = analyse("SELECT COUNT(*) FROM users GROUP BY 1");
+        assert_eq!(errs, ["aggregate functions are not allowed in GROUP BY"]);
+
+        let errs = analyse("SELECT *, COUNT(*) FROM users GROUP BY 5");
+        assert_eq!(errs, ["aggregate functions are not allowed in GROUP BY"]);
+
+        let errs = analyse("SELECT * EXCEPT (id), COUNT(*) FROM users GROUP BY 1");
+        assert_eq!(
+            errs,
+            ["column `users.email` must appear in the GROUP BY clause or be used in an aggregate function"]
+        );
+    }
+
+    #[test]
+    fn output_columns() {
+        assert_eq!(
+            columns("SELECT u.*, 1 AS one FROM users AS u"),
+            [
+                ("id".to_string(), DataType::Integer, false),
+                ("name".to_string(), DataType::Text, true),
+                ("email".to_string(), DataType::CharacterVarying, false),
+                ("age".to_string(), DataType::SmallInt, true),
+                ("one".to_string(), DataType::Integer, false),
+            ]
+        );
+        assert_eq!(
+            columns("SELECT id, email FROM users UNION SELECT user_id, title FROM posts"),
+            [
+                ("id".to_string(), DataType::Integer, false),
+                ("email".to_string(), DataType::Text, false),
+            ]
+        );
+        assert_eq!(
+            columns(r#"INSERT INTO posts (user_id, title) VALUES (1, "a") RETURNING id, score"#),
+            [
+                ("id".to_string(), DataType::BigInt, false),
+                ("score".to_string(), DataType::Real, true),
+            ]
+        );
+        assert_eq!(
+            columns("DELETE FROM users WHERE id = 1 RETURNING * EXCEPT (name, email)"),
+            [
+                ("id".to_string(), DataType::Integer, false),
+                ("age".to_string(), DataType::SmallInt, true),
+            ]
+        );
+        assert_eq!(analyse("UPDATE users SET age = 1 RETURNING rank").len(), 1);
+        assert_eq!(
+            columns(
+                r#"SELECT CASE WHEN age > 1 THEN id ELSE 0 END, CASE age WHEN 1 THEN "x" END AS label FROM users"#
+            ),
+            [
+                ("case".to_string(), DataType::Integer, false),
+                ("label".to_string(), DataType::Text, true),
+            ]
+        );
+        assert_eq!(
+            columns(r#"SELECT id::text, CAST(age AS numeric(5)), DATE "2024-01-01" FROM users"#),
+            [
+                ("id".to_string(), DataType::Text, false),
+                ("age".to_string(), DataType::Numeric, true),
+                ("date".to_string(), DataType::Date, false),
+            ]
+        );
+    }
+}
@@ -1,8 +1,143 @@
-use crate::*;
+use super::*;
+use schema_info::IdentityGeneration;
 use sql_parser::command::update::*;
 
 impl SqlAnalyzer for Update {
-    fn analyse(&self, _: &mut Ctx) -> AnalyseResult {
+    fn analyse(&self, ctx: &mut Ctx) -> AnalyseResult {
+        let Some(table) = ctx.get_table(&self.table_name.alias) else {
+            return Ok(());
+        };
+        let mut tables = vec![(self.visible_name().value(ctx.dialect), table)];
+        if let Some((_, from)) = &self.from {
+            match ctx.get_table(&from.table_name.alias) {
+                Some(from_table) => {
+                    tables.push((from.visible_name().value(ctx.dialect), from_table))
+                }
+                None => return Ok(()),
+            }
+        }
+        let mut analyser = AnalyseExpr {
+            schema: ctx.info,
+            dialect: ctx.dialect,
+            tables,
+            windows: vec![],
+            errs: vec![],
+        };
+
+        let mut assigned = vec![];
+        for assignment in self.assignments.iter() {
+            analyse_assignment_item(ctx, &mut analyser, table, assignment, &mut assigned);
+        }
+
+        if let Some(expr) = &self.where_expr {
+            let result = analyser
+                .analyse_expr(expr)
+                .and_then(|ty| err::expect_bool(&ty, expr))
+                .and_then(|_| analyser.expect_no_set_returning(expr, "WHERE"));
+            ctx.add_errs(&mut analyser, result);
+        }
+        if let Some(returning) = &self.returning {
+            if let Some(columns) = select::analyse_projection(ctx, &mut analyser, &returning.exprs)
+            {
+                ctx.columns = columns;
+            }
+        }
         Ok(())
     }
 }
+
+pub(super) fn analyse_assignment_item(
+    ctx: &mut Ctx,
+    analyser: &mut AnalyseExpr,
+    table: &Table,
+    assignment: &Assignment,
+    assigned: &mut Vec<String>,
+) {
+    match assignment {
+        Assignment::Single { column, value, .. } => {
+            analyse_assignment(ctx, analyser, table, (column, value), assigned);
+        }
+        Assignment::Multiple {
+            columns, values, ..
+        } => {
+            if columns.len() != values.len() {
+                let message = match columns.len() < values.len() {
+                    true => "source for a multiple-column UPDATE item has too many values",
+                    false => "source for a multiple-column UPDATE item has too few values",
+                };
+                ctx.add_err::<()>(err::msg(assignment, message));
+                return;
+            }
+            for target in columns.iter().zip(values.iter()) {
+                analyse_assignment(ctx, analyser, table, target, assigned);
+            }
+        }
+    }
+}
+
+fn analyse_assignment(
+    ctx: &mut Ctx,
+    analyser: &mut AnalyseExpr,
+    table: &Table,
+    (name, value): (&Name, &InsertExpr),
+    assigned: &mut Vec<String>,
+) {
+    let Some(column) = ctx.add_err(utils::get_column(table, ctx.dialect, name)) else {
+        return;
+    };
+    let column_name = name.value(ctx.dialect);
+    if assigned.contains(&column_name) {
+        ctx.add_err::<()>(err::msg(
+            name,
+            format!("multiple assignments to same column `{column_name}`"),
+        ));
+        return;
+    }
+    assigned.push(column_name.clone());
+
+    let is_generated_always =
+        column.identity_generation == Some(IdentityGeneration::Always) || column.is_generated;
+
+    if is_generated_always && !matches!(value, InsertExpr::Default(_)) {
+        ctx.add_err::<()>(err::msg(
+            name,
+            format!("column `{name}` can only be updated to DEFAULT"),
+        ));
+        return;
+    }
+    analyse_value(ctx, analyser, &column_name, column, value);
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::analyse;
+
+    #[test]
+    fn assignments() {
+        assert!(analyse(
+            r#"UPDATE users u SET name = "x", (age, email) = (age + 1, name) FROM posts p WHERE u.id = p.user_id RETURNING *"#
+        )
+        .is_empty());
+
+        let errs =
+            analyse("UPDATE users SET email = NULL, age = DEFAULT, id = 5, name = 1, age = 2");
+        assert_eq!(
+            errs,
+            [
+                "null value in column `email` violates not-null constraint",
+                "column `id` can only be updated to DEFAULT",
+                "multiple assignments to same column `age`",
+            ]
+        );
+
+        let errs =
+            analyse(r#"UPDATE posts SET user_id = "x", (title, score) = ("a") WHERE id = 1"#);
+        assert_eq!(
+            errs,
+            [
+                "column `user_id` is of type `Integer` but expression is of type `Text`",
+                "source for a multiple-column UPDATE item has too few values",
+            ]
+        );
+    }
+}
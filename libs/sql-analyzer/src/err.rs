@@ -6,15 +6,29 @@ pub fn msg<T>(span: impl GetSpan, msg: impl Into<String>) -> Result<T, AnalyseEr
 }
 
 pub fn expect_bool(ty: &DataType, span: impl GetSpan) -> Result<(), AnalyseError> {
-    if !ty.is_bool() {
+    if !ty.is_bool() && !ty.is_error() {
         return msg(span, format!("expected `boolean` type, found `{ty:?}`"));
     }
     Ok(())
 }
 
 pub fn expect_numeric(ty: &DataType, span: impl GetSpan) -> Result<(), AnalyseError> {
-    if !ty.is_numeric() {
+    if !ty.is_numeric() && !ty.is_error() {
         return msg(span, format!("expected `numeric` type, found `{ty:?}`"));
     }
     Ok(())
 }
+
+pub fn expect_integer(ty: &DataType, span: impl GetSpan) -> Result<(), AnalyseError> {
+    if !ty.is_integer() && !ty.is_error() {
+        return msg(span, format!("expected `integer` type, found `{ty:?}`"));
+    }
+    Ok(())
+}
+
+pub fn expect_text(ty: &DataType, span: impl GetSpan) -> Result<(), AnalyseError> {
+    if !ty.is_text() && !ty.is_error() {
+        return msg(span, format!("expected `text` type, found `{ty:?}`"));
+    }
+    Ok(())
+}
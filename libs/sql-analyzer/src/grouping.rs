@@ -0,0 +1,365 @@
+//! Validity of aggregate and grouped queries.
+//!
+//! Ref: https://www.postgresql.org/docs/current/queries-table-expressions.html#QUERIES-GROUP
+use crate::analyzer::AnalyseExpr;
+use crate::catalog::{Catalog, FunctionKind};
+use crate::utils::{self, Scope};
+use crate::{err, AnalyseError, Column, SchemaInfo};
+use sql_parser::dialect::Dialect;
+use sql_parser::function::FunctionCall;
+use sql_parser::grammar::{ast::*, Name, Term, Value};
+use sql_parser::visit::{self, Visit};
+use sql_parser::window::NameOrSpec;
+use sql_parser::GetSpan;
+
+/// An aggregate call computes one value per group, with `OVER` it's a window call instead.
+pub fn is_aggregate_call(call: &FunctionCall) -> bool {
+    call.over.is_none()
+        && Catalog::builtins().kind(&call.function.name()) == Some(FunctionKind::Aggregate)
+}
+
+pub fn is_window_call(call: &FunctionCall) -> bool {
+    call.over.is_some()
+}
+
+/// Rejects aggregate and window function calls in a clause that is evaluated per row,
+/// e.g. `WHERE` or `GROUP BY`.
+pub fn expect_no_aggregates(expr: &Expr, clause: &str) -> Result<(), AnalyseError> {
+    if let Some(call) = find_call(expr, &is_aggregate_call) {
+        return err::msg(
+            call,
+            format!("aggregate functions are not allowed in {clause}"),
+        );
+    }
+    if let Some(call) = find_call(expr, &is_window_call) {
+        return err::msg(
+            call,
+            format!("window functions are not allowed in {clause}"),
+        );
+    }
+    Ok(())
+}
+
+/// First function call in `expr` matching `pred`, in evaluation order.
+pub fn find_call<'e>(
+    expr: &'e Expr,
+    pred: &impl Fn(&FunctionCall) -> bool,
+) -> Option<&'e FunctionCall> {
+    struct FindCall<'e, P> {
+        pred: P,
+        found: Option<&'e FunctionCall>,
+    }
+
+    impl<'e, P: Fn(&FunctionCall) -> bool> Visit<'e> for FindCall<'e, P> {
+        fn visit_function_call(&mut self, call: &'e FunctionCall) {
+            if self.found.is_some() {
+                return;
+            }
+            match (self.pred)(call) {
+                true => self.found = Some(call),
+                false => visit::visit_function_call(self, call),
+            }
+        }
+    }
+
+    let mut finder = FindCall { pred, found: None };
+    finder.visit_expr(expr);
+    finder.found
+}
+
+fn sub_exprs(expr: &Expr) -> Vec<&Expr> {
+    match expr {
+        Expr::Term(term) => term_exprs(term),
+        Expr::Unary { expr, .. } => vec![expr],
+        Expr::Binary { left, right, .. } => vec![left, right],
+        Expr::Predicate { expr, predicate } => {
+            let mut exprs = vec![expr.as_ref()];
+            match predicate.as_ref() {
+                Predicate::In { list, .. } => exprs.extend(list.iter()),
+                Predicate::Between { low, high, .. } => exprs.extend([low, high]),
+                Predicate::Like {
+                    pattern, escape, ..
+                } => {
+                    exprs.push(pattern);
+                    exprs.extend(escape.as_ref().map(|(_, escape)| escape));
+                }
+                Predicate::Is { predicate, .. } => {
+                    if let IsPredicate::DistinctFrom { expr, .. } = predicate {
+                        exprs.push(expr);
+                    }
+                }
+            }
+            exprs
+        }
+    }
+}
+
+/// Expressions nested in `term`. A `CAST` wraps a term rather than an expression,
+/// so it has none and callers look through it themselves.
+fn term_exprs(term: &Term) -> Vec<&Expr> {
+    match term {
+        Term::Value(Value::ARRAY(exprs)) => exprs.iter().collect(),
+        Term::Value(_) | Term::Column(_) | Term::Cast(_) | Term::Placeholder(_) => vec![],
+        Term::Func(call) => {
+            let mut exprs = call.function.exprs();
+            exprs.extend(call.filter.as_ref().map(|filter| &filter.condition));
+            if let Some(NameOrSpec::Spec(spec)) = call.over.as_ref().map(|w| &w.name_or_spec) {
+                if let Some(partition_by) = &spec.partition_by {
+                    exprs.extend(partition_by.exprs.iter());
+                }
+                if let Some(order_by) = &spec.order_by {
+                    exprs.extend(order_by.sort_specs.iter().map(|spec| &spec.expr));
+                }
+            }
+            exprs
+        }
+        Term::Expr(expr) => vec![expr],
+        Term::Case(case) => {
+            let mut exprs: Vec<_> = case.operand.iter().collect();
+            for clause in &case.when_clauses {
+                exprs.extend([&clause.condition, &clause.result]);
+            }
+            exprs.extend(case.else_clause.as_ref().map(|(_, expr)| expr));
+            exprs
+        }
+    }
+}
+
+/// Once a query is grouped, a column may only be referenced if it's grouped,
+/// or within an aggregate call.
+pub struct Grouping<'a> {
+    pub info: &'a SchemaInfo,
+    pub tables: &'a Scope<'a>,
+    pub dialect: &'a dyn Dialect,
+    pub group_by: Vec<&'a Expr>,
+    /// Columns of a `*` that a `GROUP BY` position refers to.
+    pub columns: Vec<&'a Column>,
+}
+
+/// An output column of a `SELECT` list, as `GROUP BY` positions count them.
+#[derive(Clone, Copy)]
+pub enum OutputItem<'a> {
+    Expr(&'a Expr, Option<&'a Name>),
+    /// A column of an expanded `*`.
+    Column(&'a Column),
+}
+
+impl Grouping<'_> {
+    pub fn check_expr(&self, expr: &Expr) -> Result<(), AnalyseError> {
+        if self.group_by.iter().any(|g| self.same_expr(g, expr)) {
+            return Ok(());
+        }
+        match expr {
+            Expr::Term(term) => self.check_term(term),
+            _ => sub_exprs(expr)
+                .into_iter()
+                .try_for_each(|e| self.check_expr(e)),
+        }
+    }
+
+    fn check_term(&self, term: &Term) -> Result<(), AnalyseError> {
+        let is_grouped = self
+            .group_by
+            .iter()
+            .filter_map(|g| utils::as_term(g))
+            .any(|g| self.same_term(g, term));
+        if is_grouped {
+            return Ok(());
+        }
+        match term {
+            Term::Column(column) => match utils::resolve_column(self.tables, self.dialect, column) {
+                Ok(resolved)
+                    if self.columns.iter().any(|c| std::ptr::eq(*c, resolved))
+                        || self.is_grouped_by_primary_key(resolved) =>
+                {
+                    Ok(())
+                }
+                Ok(resolved) => Err((
+                    column.span(),
+                    format!(
+                        "column `{}` must appear in the GROUP BY clause or be used in an aggregate function",
+                        self.qualified_name(resolved)
+                    ),
+                )),
+                // Already reported while analysing the expression.
+                Err(_) => Ok(()),
+            },
+            Term::Func(call) if is_aggregate_call(call) => Ok(()),
+            Term::Cast(cast) => self.check_term(&cast.expr),
+            _ => term_exprs(term)
+                .into_iter()
+                .try_for_each(|e| self.check_expr(e)),
+        }
+    }
+
+    /// `SELECT *` of a grouped query, each of the `columns` must be grouped.
+    pub fn check_columns<'c>(
+        &self,
+        span: impl GetSpan,
+        columns: impl IntoIterator<Item = &'c Column>,
+    ) -> Result<(), AnalyseError> {
+        for column in columns {
+            if !self.is_column_grouped(column) && !self.is_grouped_by_primary_key(column) {
+                return err::msg(
+                    span,
+                    format!(
+                        "column `{}` must appear in the GROUP BY clause or be used in an aggregate function",
+                        self.qualified_name(column)
+                    ),
+                );
+            }
+        }
+        Ok(())
+    }
+
+    /// Whether `column` itself is a `GROUP BY` item.
+    fn is_column_grouped(&self, column: &Column) -> bool {
+        self.columns.iter().any(|c| std::ptr::eq(*c, column))
+            || self.group_by.iter().any(|g| match utils::as_term(g) {
+                Some(Term::Column(g)) => utils::resolve_column(self.tables, self.dialect, g)
+                    .is_ok_and(|g| std::ptr::eq(g, column)),
+                _ => false,
+            })
+    }
+
+    /// A primary key determines the other columns of its table, so they are grouped along with
+    /// all of its columns.
+    ///
+    /// Ref: https://www.postgresql.org/docs/current/sql-select.html#SQL-GROUPBY
+    fn is_grouped_by_primary_key(&self, column: &Column) -> bool {
+        let Some((_, table)) = self
+            .tables
+            .iter()
+            .find(|(_, table)| table.values().any(|c| std::ptr::eq(c, column)))
+        else {
+            return false;
+        };
+        let Some(table_name) = self.info.get_public_tables().and_then(|tables| {
+            tables
+                .iter()
+                .find(|(_, t)| std::ptr::eq(*t, *table))
+                .map(|(name, _)| name)
+        }) else {
+            return false;
+        };
+        self.info
+            .get_unique_indexes(table_name)
+            .iter()
+            .find(|index| index.is_primary)
+            .is_some_and(|primary_key| {
+                primary_key.columns.iter().all(|name| {
+                    table
+                        .get(name)
+                        .is_some_and(|column| self.is_column_grouped(column))
+                })
+            })
+    }
+
+    fn qualified_name(&self, column: &Column) -> String {
+        self.tables
+            .iter()
+            .find_map(|(table_name, table)| {
+                table
+                    .iter()
+                    .find(|(_, c)| std::ptr::eq(*c, column))
+                    .map(|(name, _)| format!("{table_name}.{name}"))
+            })
+            .unwrap_or_default()
+    }
+
+    /// Structural equality, good enough to match a projected expression with a `GROUP BY` one.
+    fn same_expr(&self, a: &Expr, b: &Expr) -> bool {
+        match (a, b) {
+            (Expr::Term(a), Expr::Term(b)) => self.same_term(a, b),
+            (Expr::Term(Term::Expr(a)), b) | (b, Expr::Term(Term::Expr(a))) => self.same_expr(a, b),
+            (Expr::Unary { op, expr }, Expr::Unary { op: op_b, expr: b }) => {
+                op.kind == op_b.kind && self.same_expr(expr, b)
+            }
+            (
+                Expr::Binary { left, op, right },
+                Expr::Binary {
+                    left: left_b,
+                    op: op_b,
+                    right: right_b,
+                },
+            ) => {
+                op.kind == op_b.kind
+                    && self.same_expr(left, left_b)
+                    && self.same_expr(right, right_b)
+            }
+            _ => false,
+        }
+    }
+
+    fn same_term(&self, a: &Term, b: &Term) -> bool {
+        match (a, b) {
+            (Term::Expr(a), Term::Expr(b)) => self.same_expr(a, b),
+            (Term::Expr(a), b) | (b, Term::Expr(a)) => {
+                utils::as_term(a).is_some_and(|a| self.same_term(a, b))
+            }
+            (Term::Value(a), Term::Value(b)) => format!("{a:?}") == format!("{b:?}"),
+            (Term::Column(a), Term::Column(b)) => {
+                match (
+                    utils::resolve_column(self.tables, self.dialect, a),
+                    utils::resolve_column(self.tables, self.dialect, b),
+                ) {
+                    (Ok(a), Ok(b)) => std::ptr::eq(a, b),
+                    _ => false,
+                }
+            }
+            (Term::Func(a), Term::Func(b)) => {
+                let (args_a, args_b) = (a.function.exprs(), b.function.exprs());
+                a.over.is_none()
+                    && b.over.is_none()
+                    && a.filter.is_none()
+                    && b.filter.is_none()
+                    && a.function.name() == b.function.name()
+                    && args_a.len() == args_b.len()
+                    && args_a
+                        .iter()
+                        .zip(&args_b)
+                        .all(|(a, b)| self.same_expr(a, b))
+            }
+            (Term::Cast(a), Term::Cast(b)) => {
+                a.type_name.name() == b.type_name.name() && self.same_term(&a.expr, &b.expr)
+            }
+            _ => false,
+        }
+    }
+}
+
+/// A `GROUP BY` item may be an output column position or name instead of an expression.
+pub fn resolve_group_by_item<'a>(
+    analyser: &AnalyseExpr,
+    projection: &[OutputItem<'a>],
+    item: &'a Expr,
+) -> Result<OutputItem<'a>, AnalyseError> {
+    match utils::as_term(item) {
+        Some(Term::Value(Value::Int(pos))) => match pos.base10_parse::<usize>() {
+            Ok(n) if (1..=projection.len()).contains(&n) => Ok(projection[n - 1]),
+            _ => err::msg(
+                pos.span(),
+                format!("GROUP BY position {pos} is not in select list"),
+            ),
+        },
+        // An input column takes precedence over an output column of the same name,
+        // which the columns of a `*` always are.
+        Some(Term::Column(column))
+            if column.table_name.is_none()
+                && utils::resolve_column(&analyser.tables, analyser.dialect, column).is_err() =>
+        {
+            let name = column.alias.value(analyser.dialect);
+            Ok(projection
+                .iter()
+                .find(|output| match output {
+                    OutputItem::Expr(_, alias) => {
+                        alias.is_some_and(|alias| alias.value(analyser.dialect) == name)
+                    }
+                    OutputItem::Column(_) => false,
+                })
+                .copied()
+                .unwrap_or(OutputItem::Expr(item, None)))
+        }
+        _ => Ok(OutputItem::Expr(item, None)),
+    }
+}
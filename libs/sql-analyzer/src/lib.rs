@@ -1,10 +1,16 @@
-mod command;
 mod analyzer;
+mod catalog;
+mod command;
+mod err;
+mod grouping;
+mod pattern;
 mod schema_info;
 mod utils;
-mod err;
 
-use schema_info::{Column, DataType, SchemaInfo, Table};
+pub use catalog::{Catalog, FunctionKind, Overload, ParamType, ResolveError, Volatility};
+pub use schema_info::DataType;
+use schema_info::{Column, SchemaInfo, Table};
+use sql_parser::dialect::Dialect;
 use sql_parser::grammar::Name;
 use std::{env, error::Error};
 use syn::__private::Span;
@@ -14,7 +20,30 @@ type AnalyseError = (Span, String);
 
 pub struct Ctx<'s> {
     pub info: &'s SchemaInfo,
+    pub dialect: &'s dyn Dialect,
+    pub errs: Vec<AnalyseError>,
+    /// Columns returned by the command, from its `SELECT` list or `RETURNING` clause.
+    pub columns: Vec<OutputColumn>,
+}
+
+/// A column of the result set of a command.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OutputColumn {
+    pub name: String,
+    pub data_type: DataType,
+    pub is_nullable: bool,
+    /// Span of the `SelectExpr` that produced this column.
+    #[cfg_attr(feature = "serde", serde(with = "sql_parser::tokens"))]
+    pub span: Span,
+}
+
+#[derive(Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Analysis {
+    #[cfg_attr(feature = "serde", serde(with = "sql_parser::tokens"))]
     pub errs: Vec<AnalyseError>,
+    pub columns: Vec<OutputColumn>,
 }
 
 impl<'s> Ctx<'s> {
@@ -28,12 +57,22 @@ impl<'s> Ctx<'s> {
         }
     }
 
+    /// Adds the errors `analyser` recovered from, then the one of `r`.
+    pub fn add_errs<T>(
+        &mut self,
+        analyser: &mut analyzer::AnalyseExpr,
+        r: Result<T, AnalyseError>,
+    ) -> Option<T> {
+        self.errs.append(&mut analyser.errs);
+        self.add_err(r)
+    }
+
     pub fn get_table(&mut self, name: &Name) -> Option<&'s Table> {
-        self.add_err(utils::get_table(self.info, name))
+        self.add_err(utils::get_table(self.info, self.dialect, name))
     }
 
     pub fn get_column(&mut self, table: &'s Table, name: &Name) -> Option<&'s Column> {
-        self.add_err(utils::get_column(table, name))
+        self.add_err(utils::get_column(table, self.dialect, name))
     }
 }
 
@@ -63,15 +102,222 @@ thread_local! {
     };
 }
 
-pub fn analyse_command(c: sql_parser::command::Command) -> Vec<(Span, String)> {
-    let mut errs = vec![];
+pub fn analyse_command(c: sql_parser::command::Command, dialect: &dyn Dialect) -> Analysis {
+    let mut analysis = Analysis::default();
     SCHEMA_INFO.with(|v| {
         if let Some(info) = v {
-            let mut ctx = Ctx { info, errs: vec![] };
-            if let Ok(_) = c.analyse(&mut ctx) {
-                errs = ctx.errs;
+            let mut ctx = Ctx {
+                info,
+                dialect,
+                errs: vec![],
+                columns: vec![],
+            };
+            if c.analyse(&mut ctx).is_ok() {
+                analysis.errs = ctx.errs;
+                analysis.columns = ctx.columns;
             }
         }
     });
-    errs
+    analysis
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use schema_info::{Schema, UniqueIndex};
+
+    /// ```sql
+    /// CREATE TABLE users (
+    ///     id integer GENERATED ALWAYS AS IDENTITY PRIMARY KEY,
+    ///     name text,
+    ///     email character varying NOT NULL UNIQUE,
+    ///     age smallint
+    /// );
+    /// CREATE TABLE posts (
+    ///     id bigserial PRIMARY KEY,
+    ///     user_id integer NOT NULL,
+    ///     title text NOT NULL,
+    ///     score real,
+    ///     UNIQUE (user_id, title)
+    /// );
+    /// CREATE EXTENSION pg_trgm; -- similarity(text, text) -> real
+    /// CREATE FUNCTION full_name(first text, last text DEFAULT '') RETURNS text ..;
+    /// CREATE FUNCTION active_user_ids(since date DEFAULT NULL) RETURNS SETOF integer ..;
+    /// CREATE PROCEDURE transfer(from_id integer, to_id integer, amount numeric DEFAULT 0) ..;
+    /// ```
+    type TableDef<'a> = (&'a str, &'a [(&'a str, DataType, bool)]);
+
+    pub fn schema_info() -> SchemaInfo {
+        let tables: [TableDef; 2] = [
+            (
+                "users",
+                &[
+                    ("id", DataType::Integer, false),
+                    ("name", DataType::Text, true),
+                    ("email", DataType::CharacterVarying, false),
+                    ("age", DataType::SmallInt, true),
+                ],
+            ),
+            (
+                "posts",
+                &[
+                    ("id", DataType::BigInt, false),
+                    ("user_id", DataType::Integer, false),
+                    ("title", DataType::Text, false),
+                    ("score", DataType::Real, true),
+                ],
+            ),
+        ];
+        let mut schema = Schema::new();
+        let public = schema.entry("public".to_string()).or_default();
+        for (table_name, columns) in tables {
+            let table = public.entry(table_name.to_string()).or_default();
+            for (i, (name, data_type, is_nullable)) in columns.iter().enumerate() {
+                table.insert(
+                    name.to_string(),
+                    Column {
+                        ordinal_position: i as i32 + 1,
+                        is_nullable: *is_nullable,
+                        data_type: data_type.clone(),
+                        ..Default::default()
+                    },
+                );
+            }
+        }
+        let public = schema.get_mut("public").unwrap();
+        let users_id = public.get_mut("users").unwrap().get_mut("id").unwrap();
+        users_id.identity_generation = Some(schema_info::IdentityGeneration::Always);
+
+        let posts_id = public.get_mut("posts").unwrap().get_mut("id").unwrap();
+        posts_id.default = Some("nextval('posts_id_seq'::regclass)".to_string());
+
+        let mut info = SchemaInfo {
+            schema,
+            ..Default::default()
+        };
+        let indexes = info.unique_indexes.entry("public".to_string()).or_default();
+        for (table, name, columns) in [
+            ("users", "users_pkey", &["id"][..]),
+            ("users", "users_email_key", &["email"]),
+            ("posts", "posts_pkey", &["id"]),
+            ("posts", "posts_user_id_title_key", &["user_id", "title"]),
+        ] {
+            indexes
+                .entry(table.to_string())
+                .or_default()
+                .push(UniqueIndex {
+                    name: name.to_string(),
+                    constraint_name: Some(name.to_string()),
+                    columns: columns.iter().map(|c| c.to_string()).collect(),
+                    is_primary: name.ends_with("_pkey"),
+                    ..Default::default()
+                });
+        }
+        for signature in [
+            "similarity(text, text) -> real",
+            "stable full_name(text, text default) -> text",
+            "stable active_user_ids(date default) -> setof integer",
+            "procedure volatile transfer(integer, integer, numeric default) -> void",
+        ] {
+            info.functions
+                .insert(catalog::parse_signature(signature).unwrap());
+        }
+        info
+    }
+
+    pub fn analyse(sql: &str) -> Vec<String> {
+        analysis(sql).errs.into_iter().map(|(_, msg)| msg).collect()
+    }
+
+    pub fn analysis(sql: &str) -> Analysis {
+        analysis_in(&sql_parser::dialect::Postgres, sql)
+    }
+
+    pub fn analysis_in(dialect: &dyn Dialect, sql: &str) -> Analysis {
+        let info = schema_info();
+        let command = syn::parse_str::<sql_parser::command::Command>(sql).unwrap();
+        let mut ctx = Ctx {
+            info: &info,
+            dialect,
+            errs: vec![],
+            columns: vec![],
+        };
+        command.analyse(&mut ctx).unwrap();
+        Analysis {
+            errs: ctx.errs,
+            columns: ctx.columns,
+        }
+    }
+
+    #[test]
+    fn identifiers() {
+        use sql_parser::dialect::{MySql, Sqlite, H2};
+        assert_eq!(
+            columns(r#"SELECT ID, U.Name, r"age" AS r"Age" FROM Users u"#),
+            [
+                ("id".to_string(), DataType::Integer, false),
+                ("name".to_string(), DataType::Text, true),
+                ("Age".to_string(), DataType::SmallInt, true),
+            ]
+        );
+        assert_eq!(
+            analyse(r#"SELECT r"Name" FROM r"Users""#),
+            ["table does not exist: `Users`, quoted names are case sensitive \nsuggest: users"]
+        );
+        assert_eq!(
+            analyse(r#"SELECT r"Name" FROM users"#),
+            ["column does not exist: `Name`, quoted names are case sensitive \nsuggest: name"]
+        );
+        let errs: Vec<_> = analysis_in(&H2, r#"SELECT r"id" FROM users"#)
+            .errs
+            .into_iter()
+            .map(|(_, msg)| msg)
+            .collect();
+        assert_eq!(
+            errs,
+            ["table does not exist: `USERS`, unquoted names are folded to `USERS` by H2 \nsuggest: \"users\""]
+        );
+        assert!(analysis_in(&H2, r#"SELECT r"id" FROM r"users""#)
+            .errs
+            .is_empty());
+
+        // SQLite and MySQL match names regardless of case, quoted or not.
+        for dialect in [&Sqlite as &dyn Dialect, &MySql] {
+            for sql in [
+                "SELECT ID, U.Name FROM Users AS u",
+                r#"SELECT r"ID" FROM r"USERS" WHERE users.AGE > 1"#,
+            ] {
+                let analysis = analysis_in(dialect, sql);
+                assert!(analysis.errs.is_empty(), "{}: {sql}", dialect.name());
+            }
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn json() {
+        let json = serde_json::to_value(analysis("SELECT id, name, nope FROM users")).unwrap();
+        assert_eq!(
+            json["errs"][0][0],
+            serde_json::json!({ "start": { "line": 1, "column": 18 }, "end": { "line": 1, "column": 22 } })
+        );
+
+        let json = serde_json::to_value(analysis("SELECT id, name FROM users")).unwrap();
+        assert_eq!(json["columns"][1]["name"], "name");
+        assert_eq!(json["columns"][1]["data_type"], "Text");
+
+        let analysis: Analysis = serde_json::from_value(json).unwrap();
+        assert_eq!(analysis.columns[0].data_type, DataType::Integer);
+    }
+
+    /// `(name, type, nullable)` of the result columns.
+    pub fn columns(sql: &str) -> Vec<(String, DataType, bool)> {
+        let analysis = analysis(sql);
+        assert!(analysis.errs.is_empty(), "{:?}", analysis.errs);
+        analysis
+            .columns
+            .into_iter()
+            .map(|c| (c.name, c.data_type, c.is_nullable))
+            .collect()
+    }
 }
@@ -0,0 +1,123 @@
+//! Compile time validation of literal `LIKE` and regular expression patterns.
+//! Non literal patterns are only checked at runtime by the database.
+
+use crate::{err, utils, AnalyseError};
+use regex_syntax::ast::{parse::Parser, ErrorKind};
+use sql_parser::grammar::ast::{Expr, LikeKind};
+use syn::__private::Span;
+
+/// `LIKE`, `ILIKE` and `SIMILAR TO` pattern, `\` is the default escape character.
+pub fn check_like(
+    kind: LikeKind,
+    pattern: &Expr,
+    escape: Option<&Expr>,
+) -> Result<(), AnalyseError> {
+    let escape =
+        match escape {
+            None => Some('\\'),
+            Some(escape) => match utils::as_str_literal(escape) {
+                None => return Ok(()),
+                Some(lit) => {
+                    let value = lit.value();
+                    let mut chars = value.chars();
+                    match (chars.next(), chars.next()) {
+                        (ch, None) => ch,
+                        _ => return err::msg(
+                            lit.span(),
+                            "invalid escape string, escape string must be empty or one character",
+                        ),
+                    }
+                }
+            },
+        };
+    let Some(lit) = utils::as_str_literal(pattern) else {
+        return Ok(());
+    };
+    let value = lit.value();
+    let mut chars = value.chars();
+    while let Some(ch) = chars.next() {
+        if Some(ch) == escape && chars.next().is_none() {
+            return err::msg(
+                lit.span(),
+                "LIKE pattern must not end with escape character",
+            );
+        }
+    }
+    match kind {
+        LikeKind::SimilarTo => check_regex_str(&similar_to_regex(&value, escape), lit.span()),
+        LikeKind::Like | LikeKind::ILike => Ok(()),
+    }
+}
+
+/// The POSIX regular expression a `SIMILAR TO` pattern is matched as: `%` and `_` are
+/// wildcards, `.`, `^` and `$` are plain chars and the whole text has to match.
+///
+/// Ref: `similar_escape` in https://github.com/postgres/postgres/blob/master/src/backend/utils/adt/regexp.c
+fn similar_to_regex(pattern: &str, escape: Option<char>) -> String {
+    let mut regex = String::from("^(?:");
+    let mut in_brackets = false;
+    let mut chars = pattern.chars();
+    while let Some(ch) = chars.next() {
+        match ch {
+            _ if Some(ch) == escape => {
+                if let Some(ch) = chars.next() {
+                    regex.push('\\');
+                    regex.push(ch);
+                }
+            }
+            ']' if in_brackets => {
+                in_brackets = false;
+                regex.push(ch);
+            }
+            _ if in_brackets => regex.push(ch),
+            '[' => {
+                in_brackets = true;
+                regex.push(ch);
+            }
+            '%' => regex.push_str(".*"),
+            '_' => regex.push('.'),
+            '.' | '^' | '$' => {
+                regex.push('\\');
+                regex.push(ch);
+            }
+            _ => regex.push(ch),
+        }
+    }
+    regex.push_str(")$");
+    regex
+}
+
+/// POSIX regular expression used with `~`, `~*`, `!~` and `!~*`.
+pub fn check_regex(pattern: &Expr) -> Result<(), AnalyseError> {
+    match utils::as_str_literal(pattern) {
+        Some(lit) => check_regex_str(&lit.value(), lit.span()),
+        None => Ok(()),
+    }
+}
+
+fn check_regex_str(regex: &str, span: Span) -> Result<(), AnalyseError> {
+    let Err(error) = Parser::new().parse(regex) else {
+        return Ok(());
+    };
+    // Postgres "advanced regular expressions" support backreferences, lookarounds and
+    // escapes like `\m` or `\y`, so only structural errors are reported.
+    let is_malformed = matches!(
+        error.kind(),
+        ErrorKind::ClassUnclosed
+            | ErrorKind::ClassRangeInvalid
+            | ErrorKind::GroupUnclosed
+            | ErrorKind::GroupUnopened
+            | ErrorKind::RepetitionMissing
+            | ErrorKind::RepetitionCountInvalid
+            | ErrorKind::RepetitionCountUnclosed
+            | ErrorKind::RepetitionCountDecimalEmpty
+            | ErrorKind::EscapeUnexpectedEof
+    );
+    if is_malformed {
+        return err::msg(
+            span,
+            format!("invalid regular expression: {}", error.kind()),
+        );
+    }
+    Ok(())
+}
@@ -1,35 +1,79 @@
+use crate::catalog::{Catalog, FunctionKind, Overload, ParamType, Volatility};
 use postgres::NoTls;
 use std::{collections::BTreeMap, error::Error};
 
 pub type Table = BTreeMap<String, Column>;
 pub type Tables = BTreeMap<String, Table>;
 pub type Schema = BTreeMap<String, Tables>;
+/// Unique indexes of each table, by table name.
+pub type UniqueIndexes = BTreeMap<String, Vec<UniqueIndex>>;
 
-#[derive(Debug)]
-pub struct SchemaInfo(Schema);
+#[derive(Debug, Default)]
+pub struct SchemaInfo {
+    pub(crate) schema: Schema,
+    pub(crate) unique_indexes: BTreeMap<String, UniqueIndexes>,
+    /// Functions and procedures of the `public` schema, such as PL/pgSQL or extension ones.
+    pub(crate) functions: Catalog,
+}
 
 impl SchemaInfo {
     pub fn get_tables(&self, schema_name: impl AsRef<str>) -> Option<&Tables> {
-        self.0.get(schema_name.as_ref())
+        self.schema.get(schema_name.as_ref())
     }
 
     pub fn get_public_tables(&self) -> Option<&Tables> {
-        self.0.get("public").or_else(|| {
-            if self.0.len() == 1 {
-                self.0.values().next()
+        self.schema.get("public").or_else(|| {
+            if self.schema.len() == 1 {
+                self.schema.values().next()
             } else {
                 None
             }
         })
     }
+
+    /// Primary keys and unique indexes of a table in the `public` schema.
+    pub fn get_unique_indexes(&self, table_name: &str) -> &[UniqueIndex] {
+        self.unique_indexes
+            .get("public")
+            .and_then(|tables| tables.get(table_name))
+            .map_or(&[], Vec::as_slice)
+    }
+
+    /// Whether a column of any table uses the user defined type (enum, domain ..) `name`.
+    pub fn has_user_type(&self, name: &str) -> bool {
+        self.schema
+            .values()
+            .flat_map(|tables| tables.values())
+            .flat_map(|table| table.values())
+            .any(|column| matches!(&column.data_type, DataType::UserDefined { name: n } if n == name))
+    }
 }
 
+/// A primary key, unique constraint or unique index.
+#[derive(Debug, Default, Clone)]
+pub struct UniqueIndex {
+    pub name: String,
+    /// Name of the constraint backing the index, if any.
+    pub constraint_name: Option<String>,
+    /// Key columns, in index order.
+    pub columns: Vec<String>,
+    pub is_primary: bool,
+    /// Partial index: `CREATE UNIQUE INDEX .. WHERE <predicate>`
+    pub is_partial: bool,
+    /// Index with expression keys, such as `lower(email)`.
+    pub has_expressions: bool,
+}
+
+#[allow(clippy::upper_case_acronyms)]
 #[derive(Debug, Default, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum DataType {
     #[default]
     Unknown,
 
     CharacterVarying,
+    /// `char(n)`, blank padded
+    Character,
     Text,
 
     /// TRUE, FALSE, UNKNOWN (NULL)
@@ -50,9 +94,43 @@ pub enum DataType {
     /// generic number type
     Numeric,
 
+    Date,
+    /// `time without time zone`
+    Time,
+    /// `timestamp without time zone`
+    Timestamp,
+    /// `timestamp with time zone`
+    TimestampWithTimeZone,
+    Interval,
+
+    Uuid,
+    Json,
+    Jsonb,
+    Bytea,
+
     Array {
         ty: Box<DataType>,
     },
+    /// Enum, domain or composite type, which is only castable to and from text.
+    UserDefined {
+        name: String,
+    },
+    /// Type of an expression that failed to analyse. Its error is already reported, so it's
+    /// accepted anywhere rather than reporting the same problem again.
+    Error,
+}
+
+/// When a cast between two types is applied automatically.
+///
+/// See: https://www.postgresql.org/docs/current/sql-createcast.html
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum CastContext {
+    /// Anywhere, e.g. `integer` to `bigint` in `a + b`.
+    Implicit,
+    /// When assigning to a column in `INSERT` / `UPDATE`, or explicitly.
+    Assignment,
+    /// Only with `CAST(..)` or `::`
+    Explicit,
 }
 
 impl DataType {
@@ -72,12 +150,226 @@ impl DataType {
     pub fn is_unknown(&self) -> bool {
         matches!(self, Self::Unknown)
     }
+    pub fn is_error(&self) -> bool {
+        matches!(self, Self::Error)
+    }
     pub fn is_bool(&self) -> bool {
         matches!(self, Self::Boolean | Self::Unknown)
     }
     pub fn is_text(&self) -> bool {
-        matches!(self, Self::Text | Self::Unknown | Self::CharacterVarying)
+        matches!(
+            self,
+            Self::Text | Self::Unknown | Self::CharacterVarying | Self::Character
+        )
     }
+    pub fn is_datetime(&self) -> bool {
+        matches!(
+            self,
+            Self::Date
+                | Self::Time
+                | Self::Timestamp
+                | Self::TimestampWithTimeZone
+                | Self::Interval
+        )
+    }
+    pub fn is_integer(&self) -> bool {
+        matches!(
+            self,
+            Self::Unknown | Self::TINYINT | Self::SmallInt | Self::Integer | Self::BigInt
+        )
+    }
+
+    /// The preferred type of its category, picked when a function call could resolve to more
+    /// than one overload.
+    pub fn is_preferred(&self) -> bool {
+        matches!(
+            self,
+            Self::Text | Self::DoublePrecision | Self::TimestampWithTimeZone | Self::Boolean
+        )
+    }
+
+    /// Postgres `typcategory` of the type, e.g. `N` for numbers and `S` for strings.
+    ///
+    /// Ref: https://www.postgresql.org/docs/current/catalog-pg-type.html#CATALOG-TYPCATEGORY-TABLE
+    pub fn category(&self) -> char {
+        match self {
+            Self::CharacterVarying | Self::Character | Self::Text => 'S',
+            Self::Boolean => 'B',
+            Self::TINYINT
+            | Self::SmallInt
+            | Self::Integer
+            | Self::BigInt
+            | Self::Real
+            | Self::DoublePrecision
+            | Self::Numeric => 'N',
+            Self::Date | Self::Time | Self::Timestamp | Self::TimestampWithTimeZone => 'D',
+            Self::Interval => 'T',
+            Self::Array { .. } => 'A',
+            Self::Uuid | Self::Json | Self::Jsonb | Self::Bytea | Self::UserDefined { .. } => 'U',
+            Self::Unknown | Self::Error => 'X',
+        }
+    }
+
+    /// Name of the type in Postgres, e.g. `double precision`.
+    pub fn sql_name(&self) -> String {
+        match self {
+            Self::Unknown => "unknown".into(),
+            Self::CharacterVarying => "character varying".into(),
+            Self::Character => "character".into(),
+            Self::Text => "text".into(),
+            Self::Boolean => "boolean".into(),
+            Self::TINYINT => "tinyint".into(),
+            Self::SmallInt => "smallint".into(),
+            Self::Integer => "integer".into(),
+            Self::BigInt => "bigint".into(),
+            Self::Real => "real".into(),
+            Self::DoublePrecision => "double precision".into(),
+            Self::Numeric => "numeric".into(),
+            Self::Date => "date".into(),
+            Self::Time => "time".into(),
+            Self::Timestamp => "timestamp".into(),
+            Self::TimestampWithTimeZone => "timestamptz".into(),
+            Self::Interval => "interval".into(),
+            Self::Uuid => "uuid".into(),
+            Self::Json => "json".into(),
+            Self::Jsonb => "jsonb".into(),
+            Self::Bytea => "bytea".into(),
+            Self::Array { ty } => format!("{}[]", ty.sql_name()),
+            Self::UserDefined { name } => name.clone(),
+            Self::Error => "error".into(),
+        }
+    }
+
+    /// Position of the type in the implicit cast chain of the numeric category,
+    /// `smallint -> integer -> bigint -> numeric -> real -> double precision`
+    fn numeric_rank(&self) -> Option<u8> {
+        Some(match self {
+            Self::TINYINT => 0,
+            Self::SmallInt => 1,
+            Self::Integer => 2,
+            Self::BigInt => 3,
+            Self::Numeric => 4,
+            Self::Real => 5,
+            Self::DoublePrecision => 6,
+            _ => return None,
+        })
+    }
+
+    /// Type for a name used by Postgres, either in `information_schema` or in a cast.
+    pub fn from_sql_name(name: &str) -> Option<DataType> {
+        Some(match name.to_lowercase().as_str() {
+            "tinyint" => Self::TINYINT,
+            "smallint" | "int2" => Self::SmallInt,
+            "integer" | "int" | "int4" => Self::Integer,
+            "bigint" | "int8" => Self::BigInt,
+            "numeric" | "decimal" => Self::Numeric,
+            "real" | "float4" => Self::Real,
+            "double precision" | "float8" | "float" => Self::DoublePrecision,
+            "boolean" | "bool" => Self::Boolean,
+            "character varying" | "char varying" | "varchar" => Self::CharacterVarying,
+            "character" | "char" | "bpchar" => Self::Character,
+            "text" => Self::Text,
+            "date" => Self::Date,
+            "time" | "time without time zone" => Self::Time,
+            "timestamp" | "timestamp without time zone" => Self::Timestamp,
+            "timestamptz" | "timestamp with time zone" => Self::TimestampWithTimeZone,
+            "interval" => Self::Interval,
+            "uuid" => Self::Uuid,
+            "json" => Self::Json,
+            "jsonb" => Self::Jsonb,
+            "bytea" => Self::Bytea,
+            _ => return None,
+        })
+    }
+
+    /// The context in which a value of this type can be converted to `target`,
+    /// `None` if Postgres has no cast between them at all.
+    ///
+    /// See: https://www.postgresql.org/docs/current/typeconv-overview.html
+    pub fn cast_context(&self, target: &DataType) -> Option<CastContext> {
+        use CastContext::*;
+        Some(match (self, target) {
+            (Self::Error, _) | (_, Self::Error) => Implicit,
+            // Untyped literals are converted by the input function of the target type.
+            (Self::Unknown, _) | (_, Self::Unknown) => Implicit,
+            (a, b) if a == b => Implicit,
+            (Self::Array { ty: a }, Self::Array { ty: b }) => a.cast_context(b)?,
+            (a, b) if a.is_text() && b.is_text() => match b {
+                Self::Character => Assignment,
+                _ => Implicit,
+            },
+            // Every type has an I/O conversion cast to and from the string types.
+            (_, b) if b.is_text() => Assignment,
+            (a, _) if a.is_text() => Explicit,
+            (Self::UserDefined { .. } | Self::Array { .. }, _)
+            | (_, Self::UserDefined { .. } | Self::Array { .. }) => return None,
+            (a, b) if a.numeric_rank().is_some() && b.numeric_rank().is_some() => {
+                match a.numeric_rank() < b.numeric_rank() {
+                    true => Implicit,
+                    false => Assignment,
+                }
+            }
+            (Self::Integer | Self::Boolean, Self::Boolean | Self::Integer) => Explicit,
+            (Self::Date, Self::Timestamp | Self::TimestampWithTimeZone)
+            | (Self::Timestamp, Self::TimestampWithTimeZone)
+            | (Self::Time, Self::Interval) => Implicit,
+            (Self::Timestamp | Self::TimestampWithTimeZone, Self::Date | Self::Time)
+            | (Self::TimestampWithTimeZone, Self::Timestamp)
+            | (Self::Interval, Self::Time)
+            | (Self::Json, Self::Jsonb)
+            | (Self::Jsonb, Self::Json) => Assignment,
+            (
+                Self::Jsonb,
+                Self::Boolean
+                | Self::SmallInt
+                | Self::Integer
+                | Self::BigInt
+                | Self::Numeric
+                | Self::Real
+                | Self::DoublePrecision,
+            ) => Explicit,
+            _ => return None,
+        })
+    }
+
+    /// Whether a value of this type can be stored in a column of `target` type,
+    /// Postgres allows implicit and assignment casts here.
+    ///
+    /// See: https://www.postgresql.org/docs/current/typeconv-query.html
+    pub fn is_assignable_to(&self, target: &DataType) -> bool {
+        self.cast_context(target)
+            .is_some_and(|context| context <= CastContext::Assignment)
+    }
+
+    /// Resolves the common type of two values, the same way Postgres does for
+    /// `UNION`, `CASE` and `VALUES`. Returns `None` if they can't be matched.
+    ///
+    /// See: https://www.postgresql.org/docs/current/typeconv-union-case.html
+    pub fn unify(&self, other: &DataType) -> Option<DataType> {
+        match (self, other) {
+            (Self::Error, _) | (_, Self::Error) => Some(Self::Error),
+            (Self::Unknown, ty) | (ty, Self::Unknown) => Some(ty.clone()),
+            (a, b) if a == b => Some(a.clone()),
+            (Self::Array { ty: a }, Self::Array { ty: b }) => {
+                a.unify(b).map(|ty| Self::Array { ty: Box::new(ty) })
+            }
+            (a, b) if a.is_text() && b.is_text() => Some(Self::Text),
+            // The type that the other one implicitly casts to, e.g. `bigint` for `integer` and `bigint`.
+            (a, b) => match (a.cast_context(b), b.cast_context(a)) {
+                (Some(CastContext::Implicit), _) => Some(b.clone()),
+                (_, Some(CastContext::Implicit)) => Some(a.clone()),
+                _ => None,
+            },
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IdentityGeneration {
+    /// `GENERATED ALWAYS AS IDENTITY`
+    Always,
+    /// `GENERATED BY DEFAULT AS IDENTITY`
+    ByDefault,
 }
 
 #[derive(Debug, Default)]
@@ -89,6 +381,16 @@ pub struct Column {
     pub udt: String,
     pub dtd_identifier: Option<String>,
     pub character_maximum_length: Option<i32>,
+    pub identity_generation: Option<IdentityGeneration>,
+    /// `GENERATED ALWAYS AS (<expr>) STORED`
+    pub is_generated: bool,
+}
+
+impl Column {
+    /// Whether the column gets a value when it is omitted or set to `DEFAULT`.
+    pub fn has_default(&self) -> bool {
+        self.default.is_some() || self.identity_generation.is_some() || self.is_generated
+    }
 }
 
 impl SchemaInfo {
@@ -105,7 +407,9 @@ impl SchemaInfo {
                         data_type,
                         udt_name,
                         dtd_identifier,
-                        character_maximum_length
+                        character_maximum_length,
+                        identity_generation,
+                        is_generated
                     FROM
                         information_schema.columns
                     WHERE
@@ -125,28 +429,10 @@ impl SchemaInfo {
             let data_type: String = row.get(6);
             let udt: String = row.get(7);
 
-            let data_type = if data_type.eq_ignore_ascii_case("TINYINT") {
-                DataType::TINYINT
-            } else if data_type.eq_ignore_ascii_case("smallint") {
-                DataType::SmallInt
-            } else if data_type.eq_ignore_ascii_case("integer") {
-                DataType::Integer
-            } else if data_type.eq_ignore_ascii_case("bigint") {
-                DataType::BigInt
-            } else if data_type.eq_ignore_ascii_case("numeric") {
-                DataType::Numeric
-            } else if data_type.eq_ignore_ascii_case("real") {
-                DataType::Real
-            } else if data_type.eq_ignore_ascii_case("double precision") {
-                DataType::DoublePrecision
-            } else if data_type.eq_ignore_ascii_case("boolean") {
-                DataType::Boolean
-            } else if data_type.eq_ignore_ascii_case("character varying") {
-                DataType::CharacterVarying
-            } else if data_type.eq_ignore_ascii_case("text") {
-                DataType::Text
-            } else {
-                return Err(format!("{{ unknown_type: {data_type},  udt: {udt}, column: {column_name}, table: {table_name}, schema: {table_schema} }}").into());
+            let data_type = match DataType::from_sql_name(&data_type) {
+                Some(data_type) => data_type,
+                None if data_type == "USER-DEFINED" => DataType::UserDefined { name: udt.clone() },
+                None => return Err(format!("{{ unknown_type: {data_type},  udt: {udt}, column: {column_name}, table: {table_name}, schema: {table_schema} }}").into()),
             };
 
             let tables = schemas.entry(table_schema).or_default();
@@ -160,8 +446,131 @@ impl SchemaInfo {
             column.udt = udt;
             column.dtd_identifier = row.get(8);
             column.character_maximum_length = row.get(9);
+            column.identity_generation = match row.get::<_, Option<String>>(10) {
+                Some(v) if v.eq_ignore_ascii_case("ALWAYS") => Some(IdentityGeneration::Always),
+                Some(v) if v.eq_ignore_ascii_case("BY DEFAULT") => {
+                    Some(IdentityGeneration::ByDefault)
+                }
+                _ => None,
+            };
+            column.is_generated = row
+                .get::<_, Option<String>>(11)
+                .is_some_and(|v| v.eq_ignore_ascii_case("ALWAYS"));
         }
+        let rows = client.query(
+            "SELECT
+                        n.nspname::text,
+                        c.relname::text,
+                        i.relname::text,
+                        con.conname::text,
+                        ix.indisprimary,
+                        ix.indpred IS NOT NULL,
+                        ix.indexprs IS NOT NULL,
+                        ARRAY(
+                            SELECT a.attname::text
+                            FROM unnest(ix.indkey) WITH ORDINALITY AS k(attnum, ord)
+                            JOIN pg_attribute a ON a.attrelid = c.oid AND a.attnum = k.attnum
+                            ORDER BY k.ord
+                        )
+                    FROM
+                        pg_index ix
+                        JOIN pg_class i ON i.oid = ix.indexrelid
+                        JOIN pg_class c ON c.oid = ix.indrelid
+                        JOIN pg_namespace n ON n.oid = c.relnamespace
+                        LEFT JOIN pg_constraint con ON con.conindid = ix.indexrelid
+                    WHERE
+                        ix.indisunique AND n.nspname = 'public';",
+            &[],
+        )?;
+
+        let mut unique_indexes: BTreeMap<String, UniqueIndexes> = Default::default();
+        for row in rows {
+            let indexes = unique_indexes.entry(row.get(0)).or_default();
+            indexes.entry(row.get(1)).or_default().push(UniqueIndex {
+                name: row.get(2),
+                constraint_name: row.get(3),
+                is_primary: row.get(4),
+                is_partial: row.get(5),
+                has_expressions: row.get(6),
+                columns: row.get(7),
+            });
+        }
+
+        // Ref: https://www.postgresql.org/docs/current/catalog-pg-proc.html
+        let rows = client.query(
+            "SELECT
+                        p.proname::text,
+                        p.prokind::text,
+                        p.provolatile::text,
+                        p.provariadic <> 0,
+                        p.pronargdefaults::integer,
+                        ARRAY(
+                            SELECT format_type(t.oid, NULL)
+                            FROM unnest(p.proargtypes::oid[]) WITH ORDINALITY AS t(oid, ord)
+                            ORDER BY t.ord
+                        ),
+                        format_type(p.prorettype, NULL),
+                        p.proretset
+                    FROM
+                        pg_proc p
+                        JOIN pg_namespace n ON n.oid = p.pronamespace
+                    WHERE
+                        n.nspname = 'public'
+                        AND p.prokind IN ('f', 'p')
+                        AND p.prorettype NOT IN ('trigger'::regtype, 'event_trigger'::regtype)
+                        AND NOT 'internal'::regtype = ANY(p.proargtypes::oid[]);",
+            &[],
+        )?;
+
+        let mut functions = Catalog::default();
+        for row in rows {
+            let name: String = row.get(0);
+            let variadic: bool = row.get(3);
+            let mut args: Vec<String> = row.get(5);
+            // The variadic parameter is an array of the type each of its arguments has.
+            if let Some(last) = args.last_mut().filter(|_| variadic) {
+                if let Some(element) = last.strip_suffix("[]") {
+                    *last = element.to_string();
+                }
+            }
+            let result: String = row.get(6);
+            // One overload the analyser can't type shouldn't keep the others from loading.
+            let (params, result) = match args
+                .iter()
+                .map(|arg| ParamType::parse(arg))
+                .collect::<Result<_, _>>()
+                .and_then(|params| Ok((params, ParamType::parse(&result)?)))
+            {
+                Ok(signature) => signature,
+                Err(err) => {
+                    log::warn!("skipping function `{name}`: {err}");
+                    continue;
+                }
+            };
+            functions.insert(Overload {
+                kind: match row.get::<_, String>(1).as_str() {
+                    "p" => FunctionKind::Procedure,
+                    _ => FunctionKind::Scalar,
+                },
+                volatility: match row.get::<_, String>(2).as_str() {
+                    "i" => Volatility::Immutable,
+                    "s" => Volatility::Stable,
+                    _ => Volatility::Volatile,
+                },
+                params,
+                variadic,
+                defaults: row.get::<_, i32>(4) as usize,
+                result,
+                returns_set: row.get(7),
+                name,
+            });
+        }
+
         // println!("{:#?}", schemas);
-        Ok(Self(schemas))
+        Ok(Self {
+            schema: schemas,
+            unique_indexes,
+            functions,
+        })
     }
 }
@@ -1,47 +1,225 @@
 use sql_parser::GetSpan;
 use std::{
+    collections::BTreeMap,
     fs::File,
     io::{self, Write},
 };
+use syn::LitStr;
 
 use crate::{
     schema_info::{Column, SchemaInfo, Table},
-    AnalyseError,
+    AnalyseError, DataType,
 };
 use log::{Level, Metadata, Record};
-use sql_parser::{grammar::Name, utils::suggest};
+use sql_parser::{
+    dialect::Dialect,
+    grammar::{ast::Expr, Column as ColumnRef, Name, Term, TypeName, Value},
+    utils::suggest,
+};
 
-pub fn get_table<'a>(info: &'a SchemaInfo, name: &Name) -> Result<&'a Table, AnalyseError> {
-    let (name, span) = (name.to_string(), name.span());
+pub fn get_table<'a>(
+    info: &'a SchemaInfo,
+    dialect: &dyn Dialect,
+    name: &Name,
+) -> Result<&'a Table, AnalyseError> {
+    let span = name.span();
 
     let tables = info
         .get_public_tables()
         .ok_or_else(|| (span, "no table found".to_string()))?;
 
-    tables.get(&name).ok_or_else(|| {
+    let value = name.value(dialect);
+    lookup(tables, dialect, &value)
+        .ok_or_else(|| (span, not_found("table", dialect, name, tables.keys())))
+}
+
+pub fn get_column<'a>(
+    table: &'a Table,
+    dialect: &dyn Dialect,
+    name: &Name,
+) -> Result<&'a Column, AnalyseError> {
+    let value = name.value(dialect);
+    lookup(table, dialect, &value).ok_or_else(|| {
         (
-            span,
-            format!(
-                "table does not exist: `{name}` \nsuggest: {}",
-                suggest(&name, tables.keys())
-            ),
+            name.span(),
+            not_found("column", dialect, name, table.keys()),
         )
     })
 }
 
-pub fn get_column<'a>(table: &'a Table, name: &Name) -> Result<&'a Column, AnalyseError> {
-    let (name, span) = (name.to_string(), name.span());
-    table.get(&name).ok_or_else(|| {
-        (
-            span,
-            format!(
-                "column does not exist: `{name}` \nsuggest: {}",
-                suggest(&name, table.keys())
-            ),
-        )
+/// The entry of `map` that `name` refers to in `dialect`, which may ignore its case.
+fn lookup<'a, T>(map: &'a BTreeMap<String, T>, dialect: &dyn Dialect, name: &str) -> Option<&'a T> {
+    map.get(name).or_else(|| {
+        map.iter()
+            .find(|(stored, _)| dialect.matches_name(stored, name))
+            .map(|(_, value)| value)
     })
 }
 
+/// Explains why `name` isn't one of `names`, which may hold it under a different case.
+fn not_found<'a>(
+    kind: &str,
+    dialect: &dyn Dialect,
+    name: &Name,
+    names: impl Iterator<Item = &'a String> + Clone,
+) -> String {
+    let value = name.value(dialect);
+    let Some(found) = names.clone().find(|n| n.eq_ignore_ascii_case(&value)) else {
+        return format!(
+            "{kind} does not exist: `{value}` \nsuggest: {}",
+            suggest(&value, names)
+        );
+    };
+    let reason = match name.is_quoted() {
+        true => "quoted names are case sensitive".to_string(),
+        false => format!(
+            "unquoted names are folded to `{value}` by {}",
+            dialect.name()
+        ),
+    };
+    format!(
+        "{kind} does not exist: `{value}`, {reason} \nsuggest: {}",
+        written_name(dialect, found)
+    )
+}
+
+/// How `name` is written to refer to it, quoted if folding or a keyword would get in the way.
+pub fn written_name(dialect: &dyn Dialect, name: &str) -> String {
+    let is_plain = name
+        .chars()
+        .all(|ch| ch == '_' || ch.is_ascii_alphanumeric())
+        && !name.starts_with(|ch: char| ch.is_ascii_digit());
+    match is_plain && dialect.fold_identifier(name) == name && !dialect.is_reserved(name) {
+        true => name.to_string(),
+        false => dialect.quote_identifier(name),
+    }
+}
+
+/// Tables visible to an expression, by the name they are referred to within the query.
+pub type Scope<'a> = [(String, &'a Table)];
+
+pub fn resolve_column<'a>(
+    scope: &Scope<'a>,
+    dialect: &dyn Dialect,
+    column: &ColumnRef<Name>,
+) -> Result<&'a Column, AnalyseError> {
+    if let Some(table_name) = &column.table_name {
+        let name = table_name.value(dialect);
+        return match scope
+            .iter()
+            .find(|(visible_name, _)| dialect.matches_name(visible_name, &name))
+        {
+            Some((_, table)) => get_column(table, dialect, &column.alias),
+            None => Err((
+                table_name.span(),
+                format!("missing FROM-clause entry for table `{name}`"),
+            )),
+        };
+    }
+    let name = column.alias.value(dialect);
+    let mut found = scope
+        .iter()
+        .filter_map(|(_, table)| lookup(table, dialect, &name));
+    match (found.next(), found.next()) {
+        (Some(column), None) => Ok(column),
+        (Some(_), Some(_)) => Err((
+            column.alias.span(),
+            format!("column reference `{name}` is ambiguous"),
+        )),
+        (None, _) => {
+            let names = scope.iter().flat_map(|(_, table)| table.keys());
+            Err((
+                column.alias.span(),
+                not_found("column", dialect, &column.alias, names),
+            ))
+        }
+    }
+}
+
+/// Resolves a type name of a cast, checking its modifiers, e.g. `varchar(0)` is an error.
+pub fn resolve_type(
+    info: &SchemaInfo,
+    dialect: &dyn Dialect,
+    type_name: &TypeName,
+) -> Result<DataType, AnalyseError> {
+    let name = type_name.name();
+    let builtin = match &type_name.schema_name {
+        None => DataType::from_sql_name(dialect.type_alias(&name).unwrap_or(&name)),
+        Some(schema) if schema.to_string().eq_ignore_ascii_case("pg_catalog") => {
+            DataType::from_sql_name(&name)
+        }
+        Some(_) => None,
+    };
+    let data_type = match builtin {
+        Some(data_type) => data_type,
+        None if type_name.schema_name.is_some() || info.has_user_type(&name) => {
+            DataType::UserDefined { name: name.clone() }
+        }
+        None => return Err((type_name.span(), format!("type `{name}` does not exist"))),
+    };
+
+    let max_modifiers = match data_type {
+        DataType::Numeric => 2,
+        DataType::CharacterVarying
+        | DataType::Character
+        | DataType::Time
+        | DataType::Timestamp
+        | DataType::TimestampWithTimeZone
+        | DataType::Interval => 1,
+        _ => 0,
+    };
+    if type_name.modifiers.len() > max_modifiers {
+        return Err((
+            type_name.span(),
+            format!("invalid type modifier for type `{name}`"),
+        ));
+    }
+    if let Some(first) = type_name.modifiers.first() {
+        let value = first
+            .base10_parse::<i32>()
+            .map_err(|err| (first.span(), err.to_string()))?;
+        let range = match data_type {
+            DataType::Numeric => 1..=1000,
+            DataType::CharacterVarying | DataType::Character => 1..=10485760,
+            _ => 0..=6,
+        };
+        if !range.contains(&value) {
+            return Err((
+                first.span(),
+                format!(
+                    "type modifier of `{name}` must be between {} and {}",
+                    range.start(),
+                    range.end()
+                ),
+            ));
+        }
+    }
+
+    let mut data_type = data_type;
+    for _ in 0..type_name.array_dims {
+        data_type = DataType::Array {
+            ty: Box::new(data_type),
+        };
+    }
+    Ok(data_type)
+}
+
+/// Returns the `Term` if the expression is nothing more than a single term.
+pub fn as_term(expr: &Expr) -> Option<&Term> {
+    match expr {
+        Expr::Term(term) => Some(term),
+        _ => None,
+    }
+}
+
+/// Returns the value of a string literal.
+pub fn as_str_literal(expr: &Expr) -> Option<&LitStr> {
+    match as_term(expr)? {
+        Term::Value(Value::String(lit)) => Some(lit),
+        _ => None,
+    }
+}
+
 pub struct Logger<W> {
     output: W,
 }
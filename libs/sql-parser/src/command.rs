@@ -1,26 +1,64 @@
 use crate::{utils::levenshtein_distance, *};
 
+pub mod call;
 pub mod delete;
 pub mod insert;
 pub mod select;
 pub mod update;
 
+use call::Call;
 use delete::Delete;
 use insert::Insert;
-use select::Select;
+use select::Query;
 use update::Update;
 
-const SUGGEST_CMD_KW: [&str; 4] = ["SELECT", "INSERT", "DELETE", "UPDATE"];
+const SUGGEST_CMD_KW: [&str; 5] = ["SELECT", "INSERT", "DELETE", "UPDATE", "CALL"];
 
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Command {
-    Select(Select),
-    Insert(Insert),
+    Select(Query),
+    Insert(Box<Insert>),
     Delete(Delete),
     Update(Update),
+    Call(Call),
 }
 
 impl Parse for Command {
+    /// All syntax errors are reported at once, see [`Command::parse_partial`].
     fn parse(input: ParseStream) -> Result<Self> {
+        let (command, errs) = Self::parse_partial(input)?;
+        match utils::combine_errors(errs) {
+            Some(err) => Err(err),
+            None => Ok(command),
+        }
+    }
+}
+
+impl Command {
+    /// Parses on past syntax errors at list and clause boundaries, what failed to parse is left
+    /// out of the command and its error returned alongside. Errors that leave no command to speak
+    /// of, such as an unknown command keyword, are returned together with those recovered from.
+    pub fn parse_partial(input: ParseStream) -> Result<(Self, Vec<Error>)> {
+        // `sql!("SELECT ..")`, the query is lexed as SQL rather than Rust tokens.
+        if input.peek(LitStr) {
+            let lit = input.parse::<LitStr>()?;
+            return lexer::parse_literal(&lit.token(), Self::parse_partial);
+        }
+        let (command, errs) = utils::collect_errors(|| {
+            let command = Self::parse_command(input)?;
+            if !input.is_empty() {
+                let unexpected = |input: ParseStream| Err::<(), _>(input.error("unexpected token"));
+                utils::recover(input, unexpected, |_| false)?;
+            }
+            Ok(command)
+        });
+        match command {
+            Ok(command) => Ok((command, errs)),
+            Err(err) => Err(utils::combine_errors(errs.into_iter().chain([err])).unwrap()),
+        }
+    }
+
+    fn parse_command(input: ParseStream) -> Result<Self> {
         let err_msg = format!("expected keyword: {}", SUGGEST_CMD_KW.join(" | "));
         let (keyword, _) = input.cursor().ident().ok_or(input.error(&err_msg))?;
         Ok(match keyword.to_string().to_uppercase().as_str() {
@@ -28,6 +66,7 @@ impl Parse for Command {
             "INSERT" => Self::Insert(input.parse()?),
             "DELETE" => Self::Delete(input.parse()?),
             "UPDATE" => Self::Update(input.parse()?),
+            "CALL" => Self::Call(input.parse()?),
             kw => {
                 let mut kws =
                     SUGGEST_CMD_KW.map(|expected| (levenshtein_distance(kw, expected), expected));
@@ -45,3 +84,15 @@ impl Parse for Command {
         })
     }
 }
+
+impl fmt::Debug for Command {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Select(query) => query.fmt(f),
+            Self::Insert(insert) => insert.fmt(f),
+            Self::Delete(delete) => delete.fmt(f),
+            Self::Update(update) => update.fmt(f),
+            Self::Call(call) => call.fmt(f),
+        }
+    }
+}
@@ -0,0 +1,50 @@
+use crate::*;
+use function::Function;
+use utils::parse_kw_if_matched;
+
+/// Ref: https://www.postgresql.org/docs/current/sql-call.html
+///
+/// ```text
+/// CALL <procedure_name> ( [<expr>, ..] )
+/// ```
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Call {
+    #[cfg_attr(feature = "serde", serde(with = "crate::tokens"))]
+    pub kw: Ident,
+    pub procedure: Function,
+}
+
+impl Parse for Call {
+    fn parse(input: ParseStream) -> Result<Self> {
+        Ok(Self {
+            kw: parse_kw_if_matched(input, "CALL")?,
+            procedure: input.parse()?,
+        })
+    }
+}
+
+impl fmt::Debug for Call {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Call")
+            .field("procedure", &self.procedure.name())
+            .field("args", &self.procedure.args())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn call() {
+        let call: Call = utils::test::syntex! { CALL transfer(1, 2, 100.5) }.unwrap();
+        assert_eq!(call.procedure.name(), "transfer");
+        assert_eq!(call.procedure.args().len(), 3);
+
+        let call: Call = utils::test::syntex! { CALL refresh() }.unwrap();
+        assert!(call.procedure.args().is_empty());
+
+        assert!(syn::parse_str::<Call>("CALL refresh").is_err());
+    }
+}
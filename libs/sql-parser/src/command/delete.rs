@@ -1,33 +1,97 @@
-use crate::{utils::parse_kw_if_matched, *};
-use grammar::ast::OrExpr;
-use grammar::TableName;
+use crate::{
+    utils::{parse_kw_if_matched, recover_clause},
+    *,
+};
+use command::select::Returning;
+use grammar::ast::Expr;
+use grammar::{Name, TableName, TableRef};
+use utils::SeparatedByComma;
 
+/// Ref: https://www.postgresql.org/docs/current/sql-delete.html
+///
+/// ```text
+/// DELETE FROM <table_name> [[AS] alias]
+///     [USING <table_ref>, ..]
+///     [WHERE { <expr> | CURRENT OF <cursor_name> }]
+///     [RETURNING <select_expr>, ..]
+/// ```
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Delete {
     /// DELETE FROM
+    #[cfg_attr(feature = "serde", serde(with = "crate::tokens"))]
     pub kw: (Ident, Ident),
     pub table_name: TableName,
-    pub where_expr: Option<OrExpr>,
+    pub alias: Option<Name>,
+    #[cfg_attr(feature = "serde", serde(with = "crate::tokens"))]
+    pub using: Option<(Ident, SeparatedByComma<TableRef>)>,
+    pub where_clause: Option<WhereClause>,
+    pub returning: Option<Returning>,
+}
+
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum WhereClause {
+    Expr(Expr),
+    /// `WHERE CURRENT OF <cursor_name>`, deletes the row most recently fetched from the cursor.
+    CurrentOf {
+        #[cfg_attr(feature = "serde", serde(with = "crate::tokens"))]
+        kws: (Ident, Ident),
+        cursor_name: Name,
+    },
+}
+
+impl Delete {
+    /// The name used to refer to the target table within the query.
+    pub fn visible_name(&self) -> &Name {
+        self.alias.as_ref().unwrap_or(&self.table_name.alias)
+    }
 }
 
 impl Parse for Delete {
     fn parse(input: ParseStream) -> Result<Self> {
+        let kw = (
+            parse_kw_if_matched(input, "DELETE")?,
+            parse_kw_if_matched(input, "FROM")?,
+        );
+        let TableRef { table_name, alias } = input.parse()?;
+        utils::skip_to_clause(input)?;
         Ok(Delete {
-            kw: (
-                parse_kw_if_matched(input, "DELETE")?,
-                parse_kw_if_matched(input, "FROM")?,
-            ),
-            table_name: input.parse()?,
-            where_expr: {
+            kw,
+            table_name,
+            alias,
+            using: match parse_kw_if_matched(input, "USING") {
+                Ok(kw) => recover_clause(input, |input| Ok((kw, input.parse()?)))?,
+                Err(_) => None,
+            },
+            where_clause: {
                 match parse_kw_if_matched(input, "WHERE") {
-                    Ok(_) => Some(input.parse()?),
+                    Ok(_) => recover_clause(input, WhereClause::parse)?,
                     Err(_) => None,
                 }
             },
+            returning: Returning::parse_optional(input)?,
         })
     }
 }
 
+impl Parse for WhereClause {
+    fn parse(input: ParseStream) -> Result<Self> {
+        if utils::peek_kw(input, "CURRENT") && input.peek2(Ident::peek_any) {
+            let fork = input.fork();
+            let current_kw = parse_kw_if_matched(&fork, "CURRENT")?;
+            if let Ok(of_kw) = parse_kw_if_matched(&fork, "OF") {
+                input.advance_to(&fork);
+                return Ok(Self::CurrentOf {
+                    kws: (current_kw, of_kw),
+                    cursor_name: input.parse()?,
+                });
+            }
+        }
+        input.parse().map(Self::Expr)
+    }
+}
+
 #[cfg(test)]
 pub(crate) mod tests {
     use super::*;
@@ -39,7 +103,30 @@ pub(crate) mod tests {
             DELETE FROM test
             WHERE
                 id = 2 AND name = 353
+            RETURNING * EXCEPT (name)
         };
         println!("{:#?}", g);
     }
+
+    #[test]
+    fn using_and_current_of() {
+        let g: Delete = utils::test::syntex! {
+            DELETE FROM posts p USING users u, comments
+            WHERE p.user_id = u.id
+            RETURNING p.*
+        }
+        .unwrap();
+        assert_eq!(g.visible_name().to_string(), "p");
+        assert!(g.using.is_some_and(|(_, tables)| tables.len() == 2));
+        assert!(matches!(g.where_clause, Some(WhereClause::Expr(_))));
+
+        let g: Delete = utils::test::syntex! {
+            DELETE FROM posts WHERE CURRENT OF posts_cursor
+        }
+        .unwrap();
+        assert!(matches!(
+            g.where_clause,
+            Some(WhereClause::CurrentOf { .. })
+        ));
+    }
 }
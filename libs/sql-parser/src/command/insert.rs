@@ -1,41 +1,222 @@
-use crate::{utils::parse_kw_if_matched, *};
-use grammar::{ast::OrExpr, Name, TableName};
+use crate::{
+    utils::{parse_keywords_if_matched, parse_kw_if_matched, peek_kw},
+    *,
+};
+use command::{
+    select::{Query, Returning},
+    update::Assignment,
+};
+use grammar::{ast::Expr, Name, TableName};
+use utils::SeparatedByComma;
 
 /// Ref: https://www.h2database.com/html/commands.html#insert
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Insert {
+    #[cfg_attr(feature = "serde", serde(with = "crate::tokens"))]
     pub kw: (Ident, Ident),
     pub table_name: TableName,
+    #[cfg_attr(feature = "serde", serde(with = "crate::tokens"))]
     pub column_name: Punctuated<Name, Token![,]>,
+    pub overriding: Option<Overriding>,
     pub values: InsertKind,
+    pub on_conflict: Option<OnConflict>,
+    pub returning: Option<Returning>,
 }
 
+/// ```text
+/// ON CONFLICT [ ( <column>, .. ) [WHERE <expr>] | ON CONSTRAINT <name> ]
+///     DO { NOTHING | UPDATE SET <assignment>, .. [WHERE <expr>] }
+/// ```
+///
+/// Ref: https://www.postgresql.org/docs/current/sql-insert.html#SQL-ON-CONFLICT
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OnConflict {
+    #[cfg_attr(feature = "serde", serde(with = "crate::tokens"))]
+    pub kws: (Ident, Ident),
+    pub target: Option<ConflictTarget>,
+    #[cfg_attr(feature = "serde", serde(with = "crate::tokens"))]
+    pub do_kw: Ident,
+    pub action: ConflictAction,
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ConflictTarget {
+    /// `( <column>, .. ) [WHERE <expr>]`, infers a unique index.
+    Columns {
+        #[cfg_attr(feature = "serde", serde(with = "crate::tokens"))]
+        paren_token: Paren,
+        #[cfg_attr(feature = "serde", serde(with = "crate::tokens"))]
+        columns: Punctuated<Name, Token![,]>,
+        where_expr: Option<Expr>,
+    },
+    /// `ON CONSTRAINT <name>`
+    Constraint {
+        #[cfg_attr(feature = "serde", serde(with = "crate::tokens"))]
+        kws: (Ident, Ident),
+        name: Name,
+    },
+}
+
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ConflictAction {
+    Nothing(#[cfg_attr(feature = "serde", serde(with = "crate::tokens"))] Ident),
+    /// `UPDATE SET ..`, the row proposed for insertion is available as the `EXCLUDED` table.
+    Update {
+        #[cfg_attr(feature = "serde", serde(with = "crate::tokens"))]
+        kws: (Ident, Ident),
+        assignments: SeparatedByComma<Assignment>,
+        where_expr: Option<Expr>,
+    },
+}
+
+/// `OVERRIDING { USER | SYSTEM } VALUE`
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Overriding {
+    #[cfg_attr(feature = "serde", serde(with = "crate::tokens"))]
+    pub kws: (Ident, Ident, Ident),
+    pub kind: OverridingKind,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum OverridingKind {
+    /// Values supplied for identity columns are ignored.
+    User,
+    /// Values supplied for identity columns override the sequence values.
+    System,
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Row {
     InsertExpr(InsertExpr),
-    Row(Punctuated<InsertExpr, Token![,]>),
+    Row(
+        #[cfg_attr(feature = "serde", serde(with = "crate::tokens"))] Paren,
+        #[cfg_attr(feature = "serde", serde(with = "crate::tokens"))]
+        Punctuated<InsertExpr, Token![,]>,
+    ),
 }
 
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum InsertExpr {
-    Default,
-    Insert(OrExpr),
+    Default(#[cfg_attr(feature = "serde", serde(with = "crate::tokens"))] Ident),
+    Insert(Expr),
 }
 
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum InsertKind {
-    Values { kw: Ident, rows: Vec<Row> },
-    // OverridingUserValue(OverrideClause),
-    // OverridingSystemValue(OverrideClause),
-    DefaultValues(Ident),
+    Values {
+        #[cfg_attr(feature = "serde", serde(with = "crate::tokens"))]
+        kw: Ident,
+        rows: Vec<Row>,
+    },
+    /// `INSERT INTO <table> SELECT ..`
+    Query(Box<Query>),
+    DefaultValues(#[cfg_attr(feature = "serde", serde(with = "crate::tokens"))] Ident),
+}
+
+impl Parse for OnConflict {
+    fn parse(input: ParseStream) -> Result<Self> {
+        Ok(Self {
+            kws: (
+                parse_kw_if_matched(input, "ON")?,
+                parse_kw_if_matched(input, "CONFLICT")?,
+            ),
+            target: {
+                if input.peek(Paren) {
+                    let content;
+                    Some(ConflictTarget::Columns {
+                        paren_token: parenthesized!(content in input),
+                        columns: content.call(Punctuated::parse_terminated)?,
+                        where_expr: match parse_kw_if_matched(input, "WHERE") {
+                            Ok(_) => Some(input.parse()?),
+                            Err(_) => None,
+                        },
+                    })
+                } else if peek_kw(input, "ON") {
+                    Some(ConflictTarget::Constraint {
+                        kws: (
+                            parse_kw_if_matched(input, "ON")?,
+                            parse_kw_if_matched(input, "CONSTRAINT")?,
+                        ),
+                        name: input.parse()?,
+                    })
+                } else {
+                    None
+                }
+            },
+            do_kw: parse_kw_if_matched(input, "DO")?,
+            action: match parse_kw_if_matched(input, "NOTHING") {
+                Ok(kw) => ConflictAction::Nothing(kw),
+                Err(_) => ConflictAction::Update {
+                    kws: (
+                        parse_kw_if_matched(input, "UPDATE")
+                            .map_err(|_| input.error("expected: NOTHING | UPDATE SET .."))?,
+                        parse_kw_if_matched(input, "SET")?,
+                    ),
+                    assignments: input.parse()?,
+                    where_expr: match parse_kw_if_matched(input, "WHERE") {
+                        Ok(_) => Some(input.parse()?),
+                        Err(_) => None,
+                    },
+                },
+            },
+        })
+    }
+}
+
+impl GetSpan for ConflictTarget {
+    fn span(&self) -> Span {
+        match self {
+            ConflictTarget::Columns { paren_token, .. } => paren_token.span.join(),
+            ConflictTarget::Constraint { name, .. } => name.span(),
+        }
+    }
 }
 
-// enum OverrideClause {
-//     InsertExpr(InsertExpr),
-//     Query(Query),
-// }
+impl fmt::Debug for ConflictTarget {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Columns {
+                columns,
+                where_expr,
+                ..
+            } => f
+                .debug_struct("Columns")
+                .field("columns", &columns.iter().collect::<Vec<_>>())
+                .field("where_expr", where_expr)
+                .finish(),
+            Self::Constraint { name, .. } => f.debug_tuple("Constraint").field(name).finish(),
+        }
+    }
+}
+
+impl Parse for Overriding {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let overriding_kw = parse_kw_if_matched(input, "OVERRIDING")?;
+        let kind_kw = parse_keywords_if_matched(input, &["USER", "SYSTEM"])?;
+        let kind = match kind_kw.to_string().eq_ignore_ascii_case("USER") {
+            true => OverridingKind::User,
+            false => OverridingKind::System,
+        };
+        Ok(Self {
+            kws: (overriding_kw, kind_kw, parse_kw_if_matched(input, "VALUE")?),
+            kind,
+        })
+    }
+}
 
 impl Parse for InsertKind {
     fn parse(input: ParseStream) -> Result<Self> {
-        let err_msg = input.error("expected: VALUES(<expr>, ..) | VALUES <expr> | DEFAULT VALUES");
+        if peek_kw(input, "SELECT") {
+            return input.parse().map(Self::Query);
+        }
+        let err_msg = input
+            .error("expected: VALUES(<expr>, ..) | VALUES <expr> | DEFAULT VALUES | SELECT ..");
         let kw: Ident = input.parse().map_err(|_| err_msg.clone())?;
         let kind = kw.to_string();
         if kind.eq_ignore_ascii_case("VALUES") {
@@ -65,7 +246,7 @@ impl Parse for InsertKind {
 impl Parse for InsertExpr {
     fn parse(input: ParseStream) -> Result<Self> {
         match parse_kw_if_matched(input, "DEFAULT") {
-            Ok(_) => Ok(Self::Default),
+            Ok(kw) => Ok(Self::Default(kw)),
             Err(_) => Ok(Self::Insert(input.parse()?)),
         }
     }
@@ -77,8 +258,11 @@ impl Parse for Row {
             || input.cursor().group(Delimiter::Parenthesis).is_some()
         {
             let contain;
-            parenthesized!(contain in input);
-            return Ok(Self::Row(contain.call(Punctuated::parse_terminated)?));
+            let paren_token = parenthesized!(contain in input);
+            return Ok(Self::Row(
+                paren_token,
+                contain.call(Punctuated::parse_terminated)?,
+            ));
         }
         Ok(Self::InsertExpr(input.parse()?))
     }
@@ -102,16 +286,52 @@ impl Parse for Insert {
                     None => Punctuated::new(),
                 }
             },
+            overriding: match peek_kw(input, "OVERRIDING") {
+                true => Some(input.parse()?),
+                false => None,
+            },
             values: input.parse()?,
+            on_conflict: match peek_kw(input, "ON") {
+                true => Some(input.parse()?),
+                false => None,
+            },
+            returning: Returning::parse_optional(input)?,
         })
     }
 }
 
+impl Row {
+    pub fn values(&self) -> Vec<&InsertExpr> {
+        match self {
+            Row::InsertExpr(expr) => vec![expr],
+            Row::Row(_, values) => values.iter().collect(),
+        }
+    }
+}
+
+impl GetSpan for Row {
+    fn span(&self) -> Span {
+        match self {
+            Row::InsertExpr(expr) => expr.span(),
+            Row::Row(paren_token, _) => paren_token.span.join(),
+        }
+    }
+}
+
+impl GetSpan for InsertExpr {
+    fn span(&self) -> Span {
+        match self {
+            InsertExpr::Default(kw) => kw.span(),
+            InsertExpr::Insert(expr) => expr.span(),
+        }
+    }
+}
+
 impl fmt::Debug for Row {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             Self::InsertExpr(arg0) => f.debug_tuple("InsertExpr").field(arg0).finish(),
-            Self::Row(arg0) => f
+            Self::Row(_, arg0) => f
                 .debug_tuple("Row")
                 .field(&arg0.iter().collect::<Vec<_>>())
                 .finish(),
@@ -125,7 +345,10 @@ impl fmt::Debug for Insert {
             .field("kw", &self.kw)
             .field("table_name", &self.table_name)
             .field("column_name", &self.column_name.iter().collect::<Vec<_>>())
+            .field("overriding", &self.overriding)
             .field("values", &self.values)
+            .field("on_conflict", &self.on_conflict)
+            .field("returning", &self.returning)
             .finish()
     }
 }
@@ -142,4 +365,57 @@ pub(crate) mod tests {
         };
         println!("{:#?}", g);
     }
+
+    #[test]
+    fn insert_query() {
+        let g: Insert = utils::test::syntex! {
+            INSERT INTO test (id, name) OVERRIDING SYSTEM VALUE
+            SELECT id, name FROM other WHERE id > 10
+        }
+        .unwrap();
+        assert!(g
+            .overriding
+            .is_some_and(|o| o.kind == OverridingKind::System));
+        assert!(matches!(g.values, InsertKind::Query(_)));
+    }
+
+    #[test]
+    fn on_conflict() {
+        let g: Insert = utils::test::syntex! {
+            INSERT INTO users (email, name) VALUES ("a@x", "a")
+            ON CONFLICT (email) DO UPDATE SET name = EXCLUDED.name WHERE users.name != EXCLUDED.name
+        }
+        .unwrap();
+        let on_conflict = g.on_conflict.unwrap();
+        assert!(matches!(
+            on_conflict.target,
+            Some(ConflictTarget::Columns { .. })
+        ));
+        assert!(matches!(
+            on_conflict.action,
+            ConflictAction::Update {
+                where_expr: Some(_),
+                ..
+            }
+        ));
+
+        let g: Insert = utils::test::syntex! {
+            INSERT INTO users SELECT * FROM other ON CONFLICT ON CONSTRAINT users_pkey DO NOTHING
+            RETURNING *, id AS user_id
+        }
+        .unwrap();
+        let on_conflict = g.on_conflict.unwrap();
+        assert!(matches!(
+            on_conflict.target,
+            Some(ConflictTarget::Constraint { .. })
+        ));
+        assert!(matches!(on_conflict.action, ConflictAction::Nothing(_)));
+        assert!(g.returning.is_some_and(|r| r.exprs.len() == 2));
+
+        let err = syn::parse_str::<Insert>("INSERT INTO t (a) VALUES (1) RETURNING").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "unexpected end of input, expected: * | <expr> [[AS] <name>], .."
+        );
+    }
 }
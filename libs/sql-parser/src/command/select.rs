@@ -1,59 +1,339 @@
 use self::grammar::Name;
 use self::utils::SeparatedByComma;
 use crate::*;
-use grammar::ast::OrExpr;
-use grammar::Column;
-use utils::parse_kw_if_matched;
+use grammar::ast::Expr;
+use grammar::{Column, TableRef};
+use syn::token;
+use utils::{parse_kw_if_matched, peek_kw, recover_clause};
+use window::{OrderByClause, WindowClause};
 
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum SelectExpr {
     WildCard {
         symbol: Column<Token![*]>,
+        #[cfg_attr(feature = "serde", serde(with = "crate::tokens"))]
         except: Punctuated<Column<Name>, Token![,]>,
     },
     Expr {
-        expr: OrExpr,
+        expr: Expr,
         alias: Option<Name>,
     },
 }
 
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum SelectFilter {
     All,
     Distinct {
+        #[cfg_attr(feature = "serde", serde(with = "crate::tokens"))]
         kw: Ident,
     },
     DistinctON {
+        #[cfg_attr(feature = "serde", serde(with = "crate::tokens"))]
         on_kw: Ident,
+        #[cfg_attr(feature = "serde", serde(with = "crate::tokens"))]
         paren_token: Paren,
-        exprs: Punctuated<OrExpr, Token![,]>,
+        #[cfg_attr(feature = "serde", serde(with = "crate::tokens"))]
+        exprs: Punctuated<Expr, Token![,]>,
     },
 }
 
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Select {
+    #[cfg_attr(feature = "serde", serde(with = "crate::tokens"))]
     pub select_kw: Ident,
     pub filter: SelectFilter,
     pub exprs: SeparatedByComma<SelectExpr>,
+    pub from: Option<FromClause>,
+    pub where_expr: Option<Expr>,
+    pub group_by: Option<GroupByClause>,
+    pub having: Option<Expr>,
+    pub window: Option<WindowClause>,
+}
+
+/// `GROUP BY <expr>, ..`
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GroupByClause {
+    #[cfg_attr(feature = "serde", serde(with = "crate::tokens"))]
+    pub group_by_kws: (Ident, Ident),
+    pub exprs: SeparatedByComma<Expr>,
+}
+
+/// `RETURNING <select_expr>, ..`
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Returning {
+    #[cfg_attr(feature = "serde", serde(with = "crate::tokens"))]
+    pub kw: Ident,
+    pub exprs: SeparatedByComma<SelectExpr>,
+}
+
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FromClause {
+    #[cfg_attr(feature = "serde", serde(with = "crate::tokens"))]
     pub from_kw: Ident,
+    pub table: TableRef,
+}
+
+/// A complete query: one or more `SELECT`s combined with set operators,
+/// followed by `ORDER BY` / `LIMIT` / `OFFSET` that apply to the whole result.
+///
+/// ```text
+/// <set_expr> [ORDER BY <sort_spec>, ..] [LIMIT { <expr> | ALL }] [OFFSET <expr> [ROW | ROWS]]
+/// ```
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Query {
+    pub body: SetExpr,
+    pub order_by: Option<OrderByClause>,
+    pub limit: Option<Limit>,
+    pub offset: Option<Offset>,
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SetExpr {
+    Select(Box<Select>),
+    /// `( <query> )`
+    Query {
+        #[cfg_attr(feature = "serde", serde(with = "crate::tokens"))]
+        paren_token: Paren,
+        query: Box<Query>,
+    },
+    SetOperation {
+        left: Box<SetExpr>,
+        op: SetOperator,
+        right: Box<SetExpr>,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SetOperatorKind {
+    Union,
+    Intersect,
+    Except,
+}
+
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SetOperator {
+    pub kind: SetOperatorKind,
+    #[cfg_attr(feature = "serde", serde(with = "crate::tokens"))]
+    pub kw: Ident,
+    /// `ALL` | `DISTINCT`
+    #[cfg_attr(feature = "serde", serde(with = "crate::tokens"))]
+    pub quantifier: Option<Ident>,
+}
+
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Limit {
+    #[cfg_attr(feature = "serde", serde(with = "crate::tokens"))]
+    pub kw: Ident,
+    /// `None` for `LIMIT ALL`
+    pub count: Option<Expr>,
+}
+
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Offset {
+    #[cfg_attr(feature = "serde", serde(with = "crate::tokens"))]
+    pub kw: Ident,
+    pub count: Expr,
+    /// `ROW` | `ROWS`
+    #[cfg_attr(feature = "serde", serde(with = "crate::tokens"))]
+    pub rows_kw: Option<Ident>,
+}
+
+impl SetOperator {
+    pub fn is_all(&self) -> bool {
+        self.quantifier
+            .as_ref()
+            .is_some_and(|kw| kw.to_string().eq_ignore_ascii_case("ALL"))
+    }
+
+    /// `INTERSECT` binds more tightly than `UNION` and `EXCEPT`.
+    fn precedence(&self) -> u8 {
+        match self.kind {
+            SetOperatorKind::Union | SetOperatorKind::Except => 1,
+            SetOperatorKind::Intersect => 2,
+        }
+    }
+
+    fn peek(input: ParseStream) -> bool {
+        ["UNION", "INTERSECT", "EXCEPT"]
+            .iter()
+            .any(|kw| peek_kw(input, kw))
+    }
+}
+
+impl SetExpr {
+    /// The left-most `SELECT` of this expression, its projection names the result columns.
+    pub fn first_select(&self) -> &Select {
+        match self {
+            SetExpr::Select(select) => select,
+            SetExpr::Query { query, .. } => query.body.first_select(),
+            SetExpr::SetOperation { left, .. } => left.first_select(),
+        }
+    }
+
+    fn parse_operand(input: ParseStream) -> Result<Self> {
+        if input.peek(token::Paren) {
+            let content;
+            return Ok(Self::Query {
+                paren_token: parenthesized!(content in input),
+                query: content.parse()?,
+            });
+        }
+        input.parse().map(Self::Select)
+    }
+
+    /// Precedence climbing over set operators, all of them are left associative.
+    fn parse_with_precedence(input: ParseStream, min_precedence: u8) -> Result<Self> {
+        let mut left = Self::parse_operand(input)?;
+        loop {
+            let fork = input.fork();
+            let op = match SetOperator::peek(&fork) {
+                true => fork.parse::<SetOperator>()?,
+                false => break,
+            };
+            let precedence = op.precedence();
+            if precedence < min_precedence {
+                break;
+            }
+            input.advance_to(&fork);
+            let right = Self::parse_with_precedence(input, precedence + 1)?;
+            left = Self::SetOperation {
+                left: Box::new(left),
+                op,
+                right: Box::new(right),
+            };
+        }
+        Ok(left)
+    }
+}
+
+impl Parse for Query {
+    fn parse(input: ParseStream) -> Result<Self> {
+        Ok(Self {
+            body: input.parse()?,
+            order_by: recover_clause(input, OrderByClause::parse_optional)?.flatten(),
+            limit: match parse_kw_if_matched(input, "LIMIT") {
+                Ok(kw) => recover_clause(input, |input| {
+                    Ok(Limit {
+                        kw,
+                        count: match parse_kw_if_matched(input, "ALL") {
+                            Ok(_) => None,
+                            Err(_) => Some(input.parse()?),
+                        },
+                    })
+                })?,
+                Err(_) => None,
+            },
+            offset: match parse_kw_if_matched(input, "OFFSET") {
+                Ok(kw) => recover_clause(input, |input| {
+                    Ok(Offset {
+                        kw,
+                        count: input.parse()?,
+                        rows_kw: utils::parse_keywords_if_matched(input, &["ROW", "ROWS"]).ok(),
+                    })
+                })?,
+                Err(_) => None,
+            },
+        })
+    }
+}
+
+impl Parse for SetExpr {
+    fn parse(input: ParseStream) -> Result<Self> {
+        Self::parse_with_precedence(input, 0)
+    }
+}
+
+impl Parse for SetOperator {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let kw = utils::parse_keywords_if_matched(input, &["UNION", "INTERSECT", "EXCEPT"])?;
+        let kind = match kw.to_string().to_uppercase().as_str() {
+            "UNION" => SetOperatorKind::Union,
+            "INTERSECT" => SetOperatorKind::Intersect,
+            _ => SetOperatorKind::Except,
+        };
+        Ok(Self {
+            kind,
+            kw,
+            quantifier: utils::parse_keywords_if_matched(input, &["ALL", "DISTINCT"]).ok(),
+        })
+    }
 }
 
 impl Parse for Select {
     fn parse(input: ParseStream) -> Result<Self> {
+        let select_kw = parse_kw_if_matched(input, "SELECT")?;
+        let filter = input.parse()?;
+        let exprs = input.parse()?;
+        utils::skip_to_clause(input)?;
         Ok(Self {
-            select_kw: parse_kw_if_matched(input, "SELECT")?,
-            filter: input.parse()?,
-            exprs: input.parse()?,
-            from_kw: parse_kw_if_matched(input, "FROM")?,
+            select_kw,
+            filter,
+            exprs,
+            from: match parse_kw_if_matched(input, "FROM") {
+                Ok(from_kw) => recover_clause(input, |input| {
+                    Ok(FromClause {
+                        from_kw,
+                        table: input.parse()?,
+                    })
+                })?,
+                Err(_) => None,
+            },
+            where_expr: match parse_kw_if_matched(input, "WHERE") {
+                Ok(_) => recover_clause(input, Expr::parse)?,
+                Err(_) => None,
+            },
+            group_by: match parse_kw_if_matched(input, "GROUP") {
+                Ok(group_kw) => recover_clause(input, |input| {
+                    Ok(GroupByClause {
+                        group_by_kws: (group_kw, parse_kw_if_matched(input, "BY")?),
+                        exprs: input.parse()?,
+                    })
+                })?,
+                Err(_) => None,
+            },
+            having: match parse_kw_if_matched(input, "HAVING") {
+                Ok(_) => recover_clause(input, Expr::parse)?,
+                Err(_) => None,
+            },
+            window: recover_clause(input, WindowClause::parse_optional)?.flatten(),
         })
     }
 }
 
+impl Returning {
+    pub fn parse_optional(input: ParseStream) -> Result<Option<Self>> {
+        let Ok(kw) = parse_kw_if_matched(input, "RETURNING") else {
+            return Ok(None);
+        };
+        // A comma separated list may be empty, an output list may not.
+        if utils::peek_clause(input) {
+            return Err(input.error("expected: * | <expr> [[AS] <name>], .."));
+        }
+        Ok(Some(Self {
+            kw,
+            exprs: input.parse()?,
+        }))
+    }
+}
+
 impl Parse for SelectExpr {
     fn parse(input: ParseStream) -> Result<Self> {
         let fork = input.fork();
         if let Ok(symbol) = fork.parse() {
-            let except = match parse_kw_if_matched(&fork, "EXCEPT").ok() {
-                None => Punctuated::new(),
-                Some(_) => {
+            // `* EXCEPT (..)` excludes columns, while `EXCEPT SELECT ..` is a set operation.
+            let except = match peek_kw(&fork, "EXCEPT") && fork.peek2(token::Paren) {
+                false => Punctuated::new(),
+                true => {
+                    parse_kw_if_matched(&fork, "EXCEPT")?;
                     let content;
                     parenthesized!(content in fork);
                     content.call(Punctuated::parse_terminated)?
@@ -95,6 +375,24 @@ impl Parse for SelectFilter {
     }
 }
 
+impl GetSpan for SelectExpr {
+    fn span(&self) -> Span {
+        match self {
+            SelectExpr::WildCard { symbol, .. } => symbol.alias.spans[0],
+            SelectExpr::Expr { expr, alias } => match alias {
+                Some(alias) => alias.span(),
+                None => expr.span(),
+            },
+        }
+    }
+}
+
+impl GetSpan for SetOperator {
+    fn span(&self) -> Span {
+        self.kw.span()
+    }
+}
+
 impl fmt::Debug for SelectFilter {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
@@ -124,6 +422,21 @@ impl fmt::Debug for SelectExpr {
     }
 }
 
+impl fmt::Debug for SetExpr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Select(select) => select.fmt(f),
+            Self::Query { query, .. } => query.fmt(f),
+            Self::SetOperation { left, op, right } => f
+                .debug_struct("SetOperation")
+                .field("left", left)
+                .field("op", op)
+                .field("right", right)
+                .finish(),
+        }
+    }
+}
+
 #[cfg(test)]
 pub(crate) mod tests {
     use super::*;
@@ -132,8 +445,85 @@ pub(crate) mod tests {
     fn test_name() {
         // EXCEPT
         let g: Result<Select> = utils::test::syntex! {
-            SELECT adad as awd, adad FROM
+            SELECT adad as awd, adad FROM test
         };
         println!("{:#?}", g.unwrap());
     }
+
+    #[test]
+    fn window_clause() {
+        let g: Select = utils::test::syntex! {
+            SELECT ROW_NUMBER() OVER w, LAG(price, 1, 0) OVER (w ROWS 1 PRECEDING)
+            FROM items
+            WINDOW w AS (PARTITION BY category ORDER BY price), w2 AS (w)
+        }
+        .unwrap();
+        assert_eq!(g.window.unwrap().definitions.len(), 2);
+    }
+
+    #[test]
+    fn aggregates() {
+        let g: Select = utils::test::syntex! {
+            SELECT category, COUNT(*), COUNT(DISTINCT owner), STRING_AGG(name, ", " ORDER BY name DESC),
+                SUM(price) FILTER (WHERE price > 0), AVG(price) OVER ()
+            FROM items
+            GROUP BY category
+            HAVING MAX(price) > 10
+        }
+        .unwrap();
+        assert_eq!(g.group_by.unwrap().exprs.len(), 1);
+        assert!(g.having.is_some());
+        assert_eq!(g.exprs.len(), 6);
+    }
+
+    #[test]
+    fn set_operations() {
+        let q: Query = utils::test::syntex! {
+            SELECT id FROM a
+            UNION ALL
+            SELECT id FROM b
+            INTERSECT
+            SELECT id FROM c
+            EXCEPT (SELECT * EXCEPT (name) FROM d)
+            ORDER BY id DESC
+            LIMIT 10 OFFSET 5
+        }
+        .unwrap();
+
+        // ((a UNION ALL (b INTERSECT c)) EXCEPT (d))
+        let SetExpr::SetOperation { left, op, right } = &q.body else {
+            panic!("expected set operation");
+        };
+        assert_eq!(op.kind, SetOperatorKind::Except);
+        assert!(matches!(**right, SetExpr::Query { .. }));
+
+        let SetExpr::SetOperation { op, right, .. } = &**left else {
+            panic!("expected set operation");
+        };
+        assert_eq!(op.kind, SetOperatorKind::Union);
+        assert!(op.is_all());
+        assert!(matches!(
+            &**right,
+            SetExpr::SetOperation { op, .. } if op.kind == SetOperatorKind::Intersect
+        ));
+
+        assert!(q.order_by.is_some());
+        assert!(q.limit.is_some_and(|l| l.count.is_some()));
+        assert!(q.offset.is_some());
+    }
+
+    #[test]
+    fn partial() {
+        let sql = "SELECT id, name +, age FROM users WHERE = 1 ORDER BY age";
+        let (command, errs) = command::Command::parse_partial.parse_str(sql).unwrap();
+        assert_eq!(errs.len(), 2);
+        let command::Command::Select(query) = command else {
+            panic!("expected a query")
+        };
+        let select = query.body.first_select();
+        assert_eq!(select.exprs.len(), 2);
+        assert!(select.from.is_some());
+        assert!(select.where_expr.is_none());
+        assert!(query.order_by.is_some());
+    }
 }
@@ -1,16 +1,167 @@
-use self::{grammar::TableName, utils::parse_kw_if_matched};
+use self::{
+    grammar::TableName,
+    utils::{parse_kw_if_matched, recover_clause, SeparatedByComma},
+};
 use crate::*;
+use command::insert::InsertExpr;
+use command::select::Returning;
+use grammar::{ast::Expr, Name, TableRef};
 
+/// Ref: https://www.postgresql.org/docs/current/sql-update.html
+///
+/// ```text
+/// UPDATE <table_name> [[AS] alias]
+///     SET { <column> = { <expr> | DEFAULT } | ( <column>, .. ) = [ROW] ( { <expr> | DEFAULT }, .. ) }, ..
+///     [FROM <table_ref>]
+///     [WHERE <expr>]
+///     [RETURNING <select_expr>, ..]
+/// ```
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Update {
+    #[cfg_attr(feature = "serde", serde(with = "crate::tokens"))]
     pub kw: Ident,
     pub table_name: TableName,
+    pub alias: Option<Name>,
+    #[cfg_attr(feature = "serde", serde(with = "crate::tokens"))]
+    pub set_kw: Ident,
+    pub assignments: SeparatedByComma<Assignment>,
+    #[cfg_attr(feature = "serde", serde(with = "crate::tokens"))]
+    pub from: Option<(Ident, TableRef)>,
+    pub where_expr: Option<Expr>,
+    pub returning: Option<Returning>,
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Assignment {
+    /// `<column> = <expr>`
+    Single {
+        column: Name,
+        #[cfg_attr(feature = "serde", serde(with = "crate::tokens"))]
+        eq_token: Token![=],
+        value: InsertExpr,
+    },
+    /// `(<column>, ..) = [ROW] (<expr>, ..)`
+    Multiple {
+        #[cfg_attr(feature = "serde", serde(with = "crate::tokens"))]
+        columns: Punctuated<Name, Token![,]>,
+        #[cfg_attr(feature = "serde", serde(with = "crate::tokens"))]
+        eq_token: Token![=],
+        #[cfg_attr(feature = "serde", serde(with = "crate::tokens"))]
+        row_kw: Option<Ident>,
+        #[cfg_attr(feature = "serde", serde(with = "crate::tokens"))]
+        values_paren: Paren,
+        #[cfg_attr(feature = "serde", serde(with = "crate::tokens"))]
+        values: Punctuated<InsertExpr, Token![,]>,
+    },
+}
+
+impl Update {
+    /// The name used to refer to the target table within the query.
+    pub fn visible_name(&self) -> &Name {
+        self.alias.as_ref().unwrap_or(&self.table_name.alias)
+    }
 }
 
 impl Parse for Update {
     fn parse(input: ParseStream) -> Result<Self> {
+        let kw = parse_kw_if_matched(input, "UPDATE")?;
+        let TableRef { table_name, alias } = input.parse()?;
+        let set_kw = parse_kw_if_matched(input, "SET")?;
+        let assignments = input.parse()?;
+        utils::skip_to_clause(input)?;
         Ok(Update {
-            kw: parse_kw_if_matched(input, "UPDATE")?,
-            table_name: input.parse()?,
+            kw,
+            table_name,
+            alias,
+            set_kw,
+            assignments,
+            from: match parse_kw_if_matched(input, "FROM") {
+                Ok(kw) => recover_clause(input, |input| Ok((kw, input.parse()?)))?,
+                Err(_) => None,
+            },
+            where_expr: match parse_kw_if_matched(input, "WHERE") {
+                Ok(_) => recover_clause(input, Expr::parse)?,
+                Err(_) => None,
+            },
+            returning: Returning::parse_optional(input)?,
         })
     }
 }
+
+impl Parse for Assignment {
+    fn parse(input: ParseStream) -> Result<Self> {
+        if input.peek(Paren) {
+            let content;
+            parenthesized!(content in input);
+            let columns = content.call(Punctuated::parse_terminated)?;
+            let eq_token = input.parse()?;
+            let row_kw = parse_kw_if_matched(input, "ROW").ok();
+            let values;
+            return Ok(Self::Multiple {
+                columns,
+                eq_token,
+                row_kw,
+                values_paren: parenthesized!(values in input),
+                values: values.call(Punctuated::parse_terminated)?,
+            });
+        }
+        Ok(Self::Single {
+            column: input.parse()?,
+            eq_token: input.parse()?,
+            value: input.parse()?,
+        })
+    }
+}
+
+impl GetSpan for Assignment {
+    fn span(&self) -> Span {
+        match self {
+            Assignment::Single { column, .. } => column.span(),
+            Assignment::Multiple { values_paren, .. } => values_paren.span.join(),
+        }
+    }
+}
+
+impl fmt::Debug for Assignment {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Single { column, value, .. } => f
+                .debug_struct("Single")
+                .field("column", column)
+                .field("value", value)
+                .finish(),
+            Self::Multiple {
+                columns, values, ..
+            } => f
+                .debug_struct("Multiple")
+                .field("columns", &columns.iter().collect::<Vec<_>>())
+                .field("values", &values.iter().collect::<Vec<_>>())
+                .finish(),
+        }
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    #[test]
+    fn test_name() {
+        let g: Update = utils::test::syntex! {
+            UPDATE users AS u
+            SET name = "john", (age, email) = ROW (age + 1, DEFAULT)
+            FROM posts p
+            WHERE u.id = p.user_id
+            RETURNING u.id, name
+        }
+        .unwrap();
+
+        assert_eq!(g.visible_name().to_string(), "u");
+        assert_eq!(g.assignments.len(), 2);
+        assert!(
+            matches!(g.assignments[1], Assignment::Multiple { ref columns, .. } if columns.len() == 2)
+        );
+        assert!(g.from.is_some() && g.where_expr.is_some() && g.returning.is_some());
+    }
+}
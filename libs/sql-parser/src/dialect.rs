@@ -0,0 +1,953 @@
+//! SQL dialects, what each database accepts on top of the grammar parsed by this crate.
+//!
+//! The parser reads a superset of the dialects, [`check`] then reports whatever the dialect in
+//! use lacks, e.g. `DISTINCT ON` against SQLite, `BITAND` against Postgres or a `?` bind
+//! parameter against Postgres.
+use crate::command::{
+    call::Call, delete::Delete, insert::OnConflict, select::*, update::Update, Command,
+};
+use crate::function::FunctionCall;
+use crate::grammar::{ast::*, Cast, CastSyntax, Name, Placeholder};
+use crate::visit::{self, Visit};
+use crate::*;
+
+pub trait Dialect {
+    /// Display name, as used in diagnostics.
+    fn name(&self) -> &'static str;
+
+    /// Keywords that can't be used as a name unless quoted.
+    fn reserved_keywords(&self) -> &'static [&'static str];
+
+    fn is_reserved(&self, name: &str) -> bool {
+        self.reserved_keywords()
+            .iter()
+            .any(|kw| kw.eq_ignore_ascii_case(name))
+    }
+
+    /// `name` as a delimited identifier.
+    fn quote_identifier(&self, name: &str) -> String {
+        format!("\"{}\"", name.replace('"', "\"\""))
+    }
+
+    /// The name an unquoted identifier is stored under in the catalog.
+    fn fold_identifier(&self, name: &str) -> String {
+        name.to_lowercase()
+    }
+
+    /// Whether `name`, as written in a query and folded, refers to `stored`, a name in the
+    /// catalog.
+    fn matches_name(&self, stored: &str, name: &str) -> bool {
+        stored == name
+    }
+
+    /// Whether the function, by its lower case name, is available.
+    fn has_function(&self, name: &str) -> bool;
+
+    /// The Postgres type a type name of this dialect stands for, `None` if it is the same.
+    fn type_alias(&self, _name: &str) -> Option<&'static str> {
+        None
+    }
+
+    /// How bind parameters are written.
+    fn placeholder(&self) -> PlaceholderStyle;
+
+    fn supports(&self, feature: Feature) -> bool;
+}
+
+/// The form of the bind parameters of a dialect.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PlaceholderStyle {
+    /// `$1`, `$2`, ..
+    Numbered,
+    /// `?`
+    Anonymous,
+}
+
+impl fmt::Display for PlaceholderStyle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            PlaceholderStyle::Numbered => "`$1`",
+            PlaceholderStyle::Anonymous => "`?`",
+        })
+    }
+}
+
+/// Syntax that not every dialect has.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Feature {
+    /// `SELECT DISTINCT ON (..)`
+    DistinctOn,
+    /// `SELECT * EXCEPT (..)`
+    WildcardExcept,
+    /// `<term>::<type>`
+    DoubleColonCast,
+    /// `INSERT .. ON CONFLICT`
+    OnConflict,
+    /// `INSERT | UPDATE | DELETE .. RETURNING`
+    Returning,
+    /// `DELETE .. USING`
+    DeleteUsing,
+    /// `UPDATE .. FROM`
+    UpdateFrom,
+    /// `<aggregate>(..) FILTER (WHERE ..)`
+    AggregateFilter,
+    /// `ILIKE`
+    ILike,
+    /// `IS [NOT] DISTINCT FROM`
+    IsDistinctFrom,
+    /// `~`, `~*`, `!~`, `!~*`
+    RegexMatch,
+    /// `CALL <procedure>(..)`
+    Call,
+}
+
+impl fmt::Display for Feature {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Feature::DistinctOn => "`DISTINCT ON`",
+            Feature::WildcardExcept => "`* EXCEPT`",
+            Feature::DoubleColonCast => "`::` cast",
+            Feature::OnConflict => "`ON CONFLICT`",
+            Feature::Returning => "`RETURNING`",
+            Feature::DeleteUsing => "`DELETE .. USING`",
+            Feature::UpdateFrom => "`UPDATE .. FROM`",
+            Feature::AggregateFilter => "`FILTER (WHERE ..)`",
+            Feature::ILike => "`ILIKE`",
+            Feature::IsDistinctFrom => "`IS DISTINCT FROM`",
+            Feature::RegexMatch => "regular expression match operator",
+            Feature::Call => "`CALL`",
+        })
+    }
+}
+
+/// Functions of H2 that no other dialect has.
+const H2_BIT_FUNCTIONS: &[&str] = &[
+    "bitand",
+    "bitor",
+    "bitxor",
+    "bitnot",
+    "bitnand",
+    "bitnor",
+    "bitxnor",
+    "bitget",
+    "bitcount",
+    "lshift",
+    "rshift",
+    "ulshift",
+    "urshift",
+    "rotateleft",
+    "rotateright",
+];
+
+/// Ref: https://www.postgresql.org/docs/current/sql-keywords-appendix.html
+pub struct Postgres;
+
+impl Dialect for Postgres {
+    fn name(&self) -> &'static str {
+        "Postgres"
+    }
+
+    fn reserved_keywords(&self) -> &'static [&'static str] {
+        &[
+            "ALL",
+            "ANALYSE",
+            "ANALYZE",
+            "AND",
+            "ANY",
+            "ARRAY",
+            "AS",
+            "ASC",
+            "ASYMMETRIC",
+            "BOTH",
+            "CASE",
+            "CAST",
+            "CHECK",
+            "COLLATE",
+            "COLUMN",
+            "CONSTRAINT",
+            "CREATE",
+            "CURRENT_CATALOG",
+            "CURRENT_DATE",
+            "CURRENT_ROLE",
+            "CURRENT_TIME",
+            "CURRENT_TIMESTAMP",
+            "CURRENT_USER",
+            "DEFAULT",
+            "DEFERRABLE",
+            "DESC",
+            "DISTINCT",
+            "DO",
+            "ELSE",
+            "END",
+            "EXCEPT",
+            "FALSE",
+            "FETCH",
+            "FOR",
+            "FOREIGN",
+            "FROM",
+            "GRANT",
+            "GROUP",
+            "HAVING",
+            "IN",
+            "INITIALLY",
+            "INTERSECT",
+            "INTO",
+            "LATERAL",
+            "LEADING",
+            "LIMIT",
+            "LOCALTIME",
+            "LOCALTIMESTAMP",
+            "NOT",
+            "NULL",
+            "OFFSET",
+            "ON",
+            "ONLY",
+            "OR",
+            "ORDER",
+            "PLACING",
+            "PRIMARY",
+            "REFERENCES",
+            "RETURNING",
+            "SELECT",
+            "SESSION_USER",
+            "SOME",
+            "SYMMETRIC",
+            "SYSTEM_USER",
+            "TABLE",
+            "THEN",
+            "TO",
+            "TRAILING",
+            "TRUE",
+            "UNION",
+            "UNIQUE",
+            "USER",
+            "USING",
+            "VARIADIC",
+            "WHEN",
+            "WHERE",
+            "WINDOW",
+            "WITH",
+        ]
+    }
+
+    fn has_function(&self, name: &str) -> bool {
+        !matches!(name, "space" | "lcase" | "ucase") && !H2_BIT_FUNCTIONS.contains(&name)
+    }
+
+    fn placeholder(&self) -> PlaceholderStyle {
+        PlaceholderStyle::Numbered
+    }
+
+    fn supports(&self, feature: Feature) -> bool {
+        feature != Feature::WildcardExcept
+    }
+}
+
+/// Ref: https://www.sqlite.org/lang_keywords.html
+pub struct Sqlite;
+
+impl Dialect for Sqlite {
+    fn name(&self) -> &'static str {
+        "SQLite"
+    }
+
+    fn reserved_keywords(&self) -> &'static [&'static str] {
+        &[
+            "ADD",
+            "ALL",
+            "ALTER",
+            "AND",
+            "AS",
+            "AUTOINCREMENT",
+            "BETWEEN",
+            "CASE",
+            "CHECK",
+            "COLLATE",
+            "COMMIT",
+            "CONSTRAINT",
+            "CREATE",
+            "DEFAULT",
+            "DEFERRABLE",
+            "DELETE",
+            "DISTINCT",
+            "DROP",
+            "ELSE",
+            "ESCAPE",
+            "EXCEPT",
+            "EXISTS",
+            "FOREIGN",
+            "FROM",
+            "GROUP",
+            "HAVING",
+            "IN",
+            "INDEX",
+            "INSERT",
+            "INTERSECT",
+            "INTO",
+            "IS",
+            "ISNULL",
+            "JOIN",
+            "LIMIT",
+            "NOT",
+            "NOTHING",
+            "NOTNULL",
+            "NULL",
+            "ON",
+            "OR",
+            "ORDER",
+            "PRIMARY",
+            "REFERENCES",
+            "SELECT",
+            "SET",
+            "TABLE",
+            "THEN",
+            "TO",
+            "TRANSACTION",
+            "UNION",
+            "UNIQUE",
+            "UPDATE",
+            "USING",
+            "VALUES",
+            "WHEN",
+            "WHERE",
+        ]
+    }
+
+    /// Names keep their case, but are matched regardless of it, even quoted ones.
+    fn fold_identifier(&self, name: &str) -> String {
+        name.to_string()
+    }
+
+    fn matches_name(&self, stored: &str, name: &str) -> bool {
+        stored.eq_ignore_ascii_case(name)
+    }
+
+    fn has_function(&self, name: &str) -> bool {
+        !matches!(
+            name,
+            "cot"
+                | "ascii"
+                | "left"
+                | "right"
+                | "position"
+                | "lpad"
+                | "rpad"
+                | "regexp_replace"
+                | "repeat"
+                | "space"
+                | "split_part"
+                | "array_agg"
+                | "bool_and"
+                | "bool_or"
+        ) && !H2_BIT_FUNCTIONS.contains(&name)
+    }
+
+    /// Column types follow the affinity rules.
+    ///
+    /// Ref: https://www.sqlite.org/datatype3.html#determination_of_column_affinity
+    fn type_alias(&self, name: &str) -> Option<&'static str> {
+        let name = name.to_uppercase();
+        Some(if name.contains("INT") {
+            "bigint"
+        } else if ["CHAR", "CLOB", "TEXT"].iter().any(|ty| name.contains(ty)) {
+            "text"
+        } else if name.contains("BLOB") {
+            "bytea"
+        } else if ["REAL", "FLOA", "DOUB"].iter().any(|ty| name.contains(ty)) {
+            "double precision"
+        } else {
+            "numeric"
+        })
+    }
+
+    fn placeholder(&self) -> PlaceholderStyle {
+        PlaceholderStyle::Anonymous
+    }
+
+    fn supports(&self, feature: Feature) -> bool {
+        !matches!(
+            feature,
+            Feature::DistinctOn
+                | Feature::WildcardExcept
+                | Feature::DoubleColonCast
+                | Feature::DeleteUsing
+                | Feature::ILike
+                | Feature::RegexMatch
+                | Feature::Call
+        )
+    }
+}
+
+/// Ref: https://dev.mysql.com/doc/refman/8.0/en/keywords.html
+pub struct MySql;
+
+impl Dialect for MySql {
+    fn name(&self) -> &'static str {
+        "MySQL"
+    }
+
+    fn reserved_keywords(&self) -> &'static [&'static str] {
+        &[
+            "ADD",
+            "ALL",
+            "ALTER",
+            "AND",
+            "AS",
+            "ASC",
+            "BETWEEN",
+            "BY",
+            "CASE",
+            "CHECK",
+            "COLUMN",
+            "CONDITION",
+            "CONSTRAINT",
+            "CREATE",
+            "CROSS",
+            "CUME_DIST",
+            "CURRENT_DATE",
+            "CURRENT_TIME",
+            "CURRENT_TIMESTAMP",
+            "CURRENT_USER",
+            "DATABASE",
+            "DEFAULT",
+            "DELETE",
+            "DENSE_RANK",
+            "DESC",
+            "DISTINCT",
+            "DIV",
+            "DROP",
+            "ELSE",
+            "EXCEPT",
+            "EXISTS",
+            "FALSE",
+            "FIRST_VALUE",
+            "FOR",
+            "FROM",
+            "GROUP",
+            "GROUPS",
+            "HAVING",
+            "IN",
+            "INDEX",
+            "INSERT",
+            "INTERVAL",
+            "INTO",
+            "IS",
+            "JOIN",
+            "KEY",
+            "KEYS",
+            "LAG",
+            "LAST_VALUE",
+            "LEAD",
+            "LEFT",
+            "LIKE",
+            "LIMIT",
+            "MATCH",
+            "MOD",
+            "NOT",
+            "NTH_VALUE",
+            "NTILE",
+            "NULL",
+            "OF",
+            "ON",
+            "OR",
+            "ORDER",
+            "OVER",
+            "PERCENT_RANK",
+            "RANGE",
+            "RANK",
+            "RECURSIVE",
+            "REGEXP",
+            "RIGHT",
+            "ROW",
+            "ROWS",
+            "ROW_NUMBER",
+            "SELECT",
+            "SET",
+            "TABLE",
+            "THEN",
+            "TO",
+            "TRUE",
+            "UNION",
+            "UNIQUE",
+            "UPDATE",
+            "USING",
+            "VALUES",
+            "WHEN",
+            "WHERE",
+            "WINDOW",
+            "WITH",
+        ]
+    }
+
+    fn quote_identifier(&self, name: &str) -> String {
+        format!("`{}`", name.replace('`', "``"))
+    }
+
+    /// Names keep their case, but are matched regardless of it, as with
+    /// `lower_case_table_names` set for table names.
+    fn fold_identifier(&self, name: &str) -> String {
+        name.to_string()
+    }
+
+    fn matches_name(&self, stored: &str, name: &str) -> bool {
+        stored.eq_ignore_ascii_case(name)
+    }
+
+    fn has_function(&self, name: &str) -> bool {
+        !matches!(
+            name,
+            "cosh"
+                | "sinh"
+                | "tanh"
+                | "split_part"
+                | "string_agg"
+                | "array_agg"
+                | "bool_and"
+                | "bool_or"
+        ) && !H2_BIT_FUNCTIONS.contains(&name)
+    }
+
+    fn type_alias(&self, name: &str) -> Option<&'static str> {
+        Some(match name.to_lowercase().as_str() {
+            "datetime" => "timestamp",
+            "double" => "double precision",
+            "float" => "real",
+            "tinytext" | "mediumtext" | "longtext" => "text",
+            "blob" | "tinyblob" | "mediumblob" | "longblob" | "binary" | "varbinary" => "bytea",
+            "mediumint" => "integer",
+            "signed" => "bigint",
+            _ => return None,
+        })
+    }
+
+    fn placeholder(&self) -> PlaceholderStyle {
+        PlaceholderStyle::Anonymous
+    }
+
+    fn supports(&self, feature: Feature) -> bool {
+        matches!(feature, Feature::DeleteUsing | Feature::Call)
+    }
+}
+
+/// Ref: https://www.h2database.com/html/advanced.html#keywords
+pub struct H2;
+
+impl Dialect for H2 {
+    fn name(&self) -> &'static str {
+        "H2"
+    }
+
+    fn reserved_keywords(&self) -> &'static [&'static str] {
+        &[
+            "ALL",
+            "AND",
+            "ANY",
+            "ARRAY",
+            "AS",
+            "ASYMMETRIC",
+            "AUTHORIZATION",
+            "BETWEEN",
+            "BOTH",
+            "CASE",
+            "CAST",
+            "CHECK",
+            "CONSTRAINT",
+            "CROSS",
+            "CURRENT_CATALOG",
+            "CURRENT_DATE",
+            "CURRENT_PATH",
+            "CURRENT_ROLE",
+            "CURRENT_SCHEMA",
+            "CURRENT_TIME",
+            "CURRENT_TIMESTAMP",
+            "CURRENT_USER",
+            "DAY",
+            "DEFAULT",
+            "DISTINCT",
+            "ELSE",
+            "END",
+            "EXCEPT",
+            "EXISTS",
+            "FALSE",
+            "FETCH",
+            "FOR",
+            "FOREIGN",
+            "FROM",
+            "FULL",
+            "GROUP",
+            "GROUPS",
+            "HAVING",
+            "HOUR",
+            "IF",
+            "ILIKE",
+            "IN",
+            "INNER",
+            "INTERSECT",
+            "INTERVAL",
+            "IS",
+            "JOIN",
+            "KEY",
+            "LEADING",
+            "LEFT",
+            "LIKE",
+            "LIMIT",
+            "LOCALTIME",
+            "LOCALTIMESTAMP",
+            "MINUS",
+            "MINUTE",
+            "MONTH",
+            "NATURAL",
+            "NOT",
+            "NULL",
+            "OFFSET",
+            "ON",
+            "OR",
+            "ORDER",
+            "OVER",
+            "PARTITION",
+            "PRIMARY",
+            "QUALIFY",
+            "RANGE",
+            "REGEXP",
+            "RIGHT",
+            "ROW",
+            "ROWNUM",
+            "ROWS",
+            "SECOND",
+            "SELECT",
+            "SESSION_USER",
+            "SET",
+            "SOME",
+            "SYMMETRIC",
+            "SYSTEM_USER",
+            "TABLE",
+            "TO",
+            "TOP",
+            "TRAILING",
+            "TRUE",
+            "UESCAPE",
+            "UNION",
+            "UNIQUE",
+            "UNKNOWN",
+            "USER",
+            "USING",
+            "VALUE",
+            "VALUES",
+            "WHEN",
+            "WHERE",
+            "WINDOW",
+            "WITH",
+            "YEAR",
+            "_ROWID_",
+        ]
+    }
+
+    fn fold_identifier(&self, name: &str) -> String {
+        name.to_uppercase()
+    }
+
+    fn has_function(&self, name: &str) -> bool {
+        !matches!(name, "split_part" | "string_agg")
+    }
+
+    fn type_alias(&self, name: &str) -> Option<&'static str> {
+        Some(match name.to_lowercase().as_str() {
+            "character large object" | "clob" => "text",
+            "binary varying" | "binary large object" | "blob" | "varbinary" => "bytea",
+            "double" => "double precision",
+            "timestamp with time zone" => "timestamptz",
+            _ => return None,
+        })
+    }
+
+    fn placeholder(&self) -> PlaceholderStyle {
+        PlaceholderStyle::Anonymous
+    }
+
+    fn supports(&self, feature: Feature) -> bool {
+        !matches!(
+            feature,
+            Feature::OnConflict
+                | Feature::Returning
+                | Feature::DeleteUsing
+                | Feature::UpdateFrom
+                | Feature::RegexMatch
+        )
+    }
+}
+
+/// The dialect by its name, case insensitive.
+pub fn from_name(name: &str) -> Option<&'static dyn Dialect> {
+    Some(match name.to_lowercase().as_str() {
+        "postgres" | "postgresql" => &Postgres,
+        "sqlite" => &Sqlite,
+        "mysql" => &MySql,
+        "h2" => &H2,
+        _ => return None,
+    })
+}
+
+/// Reports the syntax, functions and unquoted keywords of `command` that `dialect` lacks.
+pub fn check(dialect: &dyn Dialect, command: &Command) -> Vec<Error> {
+    let mut checker = Checker {
+        dialect,
+        errs: vec![],
+    };
+    checker.visit_command(command);
+    checker.errs
+}
+
+struct Checker<'d> {
+    dialect: &'d dyn Dialect,
+    errs: Vec<Error>,
+}
+
+impl Checker<'_> {
+    fn expect(&mut self, feature: Feature, span: Span) {
+        if !self.dialect.supports(feature) {
+            let message = format!("{feature} is not supported by {}", self.dialect.name());
+            self.errs.push(Error::new(span, message));
+        }
+    }
+}
+
+impl<'ast> Visit<'ast> for Checker<'_> {
+    fn visit_select_filter(&mut self, node: &'ast SelectFilter) {
+        if let SelectFilter::DistinctON { on_kw, .. } = node {
+            self.expect(Feature::DistinctOn, on_kw.span());
+        }
+        visit::visit_select_filter(self, node)
+    }
+
+    fn visit_select_expr(&mut self, node: &'ast SelectExpr) {
+        if let SelectExpr::WildCard { except, .. } = node {
+            if !except.is_empty() {
+                self.expect(Feature::WildcardExcept, node.span());
+            }
+        }
+        visit::visit_select_expr(self, node)
+    }
+
+    fn visit_cast(&mut self, node: &'ast Cast) {
+        if let CastSyntax::DoubleColon(token) = &node.syntax {
+            self.expect(Feature::DoubleColonCast, token.spans[0]);
+        }
+        visit::visit_cast(self, node)
+    }
+
+    fn visit_on_conflict(&mut self, node: &'ast OnConflict) {
+        self.expect(Feature::OnConflict, node.kws.0.span());
+        visit::visit_on_conflict(self, node)
+    }
+
+    fn visit_returning(&mut self, node: &'ast Returning) {
+        self.expect(Feature::Returning, node.kw.span());
+        visit::visit_returning(self, node)
+    }
+
+    fn visit_delete(&mut self, node: &'ast Delete) {
+        if let Some((using_kw, _)) = &node.using {
+            self.expect(Feature::DeleteUsing, using_kw.span());
+        }
+        visit::visit_delete(self, node)
+    }
+
+    fn visit_update(&mut self, node: &'ast Update) {
+        if let Some((from_kw, _)) = &node.from {
+            self.expect(Feature::UpdateFrom, from_kw.span());
+        }
+        visit::visit_update(self, node)
+    }
+
+    fn visit_call(&mut self, node: &'ast Call) {
+        self.expect(Feature::Call, node.kw.span());
+        visit::visit_call(self, node)
+    }
+
+    fn visit_function_call(&mut self, node: &'ast FunctionCall) {
+        if let Some(filter) = &node.filter {
+            self.expect(Feature::AggregateFilter, filter.filter_kw.span());
+        }
+        let name = node.function.name();
+        if !self.dialect.has_function(&name) {
+            let message = format!(
+                "function `{name}` does not exist in {}",
+                self.dialect.name()
+            );
+            self.errs.push(Error::new(node.function.span(), message));
+        }
+        visit::visit_function_call(self, node)
+    }
+
+    fn visit_binary_operator(&mut self, node: &'ast BinaryOperator) {
+        if let BinaryOperatorKind::Match { .. } = node.kind {
+            self.expect(Feature::RegexMatch, node.span);
+        }
+    }
+
+    fn visit_predicate(&mut self, node: &'ast Predicate) {
+        if let Predicate::Like {
+            kind: LikeKind::ILike,
+            kw,
+            ..
+        } = node
+        {
+            self.expect(Feature::ILike, kw.span());
+        }
+        visit::visit_predicate(self, node)
+    }
+
+    fn visit_is_predicate(&mut self, node: &'ast IsPredicate) {
+        if let IsPredicate::DistinctFrom { kws, .. } = node {
+            self.expect(Feature::IsDistinctFrom, kws.0.span());
+        }
+        visit::visit_is_predicate(self, node)
+    }
+
+    fn visit_placeholder(&mut self, node: &'ast Placeholder) {
+        let style = match node {
+            Placeholder::Numbered { .. } => PlaceholderStyle::Numbered,
+            Placeholder::Anonymous(_) => PlaceholderStyle::Anonymous,
+        };
+        let expected = self.dialect.placeholder();
+        if style != expected {
+            let message = format!(
+                "{style} placeholders are not supported by {}, use {expected}",
+                self.dialect.name()
+            );
+            self.errs.push(Error::new(node.span(), message));
+        }
+    }
+
+    fn visit_name(&mut self, node: &'ast Name) {
+        let Name::Ident(ident) = node else {
+            return;
+        };
+        let name = ident.to_string();
+        if self.dialect.is_reserved(&name) {
+            // Quoted names aren't folded, so quote the name it is stored under.
+            let message = format!(
+                "`{name}` is a reserved keyword in {}, quote it as {}",
+                self.dialect.name(),
+                self.dialect.quote_identifier(&node.value(self.dialect))
+            );
+            self.errs.push(Error::new(ident.span(), message));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn errors(dialect: &dyn Dialect, sql: &str) -> Vec<String> {
+        let command = syn::parse_str::<Command>(sql).unwrap();
+        check(dialect, &command)
+            .into_iter()
+            .map(|err| err.to_string())
+            .collect()
+    }
+
+    #[test]
+    fn features() {
+        let sql = r#"SELECT DISTINCT ON (id) id, name::text FROM users WHERE name ILIKE "a%""#;
+        assert!(errors(&Postgres, sql).is_empty());
+        assert_eq!(
+            errors(&Sqlite, sql),
+            [
+                "`DISTINCT ON` is not supported by SQLite",
+                "`::` cast is not supported by SQLite",
+                "`ILIKE` is not supported by SQLite",
+            ]
+        );
+
+        let sql = "SELECT * EXCEPT (name), BITAND(id, 1) FROM users";
+        assert!(errors(&H2, sql).is_empty());
+        assert_eq!(
+            errors(&Postgres, sql),
+            [
+                "`* EXCEPT` is not supported by Postgres",
+                "function `bitand` does not exist in Postgres",
+            ]
+        );
+
+        let sql = "SELECT LCASE(name), UCASE(name) FROM users";
+        assert!(errors(&MySql, sql).is_empty());
+        assert_eq!(
+            errors(&Postgres, sql),
+            [
+                "function `lcase` does not exist in Postgres",
+                "function `ucase` does not exist in Postgres",
+            ]
+        );
+
+        let sql = "DELETE FROM users WHERE id = 1 RETURNING id";
+        assert_eq!(
+            errors(&MySql, sql),
+            ["`RETURNING` is not supported by MySQL"]
+        );
+
+        let sql = "CALL refresh(1)";
+        assert!(errors(&MySql, sql).is_empty());
+        assert_eq!(errors(&Sqlite, sql), ["`CALL` is not supported by SQLite"]);
+    }
+
+    #[test]
+    fn placeholders() {
+        let sql = "SELECT id FROM users WHERE id = $1 OR age > $1";
+        assert!(errors(&Postgres, sql).is_empty());
+        assert_eq!(
+            errors(&Sqlite, sql),
+            [
+                "`$1` placeholders are not supported by SQLite, use `?`",
+                "`$1` placeholders are not supported by SQLite, use `?`",
+            ]
+        );
+
+        let sql = "SELECT id FROM users WHERE id = ?";
+        assert!(errors(&Sqlite, sql).is_empty());
+        assert!(errors(&MySql, sql).is_empty());
+        assert!(errors(&H2, sql).is_empty());
+        assert_eq!(
+            errors(&Postgres, sql),
+            ["`?` placeholders are not supported by Postgres, use `$1`"]
+        );
+        assert!(syn::parse_str::<Command>("SELECT $0").is_err());
+    }
+
+    #[test]
+    fn reserved_keywords() {
+        let sql = r#"SELECT key, r"user" FROM users"#;
+        assert!(errors(&Postgres, sql).is_empty());
+        assert_eq!(
+            errors(&MySql, sql),
+            ["`key` is a reserved keyword in MySQL, quote it as `key`"]
+        );
+        // Quoting keeps the case, so the folded name is quoted.
+        assert_eq!(
+            errors(&Postgres, "SELECT id FROM User"),
+            [r#"`User` is a reserved keyword in Postgres, quote it as "user""#]
+        );
+    }
+
+    #[test]
+    fn dialects() {
+        assert_eq!(from_name("PostgreSQL").unwrap().name(), "Postgres");
+        assert_eq!(
+            from_name("mysql").unwrap().quote_identifier("a`b"),
+            "`a``b`"
+        );
+        assert_eq!(
+            from_name("sqlite").unwrap().type_alias("VARCHAR(10)"),
+            Some("text")
+        );
+        assert_eq!(from_name("h2").unwrap().fold_identifier("users"), "USERS");
+        assert!(from_name("sqlite").unwrap().matches_name("users", "Users"));
+        assert!(from_name("mysql").unwrap().matches_name("users", "USERS"));
+        assert!(!from_name("postgres")
+            .unwrap()
+            .matches_name("users", "Users"));
+        assert!(!from_name("h2").unwrap().matches_name("users", "USERS"));
+        assert!(from_name("oracle").is_none());
+    }
+}
@@ -0,0 +1,154 @@
+use crate::command::Command;
+use crate::*;
+use proc_macro2::TokenStream;
+use std::ops::Range;
+
+/// A syntax error located in the SQL text, for use outside of a macro where spans mean nothing.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub message: String,
+    /// 1-based
+    pub line: usize,
+    /// 1-based, in characters
+    pub column: usize,
+    /// Byte range in the SQL text.
+    pub range: Range<usize>,
+}
+
+impl ParseError {
+    fn new(sql: &str, message: String, range: Range<usize>) -> Self {
+        let line_start = sql[..range.start].rfind('\n').map_or(0, |i| i + 1);
+        Self {
+            message,
+            line: sql[..range.start].matches('\n').count() + 1,
+            column: sql[line_start..range.start].chars().count() + 1,
+            range,
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}: {}", self.line, self.column, self.message)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Parses a command from plain SQL text at runtime, e.g. in tooling or tests.
+///
+/// The text goes through the same lexer as `sql!("..")`. Token positions are recorded by
+/// `proc-macro2`, which keeps every parsed text for the lifetime of the thread, hence the
+/// `positions` feature this needs.
+pub fn parse_command(sql: &str) -> std::result::Result<Command, Vec<ParseError>> {
+    // One punctuation per char of `sql`, and one past its end, with the same line breaks,
+    // gives a span for every position of `sql` with its line and column.
+    let layout: String = sql
+        .chars()
+        .map(|ch| if ch == '\n' { ch } else { ';' })
+        .chain([';'])
+        .collect();
+    let mut puncts = layout
+        .parse::<TokenStream>()
+        .map_err(|err| vec![ParseError::new(sql, err.to_string(), 0..0)])?
+        .into_iter();
+    // A line break takes the span of the char before it.
+    let mut span = Span::call_site();
+    let spans: Vec<Span> = layout
+        .chars()
+        .map(|ch| {
+            if ch != '\n' {
+                span = puncts.next().map_or(span, |tt| tt.span());
+            }
+            span
+        })
+        .collect();
+    let char_starts: Vec<usize> = sql
+        .char_indices()
+        .map(|(i, _)| i)
+        .chain([sql.len()])
+        .collect();
+    let chars = char_starts.len() - 1;
+    let char_index = |byte: usize| char_starts.partition_point(|&start| start < byte);
+    let span_of = |range: Range<usize>| {
+        let start = spans[char_index(range.start)];
+        match range.is_empty() {
+            true => start,
+            false => start
+                .join(spans[char_index(range.end) - 1])
+                .unwrap_or(start),
+        }
+    };
+    let tokens = lexer::to_token_stream(sql, span_of);
+    tokens
+        .and_then(|tokens| Command::parse.parse2(tokens))
+        .map_err(|err| {
+            err.into_iter()
+                .map(|err| {
+                    // A byte of the layout is a char of `sql`, the call site is the end of input.
+                    let range = match err.span().byte_range() {
+                        range if range.start < chars && range != (0..0) => {
+                            char_starts[range.start]..char_starts[range.end.min(chars)]
+                        }
+                        _ => sql.len()..sql.len(),
+                    };
+                    ParseError::new(sql, err.to_string(), range)
+                })
+                .collect()
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn positions() {
+        assert!(parse_command("SELECT id FROM users WHERE id = 1").is_ok());
+
+        let errs = parse_command("SELECT id\nFROM users\nWHERE id = 1 AND").unwrap_err();
+        assert_eq!(errs.len(), 1);
+        assert_eq!((errs[0].line, errs[0].column), (3, 17));
+        assert_eq!(errs[0].range, 37..37);
+
+        let errs = parse_command("SELECT 'é', 1e-3x").unwrap_err();
+        assert_eq!(
+            errs,
+            [ParseError {
+                message: "trailing junk after numeric literal".to_string(),
+                line: 1,
+                column: 13,
+                range: 13..18,
+            }]
+        );
+        assert_eq!(
+            errs[0].to_string(),
+            "1:13: trailing junk after numeric literal"
+        );
+
+        let errs = parse_command("SELECT 1\r\nFROM users\r\nWHERE").unwrap_err();
+        assert_eq!((errs[0].line, errs[0].column), (3, 6));
+
+        let errs = parse_command("SELEC 1").unwrap_err();
+        assert_eq!(errs[0].range, 0..5);
+
+        let errs = parse_command("SELECT a$b FROM t").unwrap_err();
+        assert_eq!(errs[0].range, 7..10);
+    }
+
+    #[test]
+    fn all_errors() {
+        let errs = parse_command(
+            "SELECT a +, concat(1,, 2)\nFROM users WHERE = 1\nORDER BY a DESC x LIMIT 1",
+        )
+        .unwrap_err();
+        let positions: Vec<_> = errs.iter().map(|err| (err.line, err.column)).collect();
+        assert_eq!(positions, [(1, 11), (1, 22), (2, 18), (3, 17)]);
+        assert_eq!(errs[3].message, "unexpected token");
+
+        let errs = parse_command("UPDATE users SET name = , age = 1 WHERE id = = 1 RETURNING id")
+            .unwrap_err();
+        let columns: Vec<_> = errs.iter().map(|err| err.column).collect();
+        assert_eq!(columns, [25, 46]);
+    }
+}
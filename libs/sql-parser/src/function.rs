@@ -1,161 +1,368 @@
-#![allow(non_camel_case_types)]
 use crate::*;
-use grammar::ast::{Arithmetic, OrExpr};
-use utils::SeparatedByComma;
-use proc_macro2::TokenStream;
-
-macro_rules! parse_arg {
-    [$name: ident, $i: ident,] => {
-        FunctionKind::$name()
-    };
-    [$name: ident, $i: ident, $_1: ty] => {
-        FunctionKind::$name($i.parse()?)
-    };
-    [$name: ident, $i: ident, $_1: ty, $_2: ty] => {
-        {
-            let a = $i.parse()?;
-            let _ = $i.parse::<Token![,]>()?;
-            let b = $i.parse()?;
-            FunctionKind::$name(a, b)
+use grammar::ast::Expr;
+use utils::{parse_keywords_if_matched, parse_kw_if_matched, SeparatedByComma};
+use window::{OrderByClause, Window};
+
+/// `<name> ( <args> )`
+///
+/// Every function is parsed the same way, its overloads are resolved by the analyzer.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Function {
+    #[cfg_attr(feature = "serde", serde(with = "crate::tokens"))]
+    pub name: Ident,
+    #[cfg_attr(feature = "serde", serde(with = "crate::tokens"))]
+    pub paren_token: Paren,
+    pub args: FunctionArgs,
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum FunctionArgs {
+    List(ArgList),
+    /// The SQL standard forms of `SUBSTRING`, `TRIM` and `POSITION`, which use keywords.
+    Substring(SubstringArgs),
+    Trim(TrimArgs),
+    Position(PositionArgs),
+}
+
+/// `<function> ( .. ) [FILTER ( WHERE <condition> )] [OVER <window>]`
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FunctionCall {
+    pub function: Function,
+    pub filter: Option<Filter>,
+    pub over: Option<Window>,
+}
+
+/// `FILTER ( WHERE <condition> )`
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Filter {
+    #[cfg_attr(feature = "serde", serde(with = "crate::tokens"))]
+    pub filter_kw: Ident,
+    #[cfg_attr(feature = "serde", serde(with = "crate::tokens"))]
+    pub paren_token: Paren,
+    #[cfg_attr(feature = "serde", serde(with = "crate::tokens"))]
+    pub where_kw: Ident,
+    pub condition: Expr,
+}
+
+/// Ref: https://www.postgresql.org/docs/current/sql-expressions.html#SYNTAX-AGGREGATES
+///
+/// ```text
+/// ( [ALL | DISTINCT] { * | <expr>, .. } [ORDER BY <sort_spec>, ..] )
+/// ```
+///
+/// The quantifier, `*` and `ORDER BY` are only valid for aggregate calls.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ArgList {
+    #[cfg_attr(feature = "serde", serde(with = "crate::tokens"))]
+    pub quantifier: Option<Ident>,
+    pub args: ArgItems,
+    pub order_by: Option<OrderByClause>,
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ArgItems {
+    Wildcard(#[cfg_attr(feature = "serde", serde(with = "crate::tokens"))] Token![*]),
+    Exprs(SeparatedByComma<Expr>),
+}
+
+/// `( <string> { [FROM <start>] [FOR <count>] | , <start> [, <count>] } )`
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SubstringArgs {
+    pub string: Expr,
+    pub start: Option<Expr>,
+    pub count: Option<Expr>,
+}
+
+/// `( [LEADING | TRAILING | BOTH] { [<characters>] FROM <string> | <string> [, <characters>] } )`
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TrimArgs {
+    #[cfg_attr(feature = "serde", serde(with = "crate::tokens"))]
+    pub side: Option<Ident>,
+    pub characters: Option<Expr>,
+    pub string: Expr,
+}
+
+/// `( <substring> IN <string> )`
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PositionArgs {
+    pub substring: Expr,
+    #[cfg_attr(feature = "serde", serde(with = "crate::tokens"))]
+    pub in_kw: Ident,
+    pub string: Expr,
+}
+
+impl Function {
+    /// Lowercase function name, as Postgres names the output column.
+    pub fn name(&self) -> String {
+        self.name.unraw().to_string().to_lowercase()
+    }
+
+    /// Positional arguments, a keyword form gives them in the order of the plain call:
+    /// `SUBSTRING(s FROM a FOR b)` is `substring(s, a, b)` and `TRIM(c FROM s)` is `trim(s, c)`.
+    pub fn args(&self) -> Vec<&Expr> {
+        match &self.args {
+            FunctionArgs::List(list) => match &list.args {
+                ArgItems::Wildcard(_) => vec![],
+                ArgItems::Exprs(exprs) => exprs.iter().collect(),
+            },
+            FunctionArgs::Substring(args) => {
+                [Some(&args.string), args.start.as_ref(), args.count.as_ref()]
+                    .into_iter()
+                    .flatten()
+                    .collect()
+            }
+            FunctionArgs::Trim(args) => {
+                [&args.string].into_iter().chain(&args.characters).collect()
+            }
+            FunctionArgs::Position(args) => vec![&args.substring, &args.string],
+        }
+    }
+
+    /// Every expression of the call, the arguments followed by the `ORDER BY` of an aggregate.
+    pub fn exprs(&self) -> Vec<&Expr> {
+        let mut exprs = self.args();
+        if let Some(order_by) = self.arg_list().and_then(|list| list.order_by.as_ref()) {
+            exprs.extend(order_by.sort_specs.iter().map(|spec| &spec.expr));
+        }
+        exprs
+    }
+
+    pub fn arg_list(&self) -> Option<&ArgList> {
+        match &self.args {
+            FunctionArgs::List(list) => Some(list),
+            _ => None,
         }
     }
+
+    /// `count(*)`
+    pub fn is_wildcard(&self) -> bool {
+        self.arg_list()
+            .is_some_and(|list| matches!(list.args, ArgItems::Wildcard(_)))
+    }
+}
+
+impl ArgList {
+    pub fn is_distinct(&self) -> bool {
+        self.quantifier
+            .as_ref()
+            .is_some_and(|kw| kw.to_string().eq_ignore_ascii_case("DISTINCT"))
+    }
 }
 
-macro_rules! define_function {
-    [
-        $(  $name: ident   $(| $alies: ident)*  (  $($arg: tt)*   )  ),*
-    ] => {
-        // pub const SQL_FUNC_NAMES: &[&str] = &[$(stringify!($name)),* ];
-        pub type Function = WithSpan<FunctionKind>;
-        pub enum FunctionKind {
-            UnknownFunc(Ident, TokenStream),
-            $($name ($($arg)*)),*
+impl Parse for Function {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let name = input.call(Ident::parse_any)?;
+        let content;
+        let paren_token = parenthesized!(content in input);
+        let args = match name.to_string().to_uppercase().as_str() {
+            // `substr` is a plain function, it only takes the comma form.
+            "SUBSTRING" => FunctionArgs::Substring(content.parse()?),
+            "TRIM" => FunctionArgs::Trim(content.parse()?),
+            "POSITION" => FunctionArgs::Position(content.parse()?),
+            _ => FunctionArgs::List(content.parse()?),
+        };
+        Ok(Self {
+            name,
+            paren_token,
+            args,
+        })
+    }
+}
+
+impl Parse for ArgList {
+    fn parse(input: ParseStream) -> Result<Self> {
+        Ok(Self {
+            quantifier: parse_keywords_if_matched(input, &["ALL", "DISTINCT"]).ok(),
+            args: match input.peek(Token![*]) {
+                true => ArgItems::Wildcard(input.parse()?),
+                false => ArgItems::Exprs(input.parse()?),
+            },
+            order_by: OrderByClause::parse_optional(input)?,
+        })
+    }
+}
+
+impl Parse for SubstringArgs {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let string = input.parse()?;
+        if input.peek(Token![,]) {
+            let _ = input.parse::<Token![,]>()?;
+            let start = Some(input.parse()?);
+            let count = match input.parse::<Option<Token![,]>>()? {
+                Some(_) => Some(input.parse()?),
+                None => None,
+            };
+            return Ok(Self {
+                string,
+                start,
+                count,
+            });
         }
-        impl Parse for WithSpan<FunctionKind> {
-            fn parse(input: ParseStream) -> Result<Self> {
-                let fn_name = input.parse::<Ident>()?;
-                let i;
-                parenthesized!(i in input);
-                Ok(Self::new(fn_name.span(), match fn_name.to_string().to_uppercase().as_str() {
-                    $(stringify!($name) $(| stringify!($alies))* => parse_arg!($name, i, $($arg)*),)*
-                    _ => FunctionKind::UnknownFunc(fn_name, i.parse()?),
-                }))
-            }
+        Ok(Self {
+            string,
+            start: match parse_kw_if_matched(input, "FROM") {
+                Ok(_) => Some(input.parse()?),
+                Err(_) => None,
+            },
+            count: match parse_kw_if_matched(input, "FOR") {
+                Ok(_) => Some(input.parse()?),
+                Err(_) => None,
+            },
+        })
+    }
+}
+
+impl Parse for TrimArgs {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let side = parse_keywords_if_matched(input, &["LEADING", "TRAILING", "BOTH"]).ok();
+        if parse_kw_if_matched(input, "FROM").is_ok() {
+            let string = input.parse()?;
+            let characters = match input.parse::<Option<Token![,]>>()? {
+                Some(_) => Some(input.parse()?),
+                None => None,
+            };
+            return Ok(Self {
+                side,
+                characters,
+                string,
+            });
+        }
+        let first = input.parse()?;
+        if parse_kw_if_matched(input, "FROM").is_ok() {
+            return Ok(Self {
+                side,
+                characters: Some(first),
+                string: input.parse()?,
+            });
         }
-    };
-}
-
-define_function! {
-    // ----------------------- Numeric Function -----------------------
-    ABS(Arithmetic),
-    ACOS(Arithmetic),
-    ASIN(Arithmetic),
-    ATAN(Arithmetic),
-    COS(Arithmetic),
-    COSH(Arithmetic),
-    COT(Arithmetic),
-    SIN(Arithmetic),
-    SINH(Arithmetic),
-    TAN(Arithmetic),
-    TANH(Arithmetic),
-
-    ATAN2(Arithmetic, Arithmetic),
-
-    BITAND(OrExpr, OrExpr),
-    BITOR(OrExpr, OrExpr),
-    BITXOR(OrExpr, OrExpr),
-    BITNOT(OrExpr),
-    BITNAND(OrExpr, OrExpr),
-    BITNOR(OrExpr, OrExpr),
-    BITXNOR(OrExpr, OrExpr),
-    BITGET(OrExpr, LitInt),
-    BITCOUNT(OrExpr),
-
-    LSHIFT(OrExpr, LitInt),
-    RSHIFT(OrExpr, LitInt),
-    ULSHIFT(OrExpr, LitInt),
-    URSHIFT(OrExpr, LitInt),
-
-    ROTATELEFT(OrExpr, LitInt),
-    ROTATERIGHT(OrExpr, LitInt),
-
-    MOD(Arithmetic, Arithmetic),
-
-    CEIL | CEILING(Arithmetic),
-    DEGREES(Arithmetic),
-    EXP(Arithmetic),
-    FLOOR(Arithmetic),
-    LN(Arithmetic),
-
-    LOG(Arithmetic, Arithmetic),
-    LOG10(Arithmetic),
-
-    // ORA_HASH(OrExpr),
-    RADIANS(Arithmetic),
-    SQRT(Arithmetic),
-    PI(),
-    POWER(Arithmetic, Arithmetic),
-    // RAND | RANDOM(Arithmetic),
-    // RANDOM_UUID | UUID(),
-    ROUND(Arithmetic),
-    // SECURE_RAND(LitInt),
-    SIGN(Arithmetic),
-
-    // ENCRYPT(..),
-    // DECRYPT(..),
-    // HASH(..),
-    // TRUNC | TRUNCATE(..),
-    // COMPRESS(..),
-    // EXPAND(..),
-    // ZERO(..)
-
-    // ----------------------- String Function -----------------------
-
-    ASCII(LitStr),
-    // BIT_LENGTH(),
-    CHAR_LENGTH | CHARACTER_LENGTH | LENGTH(LitStr),
-    // OCTET_LENGTH(),
-    // CHAR | CHR(LitInt),
-    CONCAT(SeparatedByComma<LitStr>),
-
-    // CONCAT_WS(),
-    // DIFFERENCE(LitStr, LitStr),
-    // HEXTORAW(LitStr),
-    // RAWTOHEX(),
-    // INSERT(),
-    LOWER | LCASE(LitStr),
-    UPPER | UCASE(LitStr),
-    LEFT(LitStr, LitInt),
-    RIGHT(LitStr, LitInt),
-    // LOCATE(),
-    // LPAD(),
-    // RPAD(),
-    // LTRIM(),
-    // RTRIM(),
-    // BTRIM(),
-    // TRIM(),
-    // REGEXP_REPLACE(),
-    // REGEXP_LIKE(),
-    // REGEXP_SUBSTR(),
-    REPEAT(LitStr, LitInt),
-    // REPLACE(),
-    // SOUNDEX(LitStr),
-    SPACE(LitInt),
-    // STRINGDECODE(),
-    // STRINGENCODE(),
-    // STRINGTOUTF8(),
-    // SUBSTRING(),
-    // UTF8TOSTRING(),
-    // QUOTE_IDENT(),
-    // XMLATTR(),
-    // XMLNODE(),
-    // XMLCOMMENT(),
-    // XMLCDATA(),
-    // XMLSTARTDOC(),
-    // XMLTEXT(),
-    // TO_CHAR(),
-    // TRANSLATE()
-
-    // ----------------------- Aggregate Functions -----------------------
-    AVG()
+        Ok(Self {
+            side,
+            characters: match input.parse::<Option<Token![,]>>()? {
+                Some(_) => Some(input.parse()?),
+                None => None,
+            },
+            string: first,
+        })
+    }
+}
+
+impl Parse for PositionArgs {
+    fn parse(input: ParseStream) -> Result<Self> {
+        Ok(Self {
+            substring: Expr::parse_operand(input)?,
+            in_kw: parse_kw_if_matched(input, "IN")?,
+            string: Expr::parse_operand(input)?,
+        })
+    }
+}
+
+impl Parse for FunctionCall {
+    fn parse(input: ParseStream) -> Result<Self> {
+        Ok(Self {
+            function: input.parse()?,
+            filter: match parse_kw_if_matched(input, "FILTER") {
+                Ok(filter_kw) => {
+                    let content;
+                    Some(Filter {
+                        filter_kw,
+                        paren_token: parenthesized!(content in input),
+                        where_kw: parse_kw_if_matched(&content, "WHERE")?,
+                        condition: content.parse()?,
+                    })
+                }
+                Err(_) => None,
+            },
+            over: Window::parse_optional(input)?,
+        })
+    }
+}
+
+impl GetSpan for Function {
+    fn span(&self) -> Span {
+        self.name.span()
+    }
+}
+
+impl GetSpan for FunctionCall {
+    fn span(&self) -> Span {
+        self.function.span()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keyword_arguments() {
+        let f: Function = utils::test::syntex!(SUBSTRING(name FROM 2 FOR 3)).unwrap();
+        let FunctionArgs::Substring(args) = &f.args else {
+            panic!("expected SUBSTRING");
+        };
+        assert!(args.start.is_some() && args.count.is_some());
+
+        let f: Function = utils::test::syntex!(SUBSTRING(name, 2)).unwrap();
+        assert!(matches!(
+            &f.args,
+            FunctionArgs::Substring(SubstringArgs {
+                start: Some(_),
+                count: None,
+                ..
+            })
+        ));
+
+        let f: Function = utils::test::syntex!(SUBSTR(name, 2)).unwrap();
+        assert!(matches!(&f.args, FunctionArgs::List(_)));
+        assert!(syn::parse_str::<Function>("SUBSTR(name FROM 1 FOR 2)").is_err());
+
+        let f: Function = utils::test::syntex!(TRIM(LEADING "x" FROM name)).unwrap();
+        let FunctionArgs::Trim(args) = &f.args else {
+            panic!("expected TRIM");
+        };
+        assert!(args.side.is_some() && args.characters.is_some());
+
+        let f: Function = utils::test::syntex!(TRIM(BOTH FROM name, "xy")).unwrap();
+        assert!(matches!(
+            &f.args,
+            FunctionArgs::Trim(TrimArgs {
+                characters: Some(_),
+                ..
+            })
+        ));
+
+        let f: Function = utils::test::syntex!(TRIM(name)).unwrap();
+        assert!(matches!(
+            &f.args,
+            FunctionArgs::Trim(TrimArgs {
+                side: None,
+                characters: None,
+                ..
+            })
+        ));
+
+        let f: Function = utils::test::syntex!(POSITION("@" IN LOWER(email))).unwrap();
+        assert_eq!(f.args().len(), 2);
+
+        let f: Function = utils::test::syntex!(REPLACE(name, "a", "b")).unwrap();
+        assert_eq!(f.args().len(), 3);
+    }
+
+    #[test]
+    fn any_function() {
+        let f: Function = utils::test::syntex!(round(score, 2)).unwrap();
+        assert_eq!((f.name(), f.args().len()), ("round".to_string(), 2));
+
+        let f: Function = utils::test::syntex!(PI()).unwrap();
+        assert!(f.args().is_empty() && !f.is_wildcard());
+
+        let f: Function = utils::test::syntex!(my_func(a, b + 1, lower(c))).unwrap();
+        assert_eq!(f.args().len(), 3);
+
+        let f: Function = utils::test::syntex!(string_agg(DISTINCT name, "," ORDER BY id)).unwrap();
+        assert!(f.arg_list().is_some_and(ArgList::is_distinct));
+        assert_eq!((f.args().len(), f.exprs().len()), (2, 3));
+
+        let f: Function = utils::test::syntex!(count(*)).unwrap();
+        assert!(f.is_wildcard() && f.args().is_empty());
+    }
 }
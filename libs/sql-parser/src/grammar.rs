@@ -1,10 +1,18 @@
 use super::*;
 
 pub mod ast;
+mod case;
+mod cast;
 mod name;
+mod placeholder;
 mod term;
+mod type_name;
 mod value;
 
+pub use case::{Case, WhenClause};
+pub use cast::{Cast, CastSyntax};
 pub use name::*;
+pub use placeholder::Placeholder;
 pub use term::Term;
+pub use type_name::TypeName;
 pub use value::Value;
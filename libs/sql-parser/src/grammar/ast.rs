@@ -1,21 +1,98 @@
 use super::*;
+use proc_macro2::{Punct, Spacing};
+use syn::buffer::Cursor;
+use utils::{parse_keywords_if_matched, parse_kw_if_matched, peek_kw};
+
+/// An expression, built by precedence climbing over Postgres's operator precedence table.
+///
+/// Ref: https://www.postgresql.org/docs/current/sql-syntax-lexical.html#SQL-PRECEDENCE
+///
+/// | Operator                           | Associativity |
+/// | ---------------------------------- | ------------- |
+/// | `::`                               | left          |
+/// | unary `+` `-` `~`                  | right         |
+/// | `^`                                | left          |
+/// | `*` `/` `%`                        | left          |
+/// | `+` `-`                            | left          |
+/// | `\|\|` `~` `!~` `&` `\|` `#` `<<` `>>` | left      |
+/// | `BETWEEN` `IN` `LIKE` `ILIKE` `SIMILAR` |          |
+/// | `=` `<>` `<` `<=` `>` `>=`         |               |
+/// | `IS`                               |               |
+/// | `NOT`                              | right         |
+/// | `AND`                              | left          |
+/// | `OR`                               | left          |
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Expr {
+    Term(Term),
+    Unary {
+        op: UnaryOperator,
+        expr: Box<Expr>,
+    },
+    Binary {
+        left: Box<Expr>,
+        op: BinaryOperator,
+        right: Box<Expr>,
+    },
+    /// `<expr> [NOT] IN (..)`, `<expr> IS NULL` ..
+    Predicate {
+        expr: Box<Expr>,
+        predicate: Box<Predicate>,
+    },
+}
+
+/// Binding power of each precedence level, higher binds tighter.
+mod precedence {
+    pub const OR: u8 = 1;
+    pub const AND: u8 = 2;
+    pub const NOT: u8 = 3;
+    pub const IS: u8 = 4;
+    pub const COMPARISON: u8 = 5;
+    pub const PREDICATE: u8 = 6;
+    pub const OTHER: u8 = 7;
+    pub const ADDITIVE: u8 = 8;
+    pub const MULTIPLICATIVE: u8 = 9;
+    pub const EXPONENT: u8 = 10;
+    pub const UNARY: u8 = 11;
+}
 
-/// `||`
 #[derive(Debug)]
-pub struct ConcatOperator(Span);
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct UnaryOperator {
+    pub kind: UnaryOperatorKind,
+    #[cfg_attr(feature = "serde", serde(with = "crate::tokens"))]
+    pub span: Span,
+}
 
-impl GetSpan for ConcatOperator {
-    fn span(&self) -> Span {
-        self.0
-    }
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum UnaryOperatorKind {
+    /// `NOT`
+    Not,
+    /// `-`
+    Minus,
+    /// `+`
+    Plus,
+    /// `~`
+    BitwiseNot,
 }
 
 #[derive(Debug)]
-pub enum Compare {
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BinaryOperator {
+    pub kind: BinaryOperatorKind,
+    #[cfg_attr(feature = "serde", serde(with = "crate::tokens"))]
+    pub span: Span,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum BinaryOperatorKind {
+    Or,
+    And,
     /// `=`
     Equal,
-    /// `!=`
-    /// The operator `!=`` is the same as `<>`
+    /// `<>`
+    /// The operator `!=` is the same as `<>`
     NotEqual,
     /// `<`
     LessThan,
@@ -25,248 +102,546 @@ pub enum Compare {
     GreaterThan,
     /// `>=`
     GreaterThanOrEqual,
+    /// POSIX regular expression match: `~`, `~*`, `!~`, `!~*`
+    Match {
+        negated: bool,
+        case_insensitive: bool,
+    },
+    /// `||`
+    Concat,
+    /// `&`
+    BitwiseAnd,
+    /// `|`
+    BitwiseOr,
+    /// `#`
+    BitwiseXor,
+    /// `<<`
+    ShiftLeft,
+    /// `>>`
+    ShiftRight,
+    /// `+`
+    Plus,
+    /// `-`
+    Minus,
+    /// `*`
+    Multiply,
+    /// `/`
+    Divide,
+    /// `%`
+    Modulo,
+    /// `^`
+    Exponent,
 }
 
-#[derive(Debug)]
-pub enum RightHandSide {
-    Comparison(Compare, Operand),
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Predicate {
+    /// `[NOT] IN (<expr>, ..)`
+    In {
+        #[cfg_attr(feature = "serde", serde(with = "crate::tokens"))]
+        not_kw: Option<Ident>,
+        #[cfg_attr(feature = "serde", serde(with = "crate::tokens"))]
+        in_kw: Ident,
+        #[cfg_attr(feature = "serde", serde(with = "crate::tokens"))]
+        paren_token: Paren,
+        #[cfg_attr(feature = "serde", serde(with = "crate::tokens"))]
+        list: Punctuated<Expr, Token![,]>,
+    },
+    /// `[NOT] BETWEEN [SYMMETRIC] <low> AND <high>`
+    Between {
+        #[cfg_attr(feature = "serde", serde(with = "crate::tokens"))]
+        not_kw: Option<Ident>,
+        #[cfg_attr(feature = "serde", serde(with = "crate::tokens"))]
+        between_kw: Ident,
+        #[cfg_attr(feature = "serde", serde(with = "crate::tokens"))]
+        symmetric_kw: Option<Ident>,
+        low: Expr,
+        #[cfg_attr(feature = "serde", serde(with = "crate::tokens"))]
+        and_kw: Ident,
+        high: Expr,
+    },
+    /// `[NOT] { LIKE | ILIKE | SIMILAR TO } <pattern> [ESCAPE <escape>]`
+    Like {
+        #[cfg_attr(feature = "serde", serde(with = "crate::tokens"))]
+        not_kw: Option<Ident>,
+        kind: LikeKind,
+        #[cfg_attr(feature = "serde", serde(with = "crate::tokens"))]
+        kw: Ident,
+        pattern: Expr,
+        #[cfg_attr(feature = "serde", serde(with = "crate::tokens"))]
+        escape: Option<(Ident, Expr)>,
+    },
+    /// `IS [NOT] { NULL | TRUE | FALSE | UNKNOWN | DISTINCT FROM <expr> }`
+    Is {
+        #[cfg_attr(feature = "serde", serde(with = "crate::tokens"))]
+        is_kw: Ident,
+        #[cfg_attr(feature = "serde", serde(with = "crate::tokens"))]
+        not_kw: Option<Ident>,
+        predicate: IsPredicate,
+    },
 }
 
-impl Parse for RightHandSide {
-    fn parse(input: ParseStream) -> Result<Self> {
-        let compare: Compare = input.parse()?;
-        Ok(Self::Comparison(compare, input.parse()?))
-    }
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum LikeKind {
+    Like,
+    ILike,
+    SimilarTo,
 }
 
-pub enum Condition {
-    Operand {
-        left: Operand,
-        right: Option<RightHandSide>,
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum IsPredicate {
+    Null(#[cfg_attr(feature = "serde", serde(with = "crate::tokens"))] Ident),
+    True(#[cfg_attr(feature = "serde", serde(with = "crate::tokens"))] Ident),
+    False(#[cfg_attr(feature = "serde", serde(with = "crate::tokens"))] Ident),
+    Unknown(#[cfg_attr(feature = "serde", serde(with = "crate::tokens"))] Ident),
+    DistinctFrom {
+        #[cfg_attr(feature = "serde", serde(with = "crate::tokens"))]
+        kws: (Ident, Ident),
+        expr: Expr,
     },
-    Not(Box<Condition>),
 }
 
-macro_rules! ast {
-    ($($a: ident<$b: ident, $c: ident>)*) => {
-        pub mod ast_kind { $(#[derive(Debug, Default)] pub struct $a;)* }
-        $(pub type $a = Ast<ast_kind::$a, $b, $c>;)*
-    };
-}
-ast! {
-    Factorial<Term, Factor>
-    Arithmetic<Factorial, Sign>
-    Operand<Arithmetic, ConcatOperator>
-    AndExpr<Condition, AndOperator>
-    OrExpr<AndExpr, OrOperator>
-}
+impl BinaryOperatorKind {
+    pub fn precedence(self) -> u8 {
+        use BinaryOperatorKind::*;
+        match self {
+            Or => precedence::OR,
+            And => precedence::AND,
+            Equal | NotEqual | LessThan | LessThanOrEqual | GreaterThan | GreaterThanOrEqual => {
+                precedence::COMPARISON
+            }
+            Match { .. }
+            | Concat
+            | BitwiseAnd
+            | BitwiseOr
+            | BitwiseXor
+            | ShiftLeft
+            | ShiftRight => precedence::OTHER,
+            Plus | Minus => precedence::ADDITIVE,
+            Multiply | Divide | Modulo => precedence::MULTIPLICATIVE,
+            Exponent => precedence::EXPONENT,
+        }
+    }
 
-pub struct Ast<N, T, Operator> {
-    pub kind: N,
-    pub left: T,
-    pub right: Option<(Operator, Box<Self>)>,
+    pub fn is_comparison(self) -> bool {
+        self.precedence() == precedence::COMPARISON
+    }
 }
 
-// -------------------------------------------------------------------------------------
-
-macro_rules! parser {
-    (@Operator: $($name: ident = $val: literal)*) => {
-        $(
-            #[derive(Debug)]
-            pub struct $name(Ident);
-            impl Parse for $name {
-                fn parse(input: ParseStream) -> Result<Self> {
-                    let message = "invalid token";
-                    input.step(|c| {
-                        let (v, rest) = c.ident().ok_or(c.error(&message))?;
-                        if !v.to_string().eq_ignore_ascii_case($val) {
-                            return Err(c.error(message));
-                        }
-                        Ok((Self(v), rest))
-                    })
-                }
-            }
-            impl GetSpan for $name {
-                fn span(&self) -> Span {
-                    self.0.span()
+impl Expr {
+    /// Parses operators that bind at least as tightly as `min_precedence`,
+    /// binary operators are left associative except comparisons, which don't associate.
+    pub fn parse_with_precedence(input: ParseStream, min_precedence: u8) -> Result<Self> {
+        let mut left = Self::parse_prefix(input)?;
+        let mut compared = false;
+        loop {
+            if let Some(precedence) = Predicate::peek(input) {
+                if precedence < min_precedence {
+                    break;
                 }
+                left = Self::Predicate {
+                    expr: Box::new(left),
+                    predicate: Box::new(input.parse()?),
+                };
+                compared = false;
+                continue;
             }
-        )*
-    };
-    (@Symbol: $($name: ident { $($sym: literal => $kind: ident),* })*) => {
-        $(
-            #[derive(Debug)]
-            pub enum $name { $($kind(Span),)* }
-            impl Parse for $name {
-                fn parse(input: ParseStream) -> Result<Self> {
-                    let message = "invalid token";
-                    input.step(|c| {
-                        let (p1, rest) = c.punct().ok_or(c.error(message))?;
-                        let ret = match p1.as_char() {
-                            $($sym => Self::$kind(p1.span()),)*
-                            _ => return Err(Error::new(p1.span(), message)),
-                        };
-                        Ok((ret, rest))
-                    })
-                }
+            let fork = input.fork();
+            let Ok(op) = fork.parse::<BinaryOperator>() else {
+                break;
+            };
+            let precedence = op.kind.precedence();
+            if precedence < min_precedence {
+                break;
             }
-            impl GetSpan for $name {
-                fn span(&self) -> Span {
-                    match self {
-                        $(Self::$kind(s) => *s,)*
-                    }
-                }
+            // `a < b < c` is a syntax error rather than `(a < b) < c`.
+            if precedence == precedence::COMPARISON && compared {
+                return Err(Error::new(
+                    op.span,
+                    "comparison operators don't associate, use parentheses",
+                ));
             }
-        )*
-    };
-}
+            compared = precedence == precedence::COMPARISON;
+            input.advance_to(&fork);
+            left = Self::Binary {
+                left: Box::new(left),
+                op,
+                right: Box::new(Self::parse_with_precedence(input, precedence + 1)?),
+            };
+        }
+        Ok(left)
+    }
 
-parser!(@Operator:
-    OrOperator = "OR"
-    AndOperator = "AND"
-);
-parser!(@Symbol:
-    Factor {
-        '*' => Multiply,
-        '/' => Divide,
-        '%' => Modulo
+    /// An expression that stops before any predicate or comparison, so a following
+    /// `IN`, `AND` or `FROM` keyword is left to the caller.
+    pub fn parse_operand(input: ParseStream) -> Result<Self> {
+        Self::parse_with_precedence(input, precedence::OTHER)
     }
-    Sign {
-        '+' => Plus,
-        '-' => Minus
+
+    fn parse_prefix(input: ParseStream) -> Result<Self> {
+        if let Ok(kw) = parse_kw_if_matched(input, "NOT") {
+            return Ok(Self::Unary {
+                op: UnaryOperator {
+                    kind: UnaryOperatorKind::Not,
+                    span: kw.span(),
+                },
+                expr: Box::new(Self::parse_with_precedence(input, precedence::NOT)?),
+            });
+        }
+        let unary = input.step(|c| {
+            let Some((p, rest)) = c.punct() else {
+                return Err(c.error("expected unary operator"));
+            };
+            let kind = match p.as_char() {
+                '-' => UnaryOperatorKind::Minus,
+                '+' => UnaryOperatorKind::Plus,
+                '~' => UnaryOperatorKind::BitwiseNot,
+                _ => return Err(c.error("expected unary operator")),
+            };
+            Ok((
+                UnaryOperator {
+                    kind,
+                    span: p.span(),
+                },
+                rest,
+            ))
+        });
+        if let Ok(op) = unary {
+            return Ok(Self::Unary {
+                op,
+                expr: Box::new(Self::parse_with_precedence(input, precedence::UNARY)?),
+            });
+        }
+        input.parse().map(Self::Term)
     }
-);
+}
 
-impl Parse for ConcatOperator {
+impl Parse for Expr {
     fn parse(input: ParseStream) -> Result<Self> {
-        let message = "expected operator: `||`";
+        Self::parse_with_precedence(input, 0)
+    }
+}
+
+impl Parse for BinaryOperator {
+    fn parse(input: ParseStream) -> Result<Self> {
+        use BinaryOperatorKind::*;
+        if let Ok(kw) = parse_keywords_if_matched(input, &["AND", "OR"]) {
+            let kind = match kw.to_string().eq_ignore_ascii_case("AND") {
+                true => And,
+                false => Or,
+            };
+            return Ok(Self {
+                kind,
+                span: kw.span(),
+            });
+        }
+        let message = "expected operator";
         input.step(|c| {
             let (p1, rest) = c.punct().ok_or(c.error(message))?;
-            let (p2, rest) = rest.punct().ok_or(c.error(message))?;
-            match (p1.as_char(), p2.as_char()) {
-                ('|', '|') => Ok((Self(p1.span()), rest)),
-                _ => Err(Error::new(p1.span(), message)),
+            // The chars of an operator are written together, `a < > b` is not `<>`.
+            fn joint<'c>(p: &Punct, cursor: Cursor<'c>) -> Option<(Punct, Cursor<'c>)> {
+                match p.spacing() {
+                    Spacing::Joint => cursor.punct(),
+                    Spacing::Alone => None,
+                }
             }
+            let p2 = joint(&p1, rest);
+            let p3 = p2.as_ref().and_then(|(p2, rest)| joint(p2, *rest));
+            let (p2, p3) = (p2.map(|(p, _)| p.as_char()), p3.map(|(p, _)| p.as_char()));
+            // Multi-character operators, longest match first.
+            let (kind, len) = match (p1.as_char(), p2, p3) {
+                ('!', Some('~'), Some('*')) => (
+                    Match {
+                        negated: true,
+                        case_insensitive: true,
+                    },
+                    3,
+                ),
+                ('!', Some('~'), _) => (
+                    Match {
+                        negated: true,
+                        case_insensitive: false,
+                    },
+                    2,
+                ),
+                ('~', Some('*'), _) => (
+                    Match {
+                        negated: false,
+                        case_insensitive: true,
+                    },
+                    2,
+                ),
+                ('<', Some('='), _) => (LessThanOrEqual, 2),
+                ('>', Some('='), _) => (GreaterThanOrEqual, 2),
+                ('<', Some('>'), _) | ('!', Some('='), _) => (NotEqual, 2),
+                ('<', Some('<'), _) => (ShiftLeft, 2),
+                ('>', Some('>'), _) => (ShiftRight, 2),
+                ('|', Some('|'), _) => (Concat, 2),
+                ('~', ..) => (
+                    Match {
+                        negated: false,
+                        case_insensitive: false,
+                    },
+                    1,
+                ),
+                ('=', ..) => (Equal, 1),
+                ('<', ..) => (LessThan, 1),
+                ('>', ..) => (GreaterThan, 1),
+                ('&', ..) => (BitwiseAnd, 1),
+                ('|', ..) => (BitwiseOr, 1),
+                ('#', ..) => (BitwiseXor, 1),
+                ('+', ..) => (Plus, 1),
+                ('-', ..) => (Minus, 1),
+                ('*', ..) => (Multiply, 1),
+                ('/', ..) => (Divide, 1),
+                ('%', ..) => (Modulo, 1),
+                ('^', ..) => (Exponent, 1),
+                _ => return Err(Error::new(p1.span(), message)),
+            };
+            let mut rest = rest;
+            for _ in 1..len {
+                rest = rest.punct().unwrap().1;
+            }
+            let op = Self {
+                kind,
+                span: p1.span(),
+            };
+            Ok((op, rest))
         })
     }
 }
 
-impl Parse for Compare {
-    fn parse(input: ParseStream) -> Result<Self> {
-        input.step(|c| {
-            let (p1, rest) = c
-                .punct()
-                .ok_or(c.error("expected operator: `=`, `<`, `>`, `!=`, `<=`, `>=`"))?;
-
-            Ok(match rest.punct() {
-                None => match p1.as_char() {
-                    '=' => (Self::Equal, rest),
-                    '<' => (Self::LessThan, rest),
-                    '>' => (Self::GreaterThan, rest),
-                    ch => return Err(c.error(format!("invalid operator: {ch}"))),
-                },
-                Some((p2, rest)) => match (p1.as_char(), p2.as_char()) {
-                    ('<', '=') => (Self::LessThan, rest),
-                    ('>', '=') => (Self::GreaterThanOrEqual, rest),
-                    ('!', '=') | ('<', '>') => (Self::NotEqual, rest),
-                    (ch1, ch2) => return Err(c.error(format!("invalid operator: {ch1}{ch2}"))),
-                },
-            })
-        })
+impl Predicate {
+    /// Precedence of the predicate that starts at the next tokens, if any.
+    fn peek(input: ParseStream) -> Option<u8> {
+        if peek_kw(input, "IS") {
+            return Some(precedence::IS);
+        }
+        let fork = input.fork();
+        let _ = parse_kw_if_matched(&fork, "NOT");
+        ["IN", "BETWEEN", "LIKE", "ILIKE", "SIMILAR"]
+            .iter()
+            .any(|kw| peek_kw(&fork, kw))
+            .then_some(precedence::PREDICATE)
     }
 }
 
-impl Parse for Condition {
+impl Parse for Predicate {
     fn parse(input: ParseStream) -> Result<Self> {
-        enum Kind {
-            Not,
+        if let Ok(is_kw) = parse_kw_if_matched(input, "IS") {
+            return Ok(Self::Is {
+                is_kw,
+                not_kw: parse_kw_if_matched(input, "NOT").ok(),
+                predicate: input.parse()?,
+            });
         }
-        let kind = input.cursor().ident().and_then(|(s, _)| {
-            if s.to_string().eq_ignore_ascii_case("NOT") {
-                return Some(Kind::Not);
-            }
-            None
-        });
-        let this = match kind {
-            None => Self::Operand {
-                left: input.parse()?,
-                right: input.parse().ok(),
-            },
-            Some(kind) => {
-                input.parse::<Ident>()?;
-                match kind {
-                    Kind::Not => Self::Not(input.parse()?),
-                }
+        // Operands bind tighter than the predicate, so `AND` in `BETWEEN .. AND ..` isn't an operator.
+        let operand = Expr::parse_operand;
+        let not_kw = parse_kw_if_matched(input, "NOT").ok();
+        if let Ok(in_kw) = parse_kw_if_matched(input, "IN") {
+            let content;
+            return Ok(Self::In {
+                not_kw,
+                in_kw,
+                paren_token: parenthesized!(content in input),
+                list: content.call(Punctuated::parse_separated_nonempty)?,
+            });
+        }
+        if let Ok(between_kw) = parse_kw_if_matched(input, "BETWEEN") {
+            return Ok(Self::Between {
+                not_kw,
+                between_kw,
+                symmetric_kw: parse_kw_if_matched(input, "SYMMETRIC").ok(),
+                low: operand(input)?,
+                and_kw: parse_kw_if_matched(input, "AND")?,
+                high: operand(input)?,
+            });
+        }
+        let kw = parse_keywords_if_matched(input, &["LIKE", "ILIKE", "SIMILAR"])?;
+        let kind = match kw.to_string().to_uppercase().as_str() {
+            "LIKE" => LikeKind::Like,
+            "ILIKE" => LikeKind::ILike,
+            _ => {
+                parse_kw_if_matched(input, "TO")?;
+                LikeKind::SimilarTo
             }
         };
-        Ok(this)
+        Ok(Self::Like {
+            not_kw,
+            kind,
+            kw,
+            pattern: operand(input)?,
+            escape: match parse_kw_if_matched(input, "ESCAPE") {
+                Ok(kw) => Some((kw, operand(input)?)),
+                Err(_) => None,
+            },
+        })
     }
 }
 
-impl<N: Default, T: Parse, O: Parse> Parse for Ast<N, T, O> {
+impl Parse for IsPredicate {
     fn parse(input: ParseStream) -> Result<Self> {
-        let left = input.parse()?;
-        Ok(Self {
-            kind: N::default(),
-            left,
-            right: if let Ok(o) = O::parse(input) {
-                Some((o, input.parse()?))
-            } else {
-                None
-            },
+        if let Ok(distinct_kw) = parse_kw_if_matched(input, "DISTINCT") {
+            return Ok(Self::DistinctFrom {
+                kws: (distinct_kw, parse_kw_if_matched(input, "FROM")?),
+                expr: Expr::parse_with_precedence(input, precedence::IS + 1)?,
+            });
+        }
+        let kw = parse_keywords_if_matched(input, &["NULL", "TRUE", "FALSE", "UNKNOWN"]).map_err(
+            |_| input.error("expected `NULL`, `TRUE`, `FALSE`, `UNKNOWN` or `DISTINCT FROM`"),
+        )?;
+        Ok(match kw.to_string().to_uppercase().as_str() {
+            "NULL" => Self::Null(kw),
+            "TRUE" => Self::True(kw),
+            "FALSE" => Self::False(kw),
+            _ => Self::Unknown(kw),
         })
     }
 }
 
-impl fmt::Debug for Condition {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+impl GetSpan for Expr {
+    fn span(&self) -> Span {
         match self {
-            Self::Operand { left, right } => match right {
-                None => left.fmt(f),
-                Some(rhs) => f
-                    .debug_struct("Operand")
-                    .field("left", left)
-                    .field("right", rhs)
-                    .finish(),
-            },
-            Self::Not(arg0) => f.debug_tuple("Not").field(arg0).finish(),
+            Expr::Term(term) => term.span(),
+            Expr::Unary { op, .. } => op.span,
+            Expr::Binary { op, .. } => op.span,
+            Expr::Predicate { predicate, .. } => predicate.span(),
         }
     }
 }
 
-impl<N: fmt::Debug, T: fmt::Debug, Operator: fmt::Debug> fmt::Debug for Ast<N, T, Operator> {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        match &self.right {
-            None => self.left.fmt(f),
-            Some((operator, right)) => f
-                .debug_struct(&format!("{:?}", self.kind))
-                .field("left", &self.left)
-                .field("operator", &operator)
-                .field("right", &right)
-                .finish(),
+impl GetSpan for Predicate {
+    fn span(&self) -> Span {
+        match self {
+            Predicate::In { in_kw, .. } => in_kw.span(),
+            Predicate::Between { between_kw, .. } => between_kw.span(),
+            Predicate::Like { pattern, .. } => pattern.span(),
+            Predicate::Is { predicate, .. } => predicate.span(),
         }
     }
 }
 
-impl<N, T: GetSpan, Operator: GetSpan> GetSpan for Ast<N, T, Operator> {
+impl GetSpan for IsPredicate {
     fn span(&self) -> Span {
-        match &self.right {
-            Some((o, _)) => o.span(),
-            None => self.left.span(),
+        match self {
+            IsPredicate::Null(kw)
+            | IsPredicate::True(kw)
+            | IsPredicate::False(kw)
+            | IsPredicate::Unknown(kw) => kw.span(),
+            IsPredicate::DistinctFrom { expr, .. } => expr.span(),
         }
     }
 }
 
-impl GetSpan for Condition {
+impl GetSpan for BinaryOperator {
     fn span(&self) -> Span {
-        match self {
-            Condition::Operand { left, right } => match right {
-                Some(rhs) => match rhs {
-                    RightHandSide::Comparison(_, right) => right.span(),
-                },
-                None => left.span(),
+        self.span
+    }
+}
+
+impl fmt::Display for BinaryOperatorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use BinaryOperatorKind::*;
+        f.write_str(match self {
+            Or => "OR",
+            And => "AND",
+            Equal => "=",
+            NotEqual => "<>",
+            LessThan => "<",
+            LessThanOrEqual => "<=",
+            GreaterThan => ">",
+            GreaterThanOrEqual => ">=",
+            Match {
+                negated,
+                case_insensitive,
+            } => match (negated, case_insensitive) {
+                (false, false) => "~",
+                (false, true) => "~*",
+                (true, false) => "!~",
+                (true, true) => "!~*",
             },
-            Condition::Not(me) => me.span(),
+            Concat => "||",
+            BitwiseAnd => "&",
+            BitwiseOr => "|",
+            BitwiseXor => "#",
+            ShiftLeft => "<<",
+            ShiftRight => ">>",
+            Plus => "+",
+            Minus => "-",
+            Multiply => "*",
+            Divide => "/",
+            Modulo => "%",
+            Exponent => "^",
+        })
+    }
+}
+
+impl fmt::Debug for Expr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Term(term) => term.fmt(f),
+            Self::Unary { op, expr } => f
+                .debug_struct("Unary")
+                .field("op", &op.kind)
+                .field("expr", expr)
+                .finish(),
+            Self::Binary { left, op, right } => f
+                .debug_struct("Binary")
+                .field("left", left)
+                .field("op", &op.kind)
+                .field("right", right)
+                .finish(),
+            Self::Predicate { expr, predicate } => f
+                .debug_struct("Predicate")
+                .field("expr", expr)
+                .field("predicate", predicate)
+                .finish(),
+        }
+    }
+}
+
+impl fmt::Debug for Predicate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::In { not_kw, list, .. } => f
+                .debug_struct("In")
+                .field("not", &not_kw.is_some())
+                .field("list", &list.iter().collect::<Vec<_>>())
+                .finish(),
+            Self::Like {
+                not_kw,
+                kind,
+                pattern,
+                escape,
+                ..
+            } => f
+                .debug_struct("Like")
+                .field("not", &not_kw.is_some())
+                .field("kind", kind)
+                .field("pattern", pattern)
+                .field("escape", &escape.as_ref().map(|(_, escape)| escape))
+                .finish(),
+            Self::Between {
+                not_kw,
+                symmetric_kw,
+                low,
+                high,
+                ..
+            } => f
+                .debug_struct("Between")
+                .field("not", &not_kw.is_some())
+                .field("symmetric", &symmetric_kw.is_some())
+                .field("low", low)
+                .field("high", high)
+                .finish(),
+            Self::Is {
+                not_kw, predicate, ..
+            } => f
+                .debug_struct("Is")
+                .field("not", &not_kw.is_some())
+                .field("predicate", predicate)
+                .finish(),
         }
     }
 }
@@ -275,12 +650,94 @@ impl GetSpan for Condition {
 mod tests {
     use super::*;
 
+    /// Renders the tree with explicit parentheses, e.g. `a - b - c` is `((a - b) - c)`.
+    fn tree(expr: &Expr) -> String {
+        match expr {
+            Expr::Term(Term::Column(column)) => column.alias.to_string(),
+            Expr::Term(Term::Value(value)) => format!("{value:?}").trim_matches('"').to_owned(),
+            Expr::Term(term) => format!("{term:?}"),
+            Expr::Unary { op, expr } => format!("({:?} {})", op.kind, tree(expr)),
+            Expr::Binary { left, op, right } => {
+                format!("({} {} {})", tree(left), op.kind, tree(right))
+            }
+            Expr::Predicate { expr, predicate } => {
+                let name = format!("{predicate:?}");
+                let name = name.split([' ', '{']).next().unwrap().to_owned();
+                format!("({} {name})", tree(expr))
+            }
+        }
+    }
+
+    fn parse(tokens: &str) -> String {
+        let expr: Expr = syn::parse_str(tokens).unwrap();
+        tree(&expr)
+    }
+
     #[test]
     fn test_name() {
-        let g: OrExpr = utils::test::syntex! {
+        let g: Expr = utils::test::syntex! {
             a and 54 or 4 + 4
         }
         .unwrap();
         println!("{:#?}", g);
     }
+
+    #[test]
+    fn precedence_and_associativity() {
+        assert_eq!(parse("a - b - c"), "((a - b) - c)");
+        assert_eq!(parse("10 / 2 / 5"), "((10 / 2) / 5)");
+        assert_eq!(parse("a + b * c ^ d"), "(a + (b * (c ^ d)))");
+        assert_eq!(parse("a ^ b ^ c"), "((a ^ b) ^ c)");
+        assert_eq!(parse("-a ^ 2"), "((Minus a) ^ 2)");
+        assert_eq!(parse("a - -b"), "(a - (Minus b))");
+        assert_eq!(parse("a || b + c"), "(a || (b + c))");
+        assert_eq!(parse("a & b << 1 # c"), "(((a & b) << 1) # c)");
+        assert_eq!(
+            parse("a + 1 = b OR c AND NOT d"),
+            "(((a + 1) = b) OR (c AND (Not d)))"
+        );
+        assert_eq!(parse("NOT a = b"), "(Not (a = b))");
+        assert_eq!(parse("a = b IS NULL"), "((a = b) Is)");
+        assert_eq!(parse("a <= b AND c >= d"), "((a <= b) AND (c >= d))");
+        assert_eq!(parse("a < -1"), "(a < (Minus 1))");
+        assert_eq!(parse("a !~* b || c"), "((a !~* b) || c)");
+        assert!(syn::parse_str::<Expr>("(a < b) = c").is_ok());
+        let err = syn::parse_str::<Expr>("1 < 2 < 3").err().unwrap();
+        assert_eq!(
+            err.to_string(),
+            "comparison operators don't associate, use parentheses"
+        );
+        assert!(syn::parse_str::<Expr>("a = b <> c").is_err());
+    }
+
+    #[test]
+    fn operator_spacing() {
+        assert_eq!(parse("a <> b"), "(a <> b)");
+        assert_eq!(parse("a<=b"), "(a <= b)");
+        assert!(syn::parse_str::<Expr>("a < > b").is_err());
+        assert!(syn::parse_str::<Expr>("a < = b").is_err());
+        assert!(syn::parse_str::<Expr>("a | | b").is_err());
+        assert!(syn::parse_str::<Expr>("a ! ~ b").is_err());
+    }
+
+    #[test]
+    fn predicates() {
+        assert_eq!(parse("a NOT IN (1, 2, 3)"), "(a In)");
+        assert!(syn::parse_str::<Expr>("a IN ()").is_err());
+        assert!(syn::parse_str::<Expr>("a IN (1, 2,)").is_err());
+        assert_eq!(
+            parse("a BETWEEN SYMMETRIC 1 AND b + 1 AND c"),
+            "((a Between) AND c)"
+        );
+        assert_eq!(
+            parse("a IS NOT NULL OR b IS DISTINCT FROM c"),
+            "((a Is) OR (b Is))"
+        );
+        assert_eq!(parse(r#"a NOT ILIKE "%x!%" ESCAPE "!""#), "(a Like)");
+        assert_eq!(parse(r#"a SIMILAR TO "(b|c)%" AND d"#), "((a Like) AND d)");
+
+        let g: Expr = syn::parse_str("a IS UNKNOWN").unwrap();
+        assert!(format!("{g:?}").contains("Unknown"));
+        assert!(syn::parse_str::<Expr>("a IS 1").is_err());
+    }
 }
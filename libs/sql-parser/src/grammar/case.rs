@@ -0,0 +1,141 @@
+use super::ast::Expr;
+use crate::*;
+use utils::{parse_kw_if_matched, peek_kw};
+
+/// Ref: https://www.postgresql.org/docs/current/functions-conditional.html#FUNCTIONS-CASE
+///
+/// ```text
+/// CASE [<operand>]
+///     WHEN <condition> THEN <result>
+///     [WHEN ..]
+///     [ELSE <result>]
+/// END
+/// ```
+///
+/// With an operand (simple form) each `WHEN` value is compared to the operand,
+/// otherwise (searched form) each `WHEN` condition must be boolean.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Case {
+    #[cfg_attr(feature = "serde", serde(with = "crate::tokens"))]
+    pub case_kw: Ident,
+    pub operand: Option<Expr>,
+    pub when_clauses: Vec<WhenClause>,
+    #[cfg_attr(feature = "serde", serde(with = "crate::tokens"))]
+    pub else_clause: Option<(Ident, Expr)>,
+    #[cfg_attr(feature = "serde", serde(with = "crate::tokens"))]
+    pub end_kw: Ident,
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct WhenClause {
+    #[cfg_attr(feature = "serde", serde(with = "crate::tokens"))]
+    pub when_kw: Ident,
+    pub condition: Expr,
+    #[cfg_attr(feature = "serde", serde(with = "crate::tokens"))]
+    pub then_kw: Ident,
+    pub result: Expr,
+}
+
+impl Case {
+    pub fn peek(input: ParseStream) -> bool {
+        peek_kw(input, "CASE")
+    }
+
+    pub fn results(&self) -> impl Iterator<Item = &Expr> {
+        self.when_clauses
+            .iter()
+            .map(|clause| &clause.result)
+            .chain(self.else_clause.as_ref().map(|(_, result)| result))
+    }
+}
+
+impl Parse for Case {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let case_kw = parse_kw_if_matched(input, "CASE")?;
+        let operand = match peek_kw(input, "WHEN") {
+            true => None,
+            false => Some(input.parse()?),
+        };
+        let mut when_clauses = vec![];
+        while peek_kw(input, "WHEN") {
+            when_clauses.push(input.parse()?);
+        }
+        if when_clauses.is_empty() {
+            return Err(input.error("expected keyword: `WHEN`"));
+        }
+        Ok(Self {
+            case_kw,
+            operand,
+            when_clauses,
+            else_clause: match parse_kw_if_matched(input, "ELSE") {
+                Ok(kw) => Some((kw, input.parse()?)),
+                Err(_) => None,
+            },
+            end_kw: parse_kw_if_matched(input, "END")?,
+        })
+    }
+}
+
+impl Parse for WhenClause {
+    fn parse(input: ParseStream) -> Result<Self> {
+        Ok(Self {
+            when_kw: parse_kw_if_matched(input, "WHEN")?,
+            condition: input.parse()?,
+            then_kw: parse_kw_if_matched(input, "THEN")?,
+            result: input.parse()?,
+        })
+    }
+}
+
+impl GetSpan for Case {
+    fn span(&self) -> Span {
+        self.case_kw.span()
+    }
+}
+
+impl fmt::Debug for Case {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Case")
+            .field("operand", &self.operand)
+            .field("when_clauses", &self.when_clauses)
+            .field("else", &self.else_clause.as_ref().map(|(_, result)| result))
+            .finish()
+    }
+}
+
+impl fmt::Debug for WhenClause {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("When")
+            .field("condition", &self.condition)
+            .field("result", &self.result)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn simple_and_searched() {
+        let g: Case = utils::test::syntex! {
+            CASE WHEN a > 1 AND b THEN "x" WHEN c IS NULL THEN "y" ELSE "z" END
+        }
+        .unwrap();
+        assert!(g.operand.is_none());
+        assert_eq!(g.when_clauses.len(), 2);
+        assert_eq!(g.results().count(), 3);
+
+        let g: Case = utils::test::syntex! {
+            CASE a + 1 WHEN 1 THEN "one" WHEN 2 THEN "two" END
+        }
+        .unwrap();
+        assert!(g.operand.is_some());
+        assert!(g.else_clause.is_none());
+
+        assert!(utils::test::syntex!(CASE a END).map(|_: Case| ()).is_err());
+        assert!(utils::test::syntex!(CASE WHEN a THEN b)
+            .map(|_: Case| ())
+            .is_err());
+    }
+}
@@ -0,0 +1,141 @@
+use super::{Term, TypeName};
+use crate::*;
+use utils::parse_kw_if_matched;
+
+/// Ref: https://www.postgresql.org/docs/current/sql-expressions.html#SQL-SYNTAX-TYPE-CASTS
+///
+/// ```text
+/// CAST(<expr> AS <type>)
+/// <term>::<type>
+/// <type> <string>
+/// ```
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Cast {
+    pub syntax: CastSyntax,
+    pub expr: Term,
+    pub type_name: TypeName,
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CastSyntax {
+    /// `CAST(<expr> AS <type>)`
+    Function {
+        #[cfg_attr(feature = "serde", serde(with = "crate::tokens"))]
+        cast_kw: Ident,
+        #[cfg_attr(feature = "serde", serde(with = "crate::tokens"))]
+        paren_token: Paren,
+        #[cfg_attr(feature = "serde", serde(with = "crate::tokens"))]
+        as_kw: Ident,
+    },
+    /// `<term>::<type>`
+    DoubleColon(#[cfg_attr(feature = "serde", serde(with = "crate::tokens"))] Token![::]),
+    /// `<type> <string>`, e.g. `DATE "2024-01-01"`
+    TypedLiteral,
+}
+
+impl Cast {
+    pub fn peek_function(input: ParseStream) -> bool {
+        utils::peek_kw(input, "CAST") && input.peek2(Paren)
+    }
+
+    pub fn parse_function(input: ParseStream) -> Result<Self> {
+        let cast_kw = parse_kw_if_matched(input, "CAST")?;
+        let content;
+        let paren_token = parenthesized!(content in input);
+        let expr = Term::Expr(content.parse()?);
+        let as_kw = parse_kw_if_matched(&content, "AS")?;
+        let type_name = content.parse()?;
+        if !content.is_empty() {
+            return Err(content.error("expected `)`"));
+        }
+        Ok(Self {
+            syntax: CastSyntax::Function {
+                cast_kw,
+                paren_token,
+                as_kw,
+            },
+            expr,
+            type_name,
+        })
+    }
+
+    /// Wraps `expr` with every trailing `::<type>`, `a::text::int` is `(a::text)::int`.
+    pub fn parse_postfix(input: ParseStream, mut expr: Term) -> Result<Term> {
+        while input.peek(Token![::]) {
+            let colons = input.parse()?;
+            expr = Term::Cast(Box::new(Self {
+                syntax: CastSyntax::DoubleColon(colons),
+                expr,
+                type_name: input.parse()?,
+            }));
+        }
+        Ok(expr)
+    }
+
+    /// `<type> <string>`, returns `None` without consuming anything if the input isn't a typed literal.
+    pub fn parse_typed_literal(input: ParseStream) -> Option<Self> {
+        let fork = input.fork();
+        let type_name = fork.parse::<TypeName>().ok()?;
+        let is_string = fork
+            .cursor()
+            .literal()
+            .is_some_and(|(lit, _)| lit.to_string().starts_with('"'));
+        if type_name.array_dims > 0 || !is_string {
+            return None;
+        }
+        let expr = Term::Value(fork.parse().ok()?);
+        input.advance_to(&fork);
+        Some(Self {
+            syntax: CastSyntax::TypedLiteral,
+            expr,
+            type_name,
+        })
+    }
+}
+
+impl GetSpan for Cast {
+    fn span(&self) -> Span {
+        match &self.syntax {
+            CastSyntax::Function { cast_kw, .. } => cast_kw.span(),
+            CastSyntax::DoubleColon(colons) => colons.spans[0],
+            CastSyntax::TypedLiteral => self.type_name.span(),
+        }
+    }
+}
+
+impl fmt::Debug for Cast {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Cast")
+            .field("expr", &self.expr)
+            .field("type_name", &self.type_name)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn casts() {
+        let cast = |t: Term| match t {
+            Term::Cast(cast) => cast,
+            t => panic!("expected cast, found {t:?}"),
+        };
+        let g = cast(utils::test::syntex!(CAST(a + 1 AS numeric(10, 2))).unwrap());
+        assert!(matches!(g.syntax, CastSyntax::Function { .. }));
+        assert_eq!(g.type_name.name(), "numeric");
+
+        let g = cast(utils::test::syntex!(a::text::int[]).unwrap());
+        assert_eq!(g.type_name.array_dims, 1);
+        assert!(matches!(g.expr, Term::Cast(_)));
+
+        let g = cast(utils::test::syntex!(TIMESTAMP WITH TIME ZONE "2024-01-01 00:00+00").unwrap());
+        assert!(matches!(g.syntax, CastSyntax::TypedLiteral));
+        assert_eq!(g.type_name.name(), "timestamp with time zone");
+
+        assert!(utils::test::syntex!(CAST(a, int))
+            .map(|_: Term| ())
+            .is_err());
+    }
+}
@@ -1,29 +1,76 @@
+use crate::dialect::Dialect;
 use crate::*;
 use std::fmt;
 use std::fmt::Display;
 use std::fmt::Write;
 
 #[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Name {
     /// Unquoted names are not case sensitive. There is no maximum name length
-    Ident(Ident),
+    Ident(#[cfg_attr(feature = "serde", serde(with = "crate::tokens"))] Ident),
     /// Quoted names are case sensitive. and can contain spaces. There is no maximum name length.
     /// Two double quotes can be used to create a single double quote inside an identifier.
-    String(Literal),
+    String(#[cfg_attr(feature = "serde", serde(with = "crate::tokens"))] Literal),
 }
 
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(bound = "T: crate::tokens::Tokens"))]
 pub struct Column<T> {
     pub schema_name: Option<Name>,
     pub table_name: Option<Name>,
+    #[cfg_attr(feature = "serde", serde(with = "crate::tokens"))]
     pub alias: T,
 }
 
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct TableName {
     pub schema_name: Option<Name>,
     pub alias: Name,
 }
 
+/// `<table_name> [[AS] alias]`
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TableRef {
+    pub table_name: TableName,
+    pub alias: Option<Name>,
+}
+
+impl TableRef {
+    /// The name used to refer to this table within the query.
+    pub fn visible_name(&self) -> &Name {
+        self.alias.as_ref().unwrap_or(&self.table_name.alias)
+    }
+}
+
+impl Name {
+    /// Quoted names keep their case, and may be reserved keywords.
+    pub fn is_quoted(&self) -> bool {
+        matches!(self, Name::String(_))
+    }
+
+    /// The name as written, without its quotes.
+    pub fn unquoted(&self) -> String {
+        match self {
+            Name::Ident(ident) => ident.unraw().to_string(),
+            Name::String(lit) => match syn::Lit::new(lit.clone()) {
+                syn::Lit::Str(s) => s.value().replace("\"\"", "\""),
+                _ => lit.to_string(),
+            },
+        }
+    }
+
+    /// The name as the database stores it, unquoted names are folded to the case of `dialect`.
+    pub fn value(&self, dialect: &dyn Dialect) -> String {
+        match self {
+            Name::Ident(_) => dialect.fold_identifier(&self.unquoted()),
+            Name::String(_) => self.unquoted(),
+        }
+    }
+}
+
 impl Parse for Name {
     fn parse(input: ParseStream) -> Result<Self> {
         let err_msg = "expected `Name`";
@@ -32,14 +79,17 @@ impl Parse for Name {
             let name = match tt {
                 TokenTree::Ident(v) => Name::Ident(v),
                 TokenTree::Literal(v) => {
-                    if !v
-                        .to_string()
-                        .as_bytes()
-                        .first()
-                        .is_some_and(|ch| matches!(ch, b'r' | b'"'))
-                    {
+                    let repr = v.to_string();
+                    if !repr.starts_with(['r', '"']) {
                         return Err(Error::new(v.span(), "invalid `Name`"));
                     }
+                    // A string constant of the lexer, SQL quotes names with `"` only.
+                    if repr.ends_with(lexer::STRING_SUFFIX) {
+                        return Err(Error::new(
+                            v.span(),
+                            "expected `Name`, found a string constant",
+                        ));
+                    }
                     Name::String(v)
                 }
                 tt => return Err(Error::new(tt.span(), err_msg)),
@@ -78,7 +128,21 @@ impl Parse for TableName {
     }
 }
 
-fn get_name(input: ParseStream) -> Result<Option<Name>> {
+impl Parse for TableRef {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let table_name = input.parse()?;
+        let alias = if utils::parse_kw_if_matched(input, "AS").is_ok() {
+            Some(input.parse()?)
+        } else if !input.is_empty() && !input.peek(Token![,]) && !utils::is_reserved_kw(input) {
+            input.parse().ok()
+        } else {
+            None
+        };
+        Ok(Self { table_name, alias })
+    }
+}
+
+pub(crate) fn get_name(input: ParseStream) -> Result<Option<Name>> {
     if !input.peek2(Token![.]) {
         return Ok(None);
     }
@@ -131,6 +195,12 @@ impl<T: GetSpan> GetSpan for Column<T> {
     }
 }
 
+impl GetSpan for TableRef {
+    fn span(&self) -> Span {
+        self.table_name.span()
+    }
+}
+
 impl GetSpan for TableName {
     fn span(&self) -> Span {
         self.alias.span()
@@ -147,4 +217,19 @@ mod tests {
         let _: Column<Name> = syntex! { field }.unwrap();
         let _: Column<Name> = syntex! { "document".field }.unwrap();
     }
+
+    #[test]
+    fn value() {
+        use crate::dialect::{MySql, Postgres, H2};
+        let value = |name: &str, dialect: &dyn Dialect| {
+            syn::parse_str::<Name>(name).unwrap().value(dialect)
+        };
+        assert_eq!(value("User", &Postgres), "user");
+        assert_eq!(value("User", &MySql), "User");
+        assert_eq!(value("User", &H2), "USER");
+        assert_eq!(value("r#type", &Postgres), "type");
+        assert_eq!(value(r#"r"User""#, &Postgres), "User");
+        assert_eq!(value(r#""User""#, &H2), "User");
+        assert_eq!(value(r##"r#"a""b"#"##, &Postgres), "a\"b");
+    }
 }
@@ -0,0 +1,60 @@
+use crate::*;
+
+/// A bind parameter, its value is supplied when the query is executed.
+/// Which form is accepted depends on the dialect, see [`crate::dialect::PlaceholderStyle`].
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Placeholder {
+    /// `$<n>`, numbered from 1, the same parameter may be used more than once.
+    Numbered {
+        #[cfg_attr(feature = "serde", serde(with = "crate::tokens"))]
+        dollar_token: Token![$],
+        #[cfg_attr(feature = "serde", serde(with = "crate::tokens"))]
+        number: LitInt,
+    },
+    /// `?`, parameters are numbered by their position in the query.
+    Anonymous(#[cfg_attr(feature = "serde", serde(with = "crate::tokens"))] Token![?]),
+}
+
+impl Placeholder {
+    pub fn peek(input: ParseStream) -> bool {
+        (input.peek(Token![$]) && input.peek2(LitInt)) || input.peek(Token![?])
+    }
+}
+
+impl Parse for Placeholder {
+    fn parse(input: ParseStream) -> Result<Self> {
+        if input.peek(Token![?]) {
+            return input.parse().map(Self::Anonymous);
+        }
+        let dollar_token = input.parse()?;
+        let number: LitInt = input.parse()?;
+        if !number.suffix().is_empty() || number.base10_parse::<u32>().is_ok_and(|n| n == 0) {
+            return Err(Error::new(
+                number.span(),
+                format!("there is no parameter ${number}"),
+            ));
+        }
+        Ok(Self::Numbered {
+            dollar_token,
+            number,
+        })
+    }
+}
+
+impl GetSpan for Placeholder {
+    fn span(&self) -> Span {
+        match self {
+            Placeholder::Numbered { dollar_token, .. } => dollar_token.span,
+            Placeholder::Anonymous(token) => token.span,
+        }
+    }
+}
+
+impl fmt::Debug for Placeholder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Numbered { number, .. } => write!(f, "${number}"),
+            Self::Anonymous(_) => f.write_str("?"),
+        }
+    }
+}
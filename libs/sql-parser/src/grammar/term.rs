@@ -1,22 +1,47 @@
-use function::Function;
+use function::FunctionCall;
 use syn::token;
 
-use super::{ast::OrExpr, value::Value, Column, Name};
+use super::{ast::Expr, value::Value, Case, Cast, Column, Name, Placeholder};
 use crate::*;
 
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Term {
     Value(Value),
     Column(Column<Name>),
-    Func(Box<Function>),
-    OrExpr(Box<OrExpr>),
+    Func(Box<FunctionCall>),
+    Expr(Box<Expr>),
+    Case(Box<Case>),
+    Cast(Box<Cast>),
+    Placeholder(Placeholder),
 }
 
 impl Parse for Term {
     fn parse(input: ParseStream) -> Result<Self> {
+        let term = Self::parse_primary(input)?;
+        Cast::parse_postfix(input, term)
+    }
+}
+
+impl Term {
+    fn parse_primary(input: ParseStream) -> Result<Self> {
         if input.peek(token::Paren) {
             let content;
             parenthesized!(content in input);
-            return Ok(Self::OrExpr(content.parse()?));
+            return Ok(Self::Expr(content.parse()?));
+        }
+        if Case::peek(input) {
+            return input.parse().map(Self::Case);
+        }
+        if Placeholder::peek(input) {
+            return input.parse().map(Self::Placeholder);
+        }
+        if Cast::peek_function(input) {
+            return Cast::parse_function(input).map(|cast| Self::Cast(Box::new(cast)));
+        }
+        if input.peek(Ident::peek_any) {
+            if let Some(cast) = Cast::parse_typed_literal(input) {
+                return Ok(Self::Cast(Box::new(cast)));
+            }
         }
         if input.peek(Ident::peek_any) && input.peek2(token::Paren) {
             let func = Self::Func(input.parse()?);
@@ -26,7 +51,7 @@ impl Parse for Term {
         if input
             .cursor()
             .literal()
-            .is_some_and(|(v, _)| v.to_string().starts_with("r\""))
+            .is_some_and(|(v, _)| v.to_string().starts_with('r'))
         {
             return Ok(Term::Column(input.parse()?));
         }
@@ -48,7 +73,10 @@ impl GetSpan for Term {
             Term::Value(v) => v.span(),
             Term::Column(v) => v.span(),
             Term::Func(v) => v.span(),
-            Term::OrExpr(v) => GetSpan::span(v),
+            Term::Expr(v) => GetSpan::span(v),
+            Term::Case(v) => v.span(),
+            Term::Cast(v) => v.span(),
+            Term::Placeholder(v) => v.span(),
         }
     }
 }
@@ -58,8 +86,14 @@ impl fmt::Debug for Term {
         match self {
             Self::Value(arg0) => f.debug_tuple("Value").field(arg0).finish(),
             Self::Column(arg0) => f.debug_tuple("Column").field(arg0).finish(),
-            Self::Func(_) => f.debug_tuple("Func").finish(),
-            Self::OrExpr(arg0) => f.debug_tuple("OrExpr").field(arg0).finish(),
+            Self::Func(arg0) => match &arg0.over {
+                Some(over) => f.debug_tuple("Func").field(over).finish(),
+                None => f.debug_tuple("Func").finish(),
+            },
+            Self::Expr(arg0) => f.debug_tuple("Expr").field(arg0).finish(),
+            Self::Case(arg0) => arg0.fmt(f),
+            Self::Cast(arg0) => arg0.fmt(f),
+            Self::Placeholder(arg0) => arg0.fmt(f),
         }
     }
 }
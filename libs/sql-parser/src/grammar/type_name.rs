@@ -0,0 +1,170 @@
+use super::{name::get_name, Name};
+use crate::*;
+use syn::{bracketed, token::Bracket};
+use utils::{parse_keywords_if_matched, parse_kw_if_matched, peek_kw};
+
+/// Ref: https://www.postgresql.org/docs/current/datatype.html
+///
+/// ```text
+/// [<schema_name>.]<name> [(<modifier>, ..)] [WITH | WITHOUT TIME ZONE] [[<n>] ..| ARRAY [[<n>]]]
+/// ```
+///
+/// e.g. `varchar(255)`, `numeric(10, 2)`, `double precision`, `int[]`,
+/// `timestamp(3) with time zone`, `my_schema.mood`
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TypeName {
+    pub schema_name: Option<Name>,
+    pub name: Name,
+    /// Remaining words of multi-word names, e.g. `PRECISION` in `DOUBLE PRECISION`.
+    #[cfg_attr(feature = "serde", serde(with = "crate::tokens"))]
+    pub name_suffix: Vec<Ident>,
+    /// `(n)` or `(p, s)`
+    #[cfg_attr(feature = "serde", serde(with = "crate::tokens"))]
+    pub modifiers: Punctuated<LitInt, Token![,]>,
+    /// Number of array dimensions, `int[][]` has 2.
+    pub array_dims: usize,
+}
+
+impl TypeName {
+    /// Lowercase name with words separated by a single space, e.g. `"timestamp with time zone"`.
+    /// Quoted names are kept as they are.
+    pub fn name(&self) -> String {
+        let mut name = match &self.name {
+            Name::Ident(ident) => ident.to_string().to_lowercase(),
+            Name::String(_) => self.name.to_string(),
+        };
+        for word in &self.name_suffix {
+            name.push(' ');
+            name += &word.to_string().to_lowercase();
+        }
+        name
+    }
+}
+
+/// The second word of a multi-word type name, if `first` starts one.
+fn suffix_words(first: &Name) -> &'static [&'static str] {
+    let Name::Ident(first) = first else {
+        return &[];
+    };
+    match first.to_string().to_uppercase().as_str() {
+        "DOUBLE" => &["PRECISION"],
+        "CHARACTER" | "CHAR" | "BIT" => &["VARYING"],
+        _ => &[],
+    }
+}
+
+fn is_time(name: &Name) -> bool {
+    matches!(name, Name::Ident(ident) if ["TIME", "TIMESTAMP"].iter().any(|kw| ident.to_string().eq_ignore_ascii_case(kw)))
+}
+
+impl Parse for TypeName {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let schema_name = get_name(input)?;
+        let name = input.parse()?;
+        let mut name_suffix = vec![];
+        if let Ok(word) = parse_keywords_if_matched(input, suffix_words(&name)) {
+            name_suffix.push(word);
+        }
+        let modifiers = match input.peek(Paren) {
+            false => Punctuated::new(),
+            true => {
+                let content;
+                parenthesized!(content in input);
+                content.call(Punctuated::parse_terminated)?
+            }
+        };
+        if is_time(&name) && (peek_kw(input, "WITH") || peek_kw(input, "WITHOUT")) {
+            name_suffix.push(parse_keywords_if_matched(input, &["WITH", "WITHOUT"])?);
+            name_suffix.push(parse_kw_if_matched(input, "TIME")?);
+            name_suffix.push(parse_kw_if_matched(input, "ZONE")?);
+        }
+        let mut array_dims = 0;
+        if parse_kw_if_matched(input, "ARRAY").is_ok() {
+            array_dims = 1;
+            if input.peek(Bracket) {
+                parse_array_bound(input)?;
+            }
+        } else {
+            while input.peek(Bracket) {
+                parse_array_bound(input)?;
+                array_dims += 1;
+            }
+        }
+        Ok(Self {
+            schema_name,
+            name,
+            name_suffix,
+            modifiers,
+            array_dims,
+        })
+    }
+}
+
+/// `[]` or `[<n>]`, Postgres ignores the bound.
+fn parse_array_bound(input: ParseStream) -> Result<()> {
+    let content;
+    bracketed!(content in input);
+    if !content.is_empty() {
+        content.parse::<LitInt>()?;
+    }
+    Ok(())
+}
+
+impl GetSpan for TypeName {
+    fn span(&self) -> Span {
+        self.name.span()
+    }
+}
+
+impl fmt::Debug for TypeName {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(schema_name) = &self.schema_name {
+            write!(f, "{schema_name}.")?;
+        }
+        f.write_str(&self.name())?;
+        if !self.modifiers.is_empty() {
+            let modifiers: Vec<_> = self.modifiers.iter().map(|m| m.to_string()).collect();
+            write!(f, "({})", modifiers.join(", "))?;
+        }
+        for _ in 0..self.array_dims {
+            f.write_str("[]")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn type_names() {
+        let debug = |t: TypeName| format!("{t:?}");
+        assert_eq!(
+            debug(utils::test::syntex!(VARCHAR(255)).unwrap()),
+            "varchar(255)"
+        );
+        assert_eq!(
+            debug(utils::test::syntex!(numeric(10, 2)).unwrap()),
+            "numeric(10, 2)"
+        );
+        assert_eq!(
+            debug(utils::test::syntex!(Double Precision[]).unwrap()),
+            "double precision[]"
+        );
+        assert_eq!(
+            debug(utils::test::syntex!(character varying(3) ARRAY[4]).unwrap()),
+            "character varying(3)[]"
+        );
+        assert_eq!(
+            debug(utils::test::syntex!(timestamp(3) WITH TIME ZONE).unwrap()),
+            "timestamp with time zone(3)"
+        );
+        assert_eq!(debug(utils::test::syntex!(int[3][]).unwrap()), "int[][]");
+        assert_eq!(
+            debug(utils::test::syntex!(my_schema.mood).unwrap()),
+            "my_schema.mood"
+        );
+        assert!(utils::test::syntex!(int[a]).map(|_: TypeName| ()).is_err());
+    }
+}
@@ -1,13 +1,17 @@
-use super::ast::OrExpr;
+use super::ast::Expr;
 use crate::*;
 
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Value {
-    String(LitStr),
-    Int(LitInt),
-    Float(LitFloat),
+    String(#[cfg_attr(feature = "serde", serde(with = "crate::tokens"))] LitStr),
+    Int(#[cfg_attr(feature = "serde", serde(with = "crate::tokens"))] LitInt),
+    Float(#[cfg_attr(feature = "serde", serde(with = "crate::tokens"))] LitFloat),
     Boolean(WithSpan<Option<bool>>),
-    ARRAY(WithSpan<Punctuated<OrExpr, Token![,]>>),
-    Null { span: Span },
+    ARRAY(WithSpan<Punctuated<Expr, Token![,]>>),
+    Null {
+        #[cfg_attr(feature = "serde", serde(with = "crate::tokens"))]
+        span: Span,
+    },
 }
 
 impl Parse for Value {
@@ -41,7 +45,13 @@ impl Parse for Value {
                             }
                             let tokens = group.stream();
                             let punctuated = |a: ParseStream| a.call(Punctuated::parse_terminated);
-                            return Ok((Self::ARRAY(WithSpan::new(group.span(), punctuated.parse2(tokens)?)), rest));
+                            return Ok((
+                                Self::ARRAY(WithSpan::new(
+                                    group.span(),
+                                    punctuated.parse2(tokens)?,
+                                )),
+                                rest,
+                            ));
                         }
                         _ => return Err(error),
                     }
@@ -0,0 +1,669 @@
+//! SQL lexer for queries written as a single string literal: `sql!("SELECT 'abc' -- comment")`.
+//!
+//! The grammar is written against Rust tokens, so SQL tokens are translated to the tokens
+//! `sql! { .. }` would have received: `'text'`, `E'text'` and `$$text$$` become string literals,
+//! `"name"` and `` `name` `` become raw strings (quoted names), bind parameters `$1` and `?` become
+//! the punctuation they are spelled with, and comments are dropped.
+//!
+//! Ref: https://www.postgresql.org/docs/current/sql-syntax-lexical.html
+use crate::*;
+use proc_macro2::{Group, Punct, Spacing, TokenStream};
+use std::ops::Range;
+use std::str::FromStr;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum TokenKind {
+    Ident(String),
+    /// `"name"` or `` `name` ``
+    QuotedIdent(String),
+    /// `'text'`, `E'text'` or `$tag$text$tag$`, with escapes resolved.
+    String(String),
+    Number(String),
+    /// An operator or a punctuation character such as `,` or `::`.
+    Punct(String),
+    /// A bind parameter, `$<n>` or `?`.
+    Param(String),
+    Open(Delimiter),
+    Close(Delimiter),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Token {
+    pub kind: TokenKind,
+    /// Byte range in the SQL text.
+    pub range: Range<usize>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct LexError {
+    pub message: String,
+    pub range: Range<usize>,
+}
+
+/// Characters that make up operators. `?` is a bind parameter on its own, so `id=?` is `id = ?`.
+const OPERATOR_CHARS: &str = "+-*/<>=~!@#%^&|";
+
+pub fn tokenize(sql: &str) -> std::result::Result<Vec<Token>, LexError> {
+    Lexer { sql, pos: 0 }.tokenize()
+}
+
+/// Parses SQL from the value of a string literal. Spans point inside the literal when the
+/// compiler supports sub-spans, otherwise at the whole literal.
+pub fn parse_literal<P: Parser>(lit: &Literal, parser: P) -> Result<P::Output> {
+    let value = syn::parse2::<LitStr>(TokenTree::Literal(lit.clone()).into())?.value();
+    let offset = source_offset(&lit.to_string(), &value);
+    let span_of = |range: Range<usize>| match offset {
+        Some(offset) => lit
+            .subspan(range.start + offset..range.end + offset)
+            .unwrap_or(lit.span()),
+        None => lit.span(),
+    };
+    let tokens = to_token_stream(&value, span_of)?;
+    parser.parse2(tokens)
+}
+
+/// Suffix of a string constant, which tells it apart from a quoted name: `'users'` is lexed
+/// to `"users"sql` and `"users"` to `r"users"`.
+pub const STRING_SUFFIX: &str = "sql";
+
+/// `r"<value>"`, with as many `#` as needed to hold the value as is.
+pub fn raw_string(value: &str) -> std::result::Result<Literal, proc_macro2::LexError> {
+    let hashes = (0..)
+        .map(|n| "#".repeat(n))
+        .find(|hashes| !value.contains(&format!("\"{hashes}")))
+        .unwrap();
+    Literal::from_str(&format!("r{hashes}\"{value}\"{hashes}"))
+}
+
+/// Byte offset of the value within the literal's source, `None` if escapes make them differ.
+fn source_offset(source: &str, value: &str) -> Option<usize> {
+    let offset = match source.strip_prefix('r') {
+        // `r` and the hashes before the quote.
+        Some(raw) => raw.find('"')? + 2,
+        None => 1,
+    };
+    (source.get(offset..offset + value.len())? == value).then_some(offset)
+}
+
+/// Translates SQL to the Rust tokens the grammar is written against.
+pub fn to_token_stream(sql: &str, span_of: impl Fn(Range<usize>) -> Span) -> Result<TokenStream> {
+    let tokens = tokenize(sql).map_err(|e| Error::new(span_of(e.range), e.message))?;
+    let mut root = vec![];
+    let mut open_groups: Vec<OpenGroup> = vec![];
+    for token in tokens {
+        let span = span_of(token.range.clone());
+        let trees = match open_groups.last_mut() {
+            Some(group) => &mut group.trees,
+            None => &mut root,
+        };
+        match token.kind {
+            TokenKind::Ident(name) => {
+                // Postgres allows `$` in names, which a Rust identifier can't hold.
+                let mut ident = Ident::parse_any.parse_str(&name).map_err(|_| {
+                    Error::new(
+                        span,
+                        format!("unsupported identifier `{name}`, quote it as \"{name}\""),
+                    )
+                })?;
+                ident.set_span(span);
+                trees.push(ident.into());
+            }
+            TokenKind::QuotedIdent(name) => {
+                // `""` stays escaped, as in a quoted name written with Rust tokens.
+                let mut lit = raw_string(&name.replace('"', "\"\""))
+                    .map_err(|_| Error::new(span, format!("unsupported identifier `{name}`")))?;
+                lit.set_span(span);
+                trees.push(lit.into());
+            }
+            TokenKind::String(value) => {
+                let mut lit =
+                    Literal::from_str(&format!("{}{STRING_SUFFIX}", Literal::string(&value)))
+                        .map_err(|_| Error::new(span, format!("unsupported string `{value}`")))?;
+                lit.set_span(span);
+                trees.push(lit.into());
+            }
+            TokenKind::Param(param) => {
+                let start = token.range.start;
+                let mut punct = Punct::new(param.chars().next().unwrap(), Spacing::Alone);
+                punct.set_span(span_of(start..start + 1));
+                trees.push(punct.into());
+                // `$1` is `$` and the number.
+                if param.len() > 1 {
+                    let mut lit = Literal::from_str(&param[1..])
+                        .map_err(|_| Error::new(span, format!("invalid parameter `{param}`")))?;
+                    lit.set_span(span_of(start + 1..token.range.end));
+                    trees.push(lit.into());
+                }
+            }
+            TokenKind::Number(number) => {
+                let mut lit = Literal::from_str(&number)
+                    .map_err(|_| Error::new(span, format!("invalid number `{number}`")))?;
+                lit.set_span(span);
+                trees.push(lit.into());
+            }
+            TokenKind::Punct(op) => {
+                let chars: Vec<_> = op.chars().collect();
+                for (i, ch) in chars.iter().enumerate() {
+                    let spacing = match i + 1 < chars.len() {
+                        true => Spacing::Joint,
+                        false => Spacing::Alone,
+                    };
+                    let mut punct = Punct::new(*ch, spacing);
+                    punct.set_span(span_of(token.range.start + i..token.range.start + i + 1));
+                    trees.push(punct.into());
+                }
+            }
+            TokenKind::Open(delimiter) => open_groups.push(OpenGroup {
+                delimiter,
+                start: token.range.start,
+                trees: vec![],
+            }),
+            TokenKind::Close(delimiter) => match open_groups.pop() {
+                Some(open) if open.delimiter == delimiter => {
+                    let mut group = Group::new(delimiter, open.trees.into_iter().collect());
+                    group.set_span(span_of(open.start..token.range.end));
+                    match open_groups.last_mut() {
+                        Some(parent) => parent.trees.push(group.into()),
+                        None => root.push(group.into()),
+                    }
+                }
+                _ => return Err(Error::new(span, "unexpected closing delimiter")),
+            },
+        }
+    }
+    match open_groups.last() {
+        Some(open) => Err(Error::new(
+            span_of(open.start..open.start + 1),
+            "unclosed delimiter",
+        )),
+        None => Ok(root.into_iter().collect()),
+    }
+}
+
+/// A group whose closing delimiter hasn't been reached yet.
+struct OpenGroup {
+    delimiter: Delimiter,
+    start: usize,
+    trees: Vec<TokenTree>,
+}
+
+struct Lexer<'a> {
+    sql: &'a str,
+    pos: usize,
+}
+
+impl Lexer<'_> {
+    fn tokenize(mut self) -> std::result::Result<Vec<Token>, LexError> {
+        let mut tokens = vec![];
+        while let Some(ch) = self.peek() {
+            let start = self.pos;
+            let kind = match ch {
+                _ if ch.is_whitespace() => {
+                    self.bump();
+                    continue;
+                }
+                '-' if self.rest().starts_with("--") => {
+                    let len = self.rest().find('\n').unwrap_or(self.rest().len());
+                    self.pos += len;
+                    continue;
+                }
+                '/' if self.rest().starts_with("/*") => {
+                    self.block_comment()?;
+                    continue;
+                }
+                '\'' => TokenKind::String(self.quoted('\'', "unterminated quoted string")?),
+                'e' | 'E' if self.rest()[1..].starts_with('\'') => {
+                    self.bump();
+                    TokenKind::String(self.escape_string()?)
+                }
+                '"' | '`' => {
+                    let name = self.quoted(ch, "unterminated quoted identifier")?;
+                    if name.is_empty() {
+                        return self.error(start, "zero-length delimited identifier");
+                    }
+                    TokenKind::QuotedIdent(name)
+                }
+                '$' if self.rest()[1..].starts_with(|c: char| c.is_ascii_digit()) => {
+                    TokenKind::Param(self.numbered_param()?)
+                }
+                '$' => TokenKind::String(self.dollar_quoted()?),
+                '?' => {
+                    self.bump();
+                    TokenKind::Param(ch.to_string())
+                }
+                '0'..='9' => TokenKind::Number(self.number()?),
+                '.' if self.rest()[1..].starts_with(|c: char| c.is_ascii_digit()) => {
+                    TokenKind::Number(self.number()?)
+                }
+                _ if ch.is_alphabetic() || ch == '_' => TokenKind::Ident(self.ident()),
+                '(' | '[' => {
+                    self.bump();
+                    TokenKind::Open(delimiter(ch))
+                }
+                ')' | ']' => {
+                    self.bump();
+                    TokenKind::Close(delimiter(ch))
+                }
+                ':' if self.rest().starts_with("::") => {
+                    self.pos += 2;
+                    TokenKind::Punct("::".to_string())
+                }
+                ',' | ';' | '.' | ':' => {
+                    self.bump();
+                    TokenKind::Punct(ch.to_string())
+                }
+                _ if OPERATOR_CHARS.contains(ch) => TokenKind::Punct(self.operator()),
+                _ => {
+                    self.bump();
+                    return self.error(start, format!("syntax error at or near `{ch}`"));
+                }
+            };
+            tokens.push(Token {
+                kind,
+                range: start..self.pos,
+            });
+        }
+        Ok(tokens)
+    }
+
+    fn rest(&self) -> &str {
+        &self.sql[self.pos..]
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.rest().chars().next()
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        let ch = self.peek()?;
+        self.pos += ch.len_utf8();
+        Some(ch)
+    }
+
+    fn error<T>(
+        &self,
+        start: usize,
+        message: impl Into<String>,
+    ) -> std::result::Result<T, LexError> {
+        Err(LexError {
+            message: message.into(),
+            range: start..self.pos,
+        })
+    }
+
+    /// Block comments nest, unlike in C.
+    fn block_comment(&mut self) -> std::result::Result<(), LexError> {
+        let start = self.pos;
+        let mut depth = 0;
+        while !self.rest().is_empty() {
+            if self.rest().starts_with("/*") {
+                depth += 1;
+                self.pos += 2;
+            } else if self.rest().starts_with("*/") {
+                depth -= 1;
+                self.pos += 2;
+                if depth == 0 {
+                    return Ok(());
+                }
+            } else {
+                self.bump();
+            }
+        }
+        self.error(start, "unterminated /* comment")
+    }
+
+    /// `'it''s'` or `"a ""quoted"" name"`, the quote is escaped by doubling it.
+    fn quoted(&mut self, quote: char, unterminated: &str) -> std::result::Result<String, LexError> {
+        let start = self.pos;
+        self.bump();
+        let mut value = String::new();
+        loop {
+            match self.bump() {
+                Some(ch) if ch == quote => match self.peek() {
+                    Some(next) if next == quote => {
+                        self.bump();
+                        value.push(quote);
+                    }
+                    _ => return Ok(value),
+                },
+                Some(ch) => value.push(ch),
+                None => return self.error(start, unterminated),
+            }
+        }
+    }
+
+    /// `E'..'`, with C-style backslash escapes.
+    fn escape_string(&mut self) -> std::result::Result<String, LexError> {
+        let start = self.pos;
+        self.bump();
+        let mut value = String::new();
+        loop {
+            let escape_start = self.pos;
+            match self.bump() {
+                Some('\'') if self.peek() == Some('\'') => {
+                    self.bump();
+                    value.push('\'');
+                }
+                Some('\'') => return Ok(value),
+                Some('\\') => {
+                    let ch = match self.bump() {
+                        Some('b') => '\u{8}',
+                        Some('f') => '\u{c}',
+                        Some('n') => '\n',
+                        Some('r') => '\r',
+                        Some('t') => '\t',
+                        Some('x') => self.escaped_char(escape_start, 16, 1, 2)?,
+                        Some('u') => self.escaped_char(escape_start, 16, 4, 4)?,
+                        Some('U') => self.escaped_char(escape_start, 16, 8, 8)?,
+                        Some('0'..='7') => {
+                            self.pos -= 1;
+                            self.escaped_char(escape_start, 8, 1, 3)?
+                        }
+                        Some(ch) => ch,
+                        None => return self.error(start, "unterminated quoted string"),
+                    };
+                    value.push(ch);
+                }
+                Some(ch) => value.push(ch),
+                None => return self.error(start, "unterminated quoted string"),
+            }
+        }
+    }
+
+    fn escaped_char(
+        &mut self,
+        start: usize,
+        radix: u32,
+        min: usize,
+        max: usize,
+    ) -> std::result::Result<char, LexError> {
+        let len = self
+            .rest()
+            .chars()
+            .take(max)
+            .take_while(|c| c.is_digit(radix))
+            .count();
+        let ch = u32::from_str_radix(&self.rest()[..len], radix)
+            .ok()
+            .and_then(char::from_u32);
+        self.pos += len;
+        match ch {
+            Some(ch) if len >= min => Ok(ch),
+            _ => self.error(start, "invalid escape sequence"),
+        }
+    }
+
+    /// `$$text$$` or `$tag$text$tag$`, nothing is escaped inside.
+    fn dollar_quoted(&mut self) -> std::result::Result<String, LexError> {
+        let start = self.pos;
+        let tag_len = self.rest()[1..]
+            .find(|c: char| !(c.is_alphanumeric() || c == '_'))
+            .filter(|&len| self.rest()[1 + len..].starts_with('$'))
+            .filter(|_| !self.rest()[1..].starts_with(|c: char| c.is_ascii_digit()));
+        let Some(tag_len) = tag_len else {
+            self.bump();
+            return self.error(start, "syntax error at or near `$`");
+        };
+        let tag = self.rest()[..tag_len + 2].to_string();
+        self.pos += tag.len();
+        match self.rest().find(&tag) {
+            Some(len) => {
+                let value = self.rest()[..len].to_string();
+                self.pos += len + tag.len();
+                Ok(value)
+            }
+            None => {
+                self.pos = self.sql.len();
+                self.error(start, "unterminated dollar-quoted string")
+            }
+        }
+    }
+
+    /// `$1`, a name can't follow the number.
+    fn numbered_param(&mut self) -> std::result::Result<String, LexError> {
+        let start = self.pos;
+        self.bump();
+        while self.peek().is_some_and(|c| c.is_ascii_digit()) {
+            self.bump();
+        }
+        if self.peek().is_some_and(|c| c.is_alphanumeric() || c == '_') {
+            self.ident();
+            return self.error(start, "trailing junk after parameter");
+        }
+        Ok(self.sql[start..self.pos].to_string())
+    }
+
+    /// `42`, `3.5`, `.5`, `1e-3`. A number directly followed by a name is rejected like Postgres does.
+    fn number(&mut self) -> std::result::Result<String, LexError> {
+        let start = self.pos;
+        let digits = |lexer: &mut Self| {
+            while lexer.peek().is_some_and(|c| c.is_ascii_digit()) {
+                lexer.bump();
+            }
+        };
+        digits(self);
+        if self.peek() == Some('.') && !self.rest().starts_with("..") {
+            self.bump();
+            digits(self);
+        }
+        if self.peek().is_some_and(|c| c == 'e' || c == 'E') {
+            let exponent = self.rest()[1..].trim_start_matches(['+', '-']);
+            if exponent.starts_with(|c: char| c.is_ascii_digit()) {
+                self.pos += self.rest().len() - exponent.len();
+                digits(self);
+            }
+        }
+        if self.peek().is_some_and(|c| c.is_alphanumeric() || c == '_') {
+            self.ident();
+            return self.error(start, "trailing junk after numeric literal");
+        }
+        let number = &self.sql[start..self.pos];
+        // Rust needs a digit before the `.`
+        Ok(match number.starts_with('.') {
+            true => format!("0{number}"),
+            false => number.to_string(),
+        })
+    }
+
+    fn ident(&mut self) -> String {
+        let start = self.pos;
+        while self
+            .peek()
+            .is_some_and(|c| c.is_alphanumeric() || c == '_' || c == '$')
+        {
+            self.bump();
+        }
+        self.sql[start..self.pos].to_string()
+    }
+
+    /// Like Postgres, `--` and `/*` end an operator, and `+`/`-` can't end a multi-character
+    /// operator unless it contains one of `~ ! @ # % ^ & | ?`, so `a<-1` is `a < -1`.
+    fn operator(&mut self) -> String {
+        let start = self.pos;
+        while self.peek().is_some_and(|c| OPERATOR_CHARS.contains(c))
+            && !(self.pos > start
+                && (self.rest().starts_with("--") || self.rest().starts_with("/*")))
+        {
+            self.bump();
+        }
+        let mut op = &self.sql[start..self.pos];
+        if op.len() > 1 && !op.contains(['~', '!', '@', '#', '%', '^', '&', '|', '?']) {
+            let trimmed = op.trim_end_matches(['+', '-']);
+            op = match trimmed.is_empty() {
+                true => &op[..1],
+                false => trimmed,
+            };
+            self.pos = start + op.len();
+        }
+        op.to_string()
+    }
+}
+
+fn delimiter(ch: char) -> Delimiter {
+    match ch {
+        '(' | ')' => Delimiter::Parenthesis,
+        _ => Delimiter::Bracket,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use command::Command;
+
+    /// `r#"<sql>"#`, as written in `sql!(r#"<sql>"#)`.
+    fn literal(sql: &str) -> Literal {
+        Literal::from_str(&format!("r#\"{sql}\"#")).unwrap()
+    }
+
+    fn kinds(sql: &str) -> Vec<TokenKind> {
+        tokenize(sql).unwrap().into_iter().map(|t| t.kind).collect()
+    }
+
+    /// Spans differ between the two forms, the rest of the tree must not.
+    fn without_spans(debug: String) -> String {
+        let mut out = String::new();
+        let mut rest = debug.as_str();
+        while let Some(i) = rest.find(", span: bytes(") {
+            out.push_str(&rest[..i]);
+            rest = &rest[i..];
+            rest = &rest[rest.find(')').unwrap() + 1..];
+        }
+        out + rest
+    }
+
+    #[test]
+    fn same_ast_as_rust_tokens() {
+        let cases = [
+            (
+                "SELECT 'it''s', \"User\".id, E'a\\tb' AS x FROM \"User\" -- trailing comment
+                WHERE id >= .5 /* a /* nested */ comment */ AND name <> $$it's$$",
+                r#"SELECT "it's", r"User".id, "a	b" AS x FROM r"User" WHERE id >= 0.5 AND name <> "it's""#,
+            ),
+            (
+                "UPDATE users SET name = lower(`name`) WHERE id::text IN ('1', '2') RETURNING *",
+                r#"UPDATE users SET name = lower(r"name") WHERE id::text IN ("1", "2") RETURNING *"#,
+            ),
+            (
+                "SELECT a<-1, b !~* 'x', ARRAY[1, 2e3] FROM t",
+                r#"SELECT a < -1, b !~* "x", ARRAY[1, 2e3] FROM t"#,
+            ),
+            (
+                "SELECT id FROM users WHERE id=$1 OR name=? OR age=$12",
+                "SELECT id FROM users WHERE id = $1 OR name = ? OR age = $12",
+            ),
+            (
+                "SELECT `a\"b` FROM \"My \"\"Table\"\"\"",
+                r###"SELECT r#"a""b"# FROM r#"My ""Table"""#"###,
+            ),
+        ];
+        for (sql, tokens) in cases {
+            let from_sql = parse_literal(&literal(sql), Command::parse).unwrap();
+            let from_tokens = syn::parse_str::<Command>(tokens).unwrap();
+            assert_eq!(
+                without_spans(format!("{from_sql:?}")),
+                without_spans(format!("{from_tokens:?}"))
+            );
+        }
+        // Through `Command::parse`, as `sql!` does.
+        assert!(syn::parse_str::<Command>(r#""SELECT 'abc' -- comment""#).is_ok());
+    }
+
+    #[test]
+    fn string_constant_is_not_a_name() {
+        let parse = |sql| parse_literal(&literal(sql), Command::parse);
+        for sql in ["SELECT id FROM 'users'", "SELECT id FROM users AS 'u'"] {
+            assert_eq!(
+                parse(sql).unwrap_err().to_string(),
+                "expected `Name`, found a string constant"
+            );
+        }
+        assert!(parse("SELECT 'users'.id FROM users").is_err());
+        assert!(parse(r#"SELECT 'users' FROM "users" AS "u""#).is_ok());
+    }
+
+    #[test]
+    fn tokens() {
+        assert_eq!(
+            kinds(r"E'\x41\101é\'' $tag$a $$ b$tag$ `a``b` x::int"),
+            [
+                TokenKind::String("AAé'".to_string()),
+                TokenKind::String("a $$ b".to_string()),
+                TokenKind::QuotedIdent("a`b".to_string()),
+                TokenKind::Ident("x".to_string()),
+                TokenKind::Punct("::".to_string()),
+                TokenKind::Ident("int".to_string()),
+            ]
+        );
+        assert_eq!(
+            kinds("a+-b a<=-1 a@-1"),
+            [
+                TokenKind::Ident("a".to_string()),
+                TokenKind::Punct("+".to_string()),
+                TokenKind::Punct("-".to_string()),
+                TokenKind::Ident("b".to_string()),
+                TokenKind::Ident("a".to_string()),
+                TokenKind::Punct("<=".to_string()),
+                TokenKind::Punct("-".to_string()),
+                TokenKind::Number("1".to_string()),
+                TokenKind::Ident("a".to_string()),
+                TokenKind::Punct("@-".to_string()),
+                TokenKind::Number("1".to_string()),
+            ]
+        );
+        assert_eq!(
+            kinds("id=$1 id=?"),
+            [
+                TokenKind::Ident("id".to_string()),
+                TokenKind::Punct("=".to_string()),
+                TokenKind::Param("$1".to_string()),
+                TokenKind::Ident("id".to_string()),
+                TokenKind::Punct("=".to_string()),
+                TokenKind::Param("?".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn errors() {
+        let err = |sql| tokenize(sql).unwrap_err();
+        assert_eq!(
+            err("SELECT 1e-3x"),
+            LexError {
+                message: "trailing junk after numeric literal".to_string(),
+                range: 7..12,
+            }
+        );
+        assert_eq!(err("SELECT 'abc").range, 7..11);
+        assert_eq!(err("SELECT /* a").message, "unterminated /* comment");
+        assert_eq!(
+            err(r#"SELECT """#).message,
+            "zero-length delimited identifier"
+        );
+        assert_eq!(err(r"SELECT E'\x'").message, "invalid escape sequence");
+        assert_eq!(err("SELECT $1a").message, "trailing junk after parameter");
+    }
+
+    #[test]
+    fn sub_spans() {
+        let err = parse_literal(&literal("SELEC 1"), Command::parse).unwrap_err();
+        assert_eq!(err.to_string(), "did you mean: SELECT");
+        // `r#"` comes before the SQL.
+        #[cfg(feature = "positions")]
+        assert_eq!(err.span().byte_range(), 3..8);
+
+        let err = parse_literal(&literal("SELECT a$b FROM t"), Command::parse).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            r#"unsupported identifier `a$b`, quote it as "a$b""#
+        );
+        #[cfg(feature = "positions")]
+        assert_eq!(err.span().byte_range(), 10..13);
+        assert!(parse_literal(&literal(r#"SELECT "a$b" FROM t"#), Command::parse).is_ok());
+
+        let err = parse_literal(&literal("SELECT (1"), Command::parse).unwrap_err();
+        assert_eq!(err.to_string(), "unclosed delimiter");
+        #[cfg(feature = "positions")]
+        assert_eq!(err.span().byte_range(), 10..11);
+    }
+}
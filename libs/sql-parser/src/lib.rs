@@ -2,12 +2,22 @@
 /// - https://forcedotcom.github.io/phoenix/
 /// - https://en.wikipedia.org/wiki/SQL_syntax
 pub mod command;
+pub mod dialect;
+#[cfg(feature = "positions")]
+pub mod error;
 pub mod function;
-pub mod window;
 pub mod grammar;
+pub mod lexer;
 pub mod spanned;
+#[cfg(feature = "serde")]
+pub mod tokens;
+pub mod window;
 
 pub mod utils;
+pub mod visit;
+pub mod visit_mut;
+#[cfg(feature = "positions")]
+pub use error::{parse_command, ParseError};
 pub use spanned::GetSpan;
 pub use spanned::WithSpan;
 
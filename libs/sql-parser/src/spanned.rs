@@ -1,5 +1,5 @@
 use proc_macro2::Span;
-use std::ops::Deref;
+use std::ops::{Deref, DerefMut};
 
 pub trait GetSpan {
     fn span(&self) -> Span;
@@ -7,12 +7,16 @@ pub trait GetSpan {
 
 impl GetSpan for Span {
     fn span(&self) -> Span {
-        self.clone()
+        *self
     }
 }
 
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(bound = "T: crate::tokens::Tokens"))]
 pub struct WithSpan<T> {
+    #[cfg_attr(feature = "serde", serde(with = "crate::tokens"))]
     pub span: Span,
+    #[cfg_attr(feature = "serde", serde(with = "crate::tokens"))]
     pub value: T,
 }
 
@@ -30,6 +34,12 @@ impl<T> Deref for WithSpan<T> {
     }
 }
 
+impl<T> DerefMut for WithSpan<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.value
+    }
+}
+
 impl<T> GetSpan for WithSpan<T> {
     fn span(&self) -> Span {
         self.span
@@ -38,12 +48,12 @@ impl<T> GetSpan for WithSpan<T> {
 
 impl<T: GetSpan> GetSpan for Box<T> {
     fn span(&self) -> Span {
-        T::span(&self)
+        T::span(self)
     }
 }
 
 impl<T: GetSpan> GetSpan for &T {
     fn span(&self) -> Span {
-        T::span(&self)
+        T::span(self)
     }
 }
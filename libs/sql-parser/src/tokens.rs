@@ -0,0 +1,323 @@
+//! Serde support for the `proc-macro2` and `syn` tokens kept in the syntax tree,
+//! used by fields marked `#[serde(with = "crate::tokens")]`.
+//!
+//! Spans are written as line/column ranges, with 1-based lines and columns, and are read back
+//! as `Span::call_site()`. Punctuation and delimiters are written as their span only.
+use crate::command::insert::InsertExpr;
+use crate::grammar::{ast::Expr, Column, Name, TableRef};
+use crate::utils::SeparatedByComma;
+use proc_macro2::{Ident, Literal, Span};
+use serde::{de, de::DeserializeOwned, Deserialize, Deserializer, Serialize, Serializer};
+use syn::{punctuated::Punctuated, token, Lit, LitFloat, LitInt, LitStr, Token};
+
+pub trait Tokens: Sized {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error>;
+    fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error>;
+}
+
+pub fn serialize<T: Tokens, S: Serializer>(value: &T, serializer: S) -> Result<S::Ok, S::Error> {
+    value.serialize(serializer)
+}
+
+pub fn deserialize<'de, T: Tokens, D: Deserializer<'de>>(deserializer: D) -> Result<T, D::Error> {
+    T::deserialize(deserializer)
+}
+
+struct Ser<'a, T>(&'a T);
+
+impl<T: Tokens> Serialize for Ser<'_, T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.0.serialize(serializer)
+    }
+}
+
+struct De<T>(T);
+
+impl<'de, T: Tokens> Deserialize<'de> for De<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        T::deserialize(deserializer).map(De)
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct LineColumn {
+    line: usize,
+    column: usize,
+}
+
+#[derive(Serialize, Deserialize)]
+struct SpanRange {
+    start: LineColumn,
+    end: LineColumn,
+}
+
+impl Tokens for Span {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let location = |lc: proc_macro2::LineColumn| LineColumn {
+            line: lc.line,
+            column: lc.column + 1,
+        };
+        SpanRange {
+            start: location(self.start()),
+            end: location(self.end()),
+        }
+        .serialize(serializer)
+    }
+
+    fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        SpanRange::deserialize(deserializer).map(|_| Span::call_site())
+    }
+}
+
+/// A token written as its text.
+#[derive(Serialize, Deserialize)]
+struct Spanned {
+    text: String,
+    #[serde(with = "crate::tokens")]
+    span: Span,
+}
+
+impl Tokens for Ident {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        Spanned {
+            text: self.to_string(),
+            span: self.span(),
+        }
+        .serialize(serializer)
+    }
+
+    fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let Spanned { text, span } = Spanned::deserialize(deserializer)?;
+        let (raw, name) = match text.strip_prefix("r#") {
+            Some(name) => (true, name),
+            None => (false, text.as_str()),
+        };
+        let mut chars = name.chars();
+        let is_ident = chars
+            .next()
+            .is_some_and(|ch| ch == '_' || ch.is_alphabetic())
+            && chars.all(|ch| ch == '_' || ch.is_alphanumeric());
+        match (is_ident, raw) {
+            (false, _) => Err(de::Error::custom(format!("invalid identifier `{text}`"))),
+            (true, false) => Ok(Ident::new(name, span)),
+            (true, true) => Ok(Ident::new_raw(name, span)),
+        }
+    }
+}
+
+impl Tokens for Literal {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        Spanned {
+            text: self.to_string(),
+            span: self.span(),
+        }
+        .serialize(serializer)
+    }
+
+    fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let Spanned { text, .. } = Spanned::deserialize(deserializer)?;
+        text.parse()
+            .map_err(|_| de::Error::custom(format!("invalid literal `{text}`")))
+    }
+}
+
+macro_rules! lit_tokens {
+    [$($ty: ident => $variant: ident),*] => {$(
+        impl Tokens for $ty {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                self.token().serialize(serializer)
+            }
+
+            fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                match Lit::new(Literal::deserialize(deserializer)?) {
+                    Lit::$variant(lit) => Ok(lit),
+                    _ => Err(de::Error::custom(concat!("expected ", stringify!($ty)))),
+                }
+            }
+        }
+    )*};
+}
+
+lit_tokens![LitStr => Str, LitInt => Int, LitFloat => Float];
+
+macro_rules! punct_tokens {
+    [$($ty: ty),*] => {$(
+        impl Tokens for $ty {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                let span = self.spans[0];
+                span.join(self.spans[self.spans.len() - 1])
+                    .unwrap_or(span)
+                    .serialize(serializer)
+            }
+
+            fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                Span::deserialize(deserializer).map(|_| Self::default())
+            }
+        }
+    )*};
+}
+
+punct_tokens![
+    Token![*],
+    Token![,],
+    Token![=],
+    Token![::],
+    Token![$],
+    Token![?]
+];
+
+impl Tokens for token::Paren {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.span.join().serialize(serializer)
+    }
+
+    fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Span::deserialize(deserializer).map(|_| Self::default())
+    }
+}
+
+impl<T: Tokens> Tokens for Option<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.as_ref().map(Ser).serialize(serializer)
+    }
+
+    fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Option::<De<T>>::deserialize(deserializer).map(|v| v.map(|De(v)| v))
+    }
+}
+
+impl<T: Tokens> Tokens for Vec<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.iter().map(Ser))
+    }
+
+    fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Vec::<De<T>>::deserialize(deserializer).map(|v| v.into_iter().map(|De(v)| v).collect())
+    }
+}
+
+/// Written as a list of the values, the separators are dropped.
+impl<T: Tokens, P: Default> Tokens for Punctuated<T, P> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.iter().map(Ser))
+    }
+
+    fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Vec::<De<T>>::deserialize(deserializer).map(|v| v.into_iter().map(|De(v)| v).collect())
+    }
+}
+
+impl<A: Tokens, B: Tokens> Tokens for (A, B) {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        (Ser(&self.0), Ser(&self.1)).serialize(serializer)
+    }
+
+    fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        <(De<A>, De<B>)>::deserialize(deserializer).map(|(De(a), De(b))| (a, b))
+    }
+}
+
+impl<A: Tokens, B: Tokens, C: Tokens> Tokens for (A, B, C) {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        (Ser(&self.0), Ser(&self.1), Ser(&self.2)).serialize(serializer)
+    }
+
+    fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        <(De<A>, De<B>, De<C>)>::deserialize(deserializer).map(|(De(a), De(b), De(c))| (a, b, c))
+    }
+}
+
+/// Nodes found next to tokens, e.g. in a `Punctuated` or a tuple, use their own `Serialize`.
+macro_rules! serde_tokens {
+    [$($ty: ty),*] => {$(
+        impl Tokens for $ty {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                Serialize::serialize(self, serializer)
+            }
+
+            fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                <Self as Deserialize>::deserialize(deserializer)
+            }
+        }
+    )*};
+}
+
+serde_tokens![bool, String, Name, Expr, InsertExpr, TableRef];
+
+impl<T: Serialize + DeserializeOwned> Tokens for SeparatedByComma<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        Serialize::serialize(self, serializer)
+    }
+
+    fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        <Self as Deserialize>::deserialize(deserializer)
+    }
+}
+
+impl<T: Tokens> Tokens for Column<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        Serialize::serialize(self, serializer)
+    }
+
+    fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        <Self as Deserialize>::deserialize(deserializer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::command::Command;
+    use crate::parse_command;
+    use serde_json::Value;
+
+    #[test]
+    fn json() {
+        let command = parse_command("SELECT a, count(*)\nFROM t WHERE b IN (1, 'x')").unwrap();
+        let json = serde_json::to_value(&command).unwrap();
+        let select = &json["Select"]["body"]["Select"];
+        assert_eq!(
+            select["select_kw"],
+            serde_json::json!({
+                "text": "SELECT",
+                "span": { "start": { "line": 1, "column": 1 }, "end": { "line": 1, "column": 7 } },
+            })
+        );
+        assert_eq!(
+            select["from"]["from_kw"]["span"],
+            serde_json::json!({ "start": { "line": 2, "column": 1 }, "end": { "line": 2, "column": 5 } })
+        );
+    }
+
+    #[test]
+    fn round_trip() {
+        for sql in [
+            "SELECT DISTINCT ON (a) a, count(*) FILTER (WHERE b) FROM t WHERE b IN (1, 'x') GROUP BY a",
+            "SELECT rank() OVER w, CASE WHEN a::text LIKE 'x%' THEN -1 END FROM t WINDOW w AS (ORDER BY a)",
+            "INSERT INTO t (a, b) VALUES (1, DEFAULT) ON CONFLICT (a) DO UPDATE SET b = excluded.b",
+            "UPDATE t SET (a, b) = ROW (1, 2) WHERE a IS NOT DISTINCT FROM 1 RETURNING *",
+            "DELETE FROM t USING u WHERE t.a = u.a AND trim(both 'x' FROM u.b) = 'y'",
+            "CALL refresh(1, 'x')",
+        ] {
+            let json = serde_json::to_string(&parse_command(sql).unwrap()).unwrap();
+            let command: Command = serde_json::from_str(&json).unwrap();
+            let round_trip = serde_json::to_value(&command).unwrap();
+            let json = serde_json::from_str(&json).unwrap();
+            assert_eq!(without_spans(round_trip), without_spans(json), "{sql}");
+        }
+    }
+
+    /// Spans don't survive a round trip.
+    fn without_spans(value: Value) -> Value {
+        match value {
+            Value::Object(map) if map.contains_key("start") && map.contains_key("end") => {
+                Value::Null
+            }
+            Value::Object(map) => map
+                .into_iter()
+                .map(|(key, value)| (key, without_spans(value)))
+                .collect(),
+            Value::Array(values) => values.into_iter().map(without_spans).collect(),
+            value => value,
+        }
+    }
+}
@@ -1,3 +1,4 @@
+use std::cell::RefCell;
 use std::ops;
 
 use crate::*;
@@ -13,6 +14,7 @@ pub mod test {
 }
 
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct SeparatedByComma<T> {
     pub values: Vec<T>,
 }
@@ -21,7 +23,8 @@ impl<T: Parse> Parse for SeparatedByComma<T> {
     fn parse(input: ParseStream) -> Result<Self> {
         let mut values = vec![];
         while !input.cursor().eof() {
-            values.push(input.parse()?);
+            let is_boundary = |input: ParseStream| input.peek(Token![,]) || peek_clause(input);
+            values.extend(recover(input, T::parse, is_boundary)?);
             if !input.peek(Token![,]) {
                 break;
             }
@@ -31,7 +34,7 @@ impl<T: Parse> Parse for SeparatedByComma<T> {
     }
 }
 
-impl<T: ops::Deref> ops::Deref for SeparatedByComma<T> {
+impl<T> ops::Deref for SeparatedByComma<T> {
     type Target = Vec<T>;
 
     fn deref(&self) -> &Self::Target {
@@ -39,6 +42,109 @@ impl<T: ops::Deref> ops::Deref for SeparatedByComma<T> {
     }
 }
 
+impl<T> ops::DerefMut for SeparatedByComma<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.values
+    }
+}
+
+thread_local! {
+    /// Syntax errors recovered from so far, `None` unless within `collect_errors`.
+    static RECOVERED: RefCell<Option<Vec<Error>>> = const { RefCell::new(None) };
+}
+
+/// Runs `parse` with error recovery enabled, and returns the errors it recovered from.
+pub fn collect_errors<T>(parse: impl FnOnce() -> T) -> (T, Vec<Error>) {
+    let outer = RECOVERED.with(|errs| errs.replace(Some(vec![])));
+    let value = parse();
+    let errs = RECOVERED.with(|errs| errs.replace(outer));
+    (value, errs.unwrap_or_default())
+}
+
+/// Parses with `parse`, and on error skips the tokens up to the next boundary so that parsing
+/// goes on. The error is recorded for `collect_errors`, or returned when recovery isn't enabled.
+pub fn recover<T>(
+    input: ParseStream,
+    parse: impl FnOnce(ParseStream) -> Result<T>,
+    is_boundary: impl Fn(ParseStream) -> bool,
+) -> Result<Option<T>> {
+    let fork = input.fork();
+    let err = match parse(&fork) {
+        Ok(value) => {
+            input.advance_to(&fork);
+            return Ok(Some(value));
+        }
+        Err(err) => err,
+    };
+    RECOVERED.with(|errs| match errs.borrow_mut().as_mut() {
+        Some(errs) => {
+            errs.push(err);
+            Ok(())
+        }
+        None => Err(err),
+    })?;
+    while !input.is_empty() && !is_boundary(input) {
+        input.step(|c| Ok(((), c.token_tree().unwrap().1)))?;
+    }
+    Ok(None)
+}
+
+/// Keywords that start a clause, parsing resumes at them after an error.
+pub const CLAUSE_KEYWORDS: &[&str] = &[
+    "FROM",
+    "WHERE",
+    "GROUP",
+    "HAVING",
+    "WINDOW",
+    "ORDER",
+    "LIMIT",
+    "OFFSET",
+    "UNION",
+    "INTERSECT",
+    "EXCEPT",
+    "ON",
+    "USING",
+    "RETURNING",
+    "SET",
+    "VALUES",
+];
+
+/// Whether the next tokens start a clause, or there are none left.
+pub fn peek_clause(input: ParseStream) -> bool {
+    input.is_empty() || CLAUSE_KEYWORDS.iter().any(|kw| peek_kw(input, kw))
+}
+
+/// Parses a clause with `parse`, recovering at the next clause on error. Tokens left over that
+/// don't start a clause are reported and skipped too.
+pub fn recover_clause<T>(
+    input: ParseStream,
+    parse: impl FnOnce(ParseStream) -> Result<T>,
+) -> Result<Option<T>> {
+    let value = recover(input, parse, peek_clause)?;
+    skip_to_clause(input)?;
+    Ok(value)
+}
+
+/// Reports and skips the tokens up to the next clause, if any.
+pub fn skip_to_clause(input: ParseStream) -> Result<()> {
+    if !peek_clause(input) {
+        recover(
+            input,
+            |input| Err::<(), _>(input.error("unexpected token")),
+            peek_clause,
+        )?;
+    }
+    Ok(())
+}
+
+/// Combines errors into one, so that all of them are reported.
+pub fn combine_errors(errs: impl IntoIterator<Item = Error>) -> Option<Error> {
+    errs.into_iter().reduce(|mut err, next| {
+        err.combine(next);
+        err
+    })
+}
+
 pub fn suggest<I>(input: &str, values: I) -> String
 where
     I: Iterator,
@@ -83,15 +189,50 @@ pub fn parse_keywords_if_matched(input: ParseStream, kws: &[&str]) -> Result<Ide
     input.step(|c| {
         let (keyword, rest) = c.ident().ok_or_else(err)?;
         let kw = keyword.to_string();
-        for expected_kw in kws {
-            if !kw.eq_ignore_ascii_case(expected_kw) {
-                return Err(err());
-            }
+        if !kws
+            .iter()
+            .any(|expected_kw| kw.eq_ignore_ascii_case(expected_kw))
+        {
+            return Err(err());
         }
         Ok((keyword, rest))
     })
 }
 
+pub fn peek_kw(input: ParseStream, kw: &str) -> bool {
+    input
+        .cursor()
+        .ident()
+        .is_some_and(|(keyword, _)| keyword.to_string().eq_ignore_ascii_case(kw))
+}
+
+/// Keywords that can't be used as an implicit alias (without `AS`).
+pub const RESERVED_KEYWORDS: &[&str] = &[
+    "FROM",
+    "WHERE",
+    "GROUP",
+    "HAVING",
+    "WINDOW",
+    "ORDER",
+    "LIMIT",
+    "OFFSET",
+    "FETCH",
+    "UNION",
+    "INTERSECT",
+    "EXCEPT",
+    "ON",
+    "USING",
+    "RETURNING",
+    "SET",
+    "AND",
+    "OR",
+    "NOT",
+];
+
+pub fn is_reserved_kw(input: ParseStream) -> bool {
+    RESERVED_KEYWORDS.iter().any(|kw| peek_kw(input, kw))
+}
+
 /// See:
 /// - https://youtu.be/d-Eq6x1yssU?si=YW94NY_vSc_KXrSb
 /// - https://en.wikipedia.org/wiki/Levenshtein_distance
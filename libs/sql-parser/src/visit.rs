@@ -0,0 +1,717 @@
+//! Read-only traversal of the syntax tree.
+//!
+//! Each `visit_*` method of [`Visit`] defaults to the free function of the same name,
+//! which walks into the children of the node. Override a method to act on a node,
+//! and call the free function from it to keep walking.
+use crate::command::{call::*, delete::*, insert::*, select::*, update::*, Command};
+use crate::function::*;
+use crate::grammar::{ast::*, *};
+use crate::window::*;
+
+pub trait Visit<'ast> {
+    fn visit_command(&mut self, node: &'ast Command) {
+        visit_command(self, node)
+    }
+    fn visit_query(&mut self, node: &'ast Query) {
+        visit_query(self, node)
+    }
+    fn visit_set_expr(&mut self, node: &'ast SetExpr) {
+        visit_set_expr(self, node)
+    }
+    fn visit_set_operator(&mut self, _node: &'ast SetOperator) {}
+    fn visit_select(&mut self, node: &'ast Select) {
+        visit_select(self, node)
+    }
+    fn visit_select_filter(&mut self, node: &'ast SelectFilter) {
+        visit_select_filter(self, node)
+    }
+    fn visit_select_expr(&mut self, node: &'ast SelectExpr) {
+        visit_select_expr(self, node)
+    }
+    fn visit_from_clause(&mut self, node: &'ast FromClause) {
+        visit_from_clause(self, node)
+    }
+    fn visit_group_by_clause(&mut self, node: &'ast GroupByClause) {
+        visit_group_by_clause(self, node)
+    }
+    fn visit_returning(&mut self, node: &'ast Returning) {
+        visit_returning(self, node)
+    }
+    fn visit_limit(&mut self, node: &'ast Limit) {
+        visit_limit(self, node)
+    }
+    fn visit_offset(&mut self, node: &'ast Offset) {
+        visit_offset(self, node)
+    }
+    fn visit_insert(&mut self, node: &'ast Insert) {
+        visit_insert(self, node)
+    }
+    fn visit_insert_kind(&mut self, node: &'ast InsertKind) {
+        visit_insert_kind(self, node)
+    }
+    fn visit_row(&mut self, node: &'ast Row) {
+        visit_row(self, node)
+    }
+    fn visit_insert_expr(&mut self, node: &'ast InsertExpr) {
+        visit_insert_expr(self, node)
+    }
+    fn visit_on_conflict(&mut self, node: &'ast OnConflict) {
+        visit_on_conflict(self, node)
+    }
+    fn visit_conflict_target(&mut self, node: &'ast ConflictTarget) {
+        visit_conflict_target(self, node)
+    }
+    fn visit_conflict_action(&mut self, node: &'ast ConflictAction) {
+        visit_conflict_action(self, node)
+    }
+    fn visit_delete(&mut self, node: &'ast Delete) {
+        visit_delete(self, node)
+    }
+    fn visit_where_clause(&mut self, node: &'ast WhereClause) {
+        visit_where_clause(self, node)
+    }
+    fn visit_update(&mut self, node: &'ast Update) {
+        visit_update(self, node)
+    }
+    fn visit_call(&mut self, node: &'ast Call) {
+        visit_call(self, node)
+    }
+    fn visit_assignment(&mut self, node: &'ast Assignment) {
+        visit_assignment(self, node)
+    }
+    fn visit_expr(&mut self, node: &'ast Expr) {
+        visit_expr(self, node)
+    }
+    fn visit_unary_operator(&mut self, _node: &'ast UnaryOperator) {}
+    fn visit_binary_operator(&mut self, _node: &'ast BinaryOperator) {}
+    fn visit_predicate(&mut self, node: &'ast Predicate) {
+        visit_predicate(self, node)
+    }
+    fn visit_is_predicate(&mut self, node: &'ast IsPredicate) {
+        visit_is_predicate(self, node)
+    }
+    fn visit_term(&mut self, node: &'ast Term) {
+        visit_term(self, node)
+    }
+    fn visit_value(&mut self, node: &'ast Value) {
+        visit_value(self, node)
+    }
+    fn visit_column(&mut self, node: &'ast Column<Name>) {
+        visit_column(self, node)
+    }
+    fn visit_name(&mut self, _node: &'ast Name) {}
+    fn visit_placeholder(&mut self, _node: &'ast Placeholder) {}
+    fn visit_table_name(&mut self, node: &'ast TableName) {
+        visit_table_name(self, node)
+    }
+    fn visit_table_ref(&mut self, node: &'ast TableRef) {
+        visit_table_ref(self, node)
+    }
+    fn visit_case(&mut self, node: &'ast Case) {
+        visit_case(self, node)
+    }
+    fn visit_when_clause(&mut self, node: &'ast WhenClause) {
+        visit_when_clause(self, node)
+    }
+    fn visit_cast(&mut self, node: &'ast Cast) {
+        visit_cast(self, node)
+    }
+    fn visit_type_name(&mut self, node: &'ast TypeName) {
+        visit_type_name(self, node)
+    }
+    fn visit_function_call(&mut self, node: &'ast FunctionCall) {
+        visit_function_call(self, node)
+    }
+    fn visit_function(&mut self, node: &'ast Function) {
+        visit_function(self, node)
+    }
+    fn visit_filter(&mut self, node: &'ast Filter) {
+        visit_filter(self, node)
+    }
+    fn visit_arg_list(&mut self, node: &'ast ArgList) {
+        visit_arg_list(self, node)
+    }
+    fn visit_substring_args(&mut self, node: &'ast SubstringArgs) {
+        visit_substring_args(self, node)
+    }
+    fn visit_trim_args(&mut self, node: &'ast TrimArgs) {
+        visit_trim_args(self, node)
+    }
+    fn visit_position_args(&mut self, node: &'ast PositionArgs) {
+        visit_position_args(self, node)
+    }
+    fn visit_window(&mut self, node: &'ast Window) {
+        visit_window(self, node)
+    }
+    fn visit_window_spec(&mut self, node: &'ast WindowSpec) {
+        visit_window_spec(self, node)
+    }
+    fn visit_window_clause(&mut self, node: &'ast WindowClause) {
+        visit_window_clause(self, node)
+    }
+    fn visit_window_definition(&mut self, node: &'ast WindowDefinition) {
+        visit_window_definition(self, node)
+    }
+    fn visit_partition_by_clause(&mut self, node: &'ast PartitionByClause) {
+        visit_partition_by_clause(self, node)
+    }
+    fn visit_order_by_clause(&mut self, node: &'ast OrderByClause) {
+        visit_order_by_clause(self, node)
+    }
+    fn visit_sort_spec(&mut self, node: &'ast SortSpec) {
+        visit_sort_spec(self, node)
+    }
+    fn visit_frame_clause(&mut self, node: &'ast FrameClause) {
+        visit_frame_clause(self, node)
+    }
+    fn visit_frame_bound(&mut self, node: &'ast FrameBound) {
+        visit_frame_bound(self, node)
+    }
+}
+
+pub fn visit_command<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast Command) {
+    match node {
+        Command::Select(query) => v.visit_query(query),
+        Command::Insert(insert) => v.visit_insert(insert),
+        Command::Delete(delete) => v.visit_delete(delete),
+        Command::Update(update) => v.visit_update(update),
+        Command::Call(call) => v.visit_call(call),
+    }
+}
+
+pub fn visit_query<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast Query) {
+    v.visit_set_expr(&node.body);
+    if let Some(order_by) = &node.order_by {
+        v.visit_order_by_clause(order_by);
+    }
+    if let Some(limit) = &node.limit {
+        v.visit_limit(limit);
+    }
+    if let Some(offset) = &node.offset {
+        v.visit_offset(offset);
+    }
+}
+
+pub fn visit_set_expr<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast SetExpr) {
+    match node {
+        SetExpr::Select(select) => v.visit_select(select),
+        SetExpr::Query { query, .. } => v.visit_query(query),
+        SetExpr::SetOperation { left, op, right } => {
+            v.visit_set_expr(left);
+            v.visit_set_operator(op);
+            v.visit_set_expr(right);
+        }
+    }
+}
+
+pub fn visit_select<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast Select) {
+    v.visit_select_filter(&node.filter);
+    for expr in node.exprs.iter() {
+        v.visit_select_expr(expr);
+    }
+    if let Some(from) = &node.from {
+        v.visit_from_clause(from);
+    }
+    if let Some(where_expr) = &node.where_expr {
+        v.visit_expr(where_expr);
+    }
+    if let Some(group_by) = &node.group_by {
+        v.visit_group_by_clause(group_by);
+    }
+    if let Some(having) = &node.having {
+        v.visit_expr(having);
+    }
+    if let Some(window) = &node.window {
+        v.visit_window_clause(window);
+    }
+}
+
+pub fn visit_select_filter<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast SelectFilter) {
+    if let SelectFilter::DistinctON { exprs, .. } = node {
+        for expr in exprs {
+            v.visit_expr(expr);
+        }
+    }
+}
+
+pub fn visit_select_expr<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast SelectExpr) {
+    match node {
+        SelectExpr::WildCard { symbol, except } => {
+            if let Some(schema_name) = &symbol.schema_name {
+                v.visit_name(schema_name);
+            }
+            if let Some(table_name) = &symbol.table_name {
+                v.visit_name(table_name);
+            }
+            for column in except {
+                v.visit_column(column);
+            }
+        }
+        SelectExpr::Expr { expr, alias } => {
+            v.visit_expr(expr);
+            if let Some(alias) = alias {
+                v.visit_name(alias);
+            }
+        }
+    }
+}
+
+pub fn visit_from_clause<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast FromClause) {
+    v.visit_table_ref(&node.table);
+}
+
+pub fn visit_group_by_clause<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast GroupByClause) {
+    for expr in node.exprs.iter() {
+        v.visit_expr(expr);
+    }
+}
+
+pub fn visit_returning<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast Returning) {
+    for expr in node.exprs.iter() {
+        v.visit_select_expr(expr);
+    }
+}
+
+pub fn visit_limit<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast Limit) {
+    if let Some(count) = &node.count {
+        v.visit_expr(count);
+    }
+}
+
+pub fn visit_offset<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast Offset) {
+    v.visit_expr(&node.count);
+}
+
+pub fn visit_insert<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast Insert) {
+    v.visit_table_name(&node.table_name);
+    for name in &node.column_name {
+        v.visit_name(name);
+    }
+    v.visit_insert_kind(&node.values);
+    if let Some(on_conflict) = &node.on_conflict {
+        v.visit_on_conflict(on_conflict);
+    }
+    if let Some(returning) = &node.returning {
+        v.visit_returning(returning);
+    }
+}
+
+pub fn visit_insert_kind<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast InsertKind) {
+    match node {
+        InsertKind::Values { rows, .. } => {
+            for row in rows {
+                v.visit_row(row);
+            }
+        }
+        InsertKind::Query(query) => v.visit_query(query),
+        InsertKind::DefaultValues(_) => {}
+    }
+}
+
+pub fn visit_row<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast Row) {
+    match node {
+        Row::InsertExpr(expr) => v.visit_insert_expr(expr),
+        Row::Row(_, exprs) => {
+            for expr in exprs {
+                v.visit_insert_expr(expr);
+            }
+        }
+    }
+}
+
+pub fn visit_insert_expr<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast InsertExpr) {
+    if let InsertExpr::Insert(expr) = node {
+        v.visit_expr(expr);
+    }
+}
+
+pub fn visit_on_conflict<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast OnConflict) {
+    if let Some(target) = &node.target {
+        v.visit_conflict_target(target);
+    }
+    v.visit_conflict_action(&node.action);
+}
+
+pub fn visit_conflict_target<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast ConflictTarget) {
+    match node {
+        ConflictTarget::Columns {
+            columns,
+            where_expr,
+            ..
+        } => {
+            for name in columns {
+                v.visit_name(name);
+            }
+            if let Some(where_expr) = where_expr {
+                v.visit_expr(where_expr);
+            }
+        }
+        ConflictTarget::Constraint { name, .. } => v.visit_name(name),
+    }
+}
+
+pub fn visit_conflict_action<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast ConflictAction) {
+    if let ConflictAction::Update {
+        assignments,
+        where_expr,
+        ..
+    } = node
+    {
+        for assignment in assignments.iter() {
+            v.visit_assignment(assignment);
+        }
+        if let Some(where_expr) = where_expr {
+            v.visit_expr(where_expr);
+        }
+    }
+}
+
+pub fn visit_delete<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast Delete) {
+    v.visit_table_name(&node.table_name);
+    if let Some(alias) = &node.alias {
+        v.visit_name(alias);
+    }
+    if let Some((_, tables)) = &node.using {
+        for table in tables.iter() {
+            v.visit_table_ref(table);
+        }
+    }
+    if let Some(where_clause) = &node.where_clause {
+        v.visit_where_clause(where_clause);
+    }
+    if let Some(returning) = &node.returning {
+        v.visit_returning(returning);
+    }
+}
+
+pub fn visit_where_clause<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast WhereClause) {
+    match node {
+        WhereClause::Expr(expr) => v.visit_expr(expr),
+        WhereClause::CurrentOf { cursor_name, .. } => v.visit_name(cursor_name),
+    }
+}
+
+pub fn visit_update<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast Update) {
+    v.visit_table_name(&node.table_name);
+    if let Some(alias) = &node.alias {
+        v.visit_name(alias);
+    }
+    for assignment in node.assignments.iter() {
+        v.visit_assignment(assignment);
+    }
+    if let Some((_, table)) = &node.from {
+        v.visit_table_ref(table);
+    }
+    if let Some(where_expr) = &node.where_expr {
+        v.visit_expr(where_expr);
+    }
+    if let Some(returning) = &node.returning {
+        v.visit_returning(returning);
+    }
+}
+
+pub fn visit_call<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast Call) {
+    v.visit_function(&node.procedure);
+}
+
+pub fn visit_assignment<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast Assignment) {
+    match node {
+        Assignment::Single { column, value, .. } => {
+            v.visit_name(column);
+            v.visit_insert_expr(value);
+        }
+        Assignment::Multiple {
+            columns, values, ..
+        } => {
+            for name in columns {
+                v.visit_name(name);
+            }
+            for value in values {
+                v.visit_insert_expr(value);
+            }
+        }
+    }
+}
+
+pub fn visit_expr<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast Expr) {
+    match node {
+        Expr::Term(term) => v.visit_term(term),
+        Expr::Unary { op, expr } => {
+            v.visit_unary_operator(op);
+            v.visit_expr(expr);
+        }
+        Expr::Binary { left, op, right } => {
+            v.visit_expr(left);
+            v.visit_binary_operator(op);
+            v.visit_expr(right);
+        }
+        Expr::Predicate { expr, predicate } => {
+            v.visit_expr(expr);
+            v.visit_predicate(predicate);
+        }
+    }
+}
+
+pub fn visit_predicate<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast Predicate) {
+    match node {
+        Predicate::In { list, .. } => {
+            for expr in list {
+                v.visit_expr(expr);
+            }
+        }
+        Predicate::Between { low, high, .. } => {
+            v.visit_expr(low);
+            v.visit_expr(high);
+        }
+        Predicate::Like {
+            pattern, escape, ..
+        } => {
+            v.visit_expr(pattern);
+            if let Some((_, escape)) = escape {
+                v.visit_expr(escape);
+            }
+        }
+        Predicate::Is { predicate, .. } => v.visit_is_predicate(predicate),
+    }
+}
+
+pub fn visit_is_predicate<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast IsPredicate) {
+    if let IsPredicate::DistinctFrom { expr, .. } = node {
+        v.visit_expr(expr);
+    }
+}
+
+pub fn visit_term<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast Term) {
+    match node {
+        Term::Value(value) => v.visit_value(value),
+        Term::Column(column) => v.visit_column(column),
+        Term::Func(call) => v.visit_function_call(call),
+        Term::Expr(expr) => v.visit_expr(expr),
+        Term::Case(case) => v.visit_case(case),
+        Term::Cast(cast) => v.visit_cast(cast),
+        Term::Placeholder(placeholder) => v.visit_placeholder(placeholder),
+    }
+}
+
+pub fn visit_value<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast Value) {
+    if let Value::ARRAY(exprs) = node {
+        for expr in exprs.iter() {
+            v.visit_expr(expr);
+        }
+    }
+}
+
+pub fn visit_column<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast Column<Name>) {
+    if let Some(schema_name) = &node.schema_name {
+        v.visit_name(schema_name);
+    }
+    if let Some(table_name) = &node.table_name {
+        v.visit_name(table_name);
+    }
+    v.visit_name(&node.alias);
+}
+
+pub fn visit_table_name<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast TableName) {
+    if let Some(schema_name) = &node.schema_name {
+        v.visit_name(schema_name);
+    }
+    v.visit_name(&node.alias);
+}
+
+pub fn visit_table_ref<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast TableRef) {
+    v.visit_table_name(&node.table_name);
+    if let Some(alias) = &node.alias {
+        v.visit_name(alias);
+    }
+}
+
+pub fn visit_case<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast Case) {
+    if let Some(operand) = &node.operand {
+        v.visit_expr(operand);
+    }
+    for clause in &node.when_clauses {
+        v.visit_when_clause(clause);
+    }
+    if let Some((_, expr)) = &node.else_clause {
+        v.visit_expr(expr);
+    }
+}
+
+pub fn visit_when_clause<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast WhenClause) {
+    v.visit_expr(&node.condition);
+    v.visit_expr(&node.result);
+}
+
+pub fn visit_cast<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast Cast) {
+    v.visit_term(&node.expr);
+    v.visit_type_name(&node.type_name);
+}
+
+pub fn visit_type_name<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast TypeName) {
+    if let Some(schema_name) = &node.schema_name {
+        v.visit_name(schema_name);
+    }
+    v.visit_name(&node.name);
+}
+
+pub fn visit_function_call<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast FunctionCall) {
+    v.visit_function(&node.function);
+    if let Some(filter) = &node.filter {
+        v.visit_filter(filter);
+    }
+    if let Some(over) = &node.over {
+        v.visit_window(over);
+    }
+}
+
+pub fn visit_function<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast Function) {
+    match &node.args {
+        FunctionArgs::List(list) => v.visit_arg_list(list),
+        FunctionArgs::Substring(args) => v.visit_substring_args(args),
+        FunctionArgs::Trim(args) => v.visit_trim_args(args),
+        FunctionArgs::Position(args) => v.visit_position_args(args),
+    }
+}
+
+pub fn visit_filter<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast Filter) {
+    v.visit_expr(&node.condition);
+}
+
+pub fn visit_arg_list<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast ArgList) {
+    if let ArgItems::Exprs(exprs) = &node.args {
+        for expr in exprs.iter() {
+            v.visit_expr(expr);
+        }
+    }
+    if let Some(order_by) = &node.order_by {
+        v.visit_order_by_clause(order_by);
+    }
+}
+
+pub fn visit_substring_args<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast SubstringArgs) {
+    v.visit_expr(&node.string);
+    if let Some(start) = &node.start {
+        v.visit_expr(start);
+    }
+    if let Some(count) = &node.count {
+        v.visit_expr(count);
+    }
+}
+
+pub fn visit_trim_args<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast TrimArgs) {
+    if let Some(characters) = &node.characters {
+        v.visit_expr(characters);
+    }
+    v.visit_expr(&node.string);
+}
+
+pub fn visit_position_args<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast PositionArgs) {
+    v.visit_expr(&node.substring);
+    v.visit_expr(&node.string);
+}
+
+pub fn visit_window<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast Window) {
+    match &node.name_or_spec {
+        NameOrSpec::Name(name) => v.visit_name(name),
+        NameOrSpec::Spec(spec) => v.visit_window_spec(spec),
+    }
+}
+
+pub fn visit_window_spec<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast WindowSpec) {
+    if let Some(name) = &node.existing_window_name {
+        v.visit_name(name);
+    }
+    if let Some(partition_by) = &node.partition_by {
+        v.visit_partition_by_clause(partition_by);
+    }
+    if let Some(order_by) = &node.order_by {
+        v.visit_order_by_clause(order_by);
+    }
+    if let Some(frame) = &node.frame {
+        v.visit_frame_clause(frame);
+    }
+}
+
+pub fn visit_window_clause<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast WindowClause) {
+    for definition in node.definitions.iter() {
+        v.visit_window_definition(definition);
+    }
+}
+
+pub fn visit_window_definition<'ast, V: Visit<'ast> + ?Sized>(
+    v: &mut V,
+    node: &'ast WindowDefinition,
+) {
+    v.visit_name(&node.name);
+    v.visit_window_spec(&node.spec);
+}
+
+pub fn visit_partition_by_clause<'ast, V: Visit<'ast> + ?Sized>(
+    v: &mut V,
+    node: &'ast PartitionByClause,
+) {
+    for expr in node.exprs.iter() {
+        v.visit_expr(expr);
+    }
+}
+
+pub fn visit_order_by_clause<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast OrderByClause) {
+    for spec in node.sort_specs.iter() {
+        v.visit_sort_spec(spec);
+    }
+}
+
+pub fn visit_sort_spec<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast SortSpec) {
+    v.visit_expr(&node.expr);
+}
+
+pub fn visit_frame_clause<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast FrameClause) {
+    v.visit_frame_bound(&node.start);
+    if let Some(end) = &node.end {
+        v.visit_frame_bound(end);
+    }
+}
+
+pub fn visit_frame_bound<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, node: &'ast FrameBound) {
+    if let FrameBound::Preceding(offset) | FrameBound::Following(offset) = node {
+        v.visit_expr(offset);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Default)]
+    struct Collect {
+        columns: Vec<String>,
+        functions: Vec<String>,
+    }
+
+    impl<'ast> Visit<'ast> for Collect {
+        fn visit_column(&mut self, node: &'ast Column<Name>) {
+            self.columns.push(node.alias.to_string());
+        }
+
+        fn visit_function_call(&mut self, node: &'ast FunctionCall) {
+            self.functions.push(node.function.name());
+            visit_function_call(self, node);
+        }
+    }
+
+    #[test]
+    fn walk() {
+        let command: Command = syn::parse_str(
+            "SELECT a, upper(TRIM(b FROM c)), count(*) FILTER (WHERE d) OVER (PARTITION BY e)
+            FROM t WHERE f IN (g, CASE WHEN h THEN i END::text) ORDER BY j LIMIT k",
+        )
+        .unwrap();
+        let mut collect = Collect::default();
+        collect.visit_command(&command);
+        assert_eq!(
+            collect.columns,
+            ["a", "b", "c", "d", "e", "f", "g", "h", "i", "j", "k"]
+        );
+        assert_eq!(collect.functions, ["upper", "trim", "count"]);
+    }
+}
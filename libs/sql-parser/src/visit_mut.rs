@@ -0,0 +1,713 @@
+//! Mutable traversal of the syntax tree, to rewrite it in place.
+//!
+//! Same as [`crate::visit`], each `visit_*_mut` method defaults to the free function
+//! of the same name which walks into the children of the node.
+use crate::command::{call::*, delete::*, insert::*, select::*, update::*, Command};
+use crate::function::*;
+use crate::grammar::{ast::*, *};
+use crate::window::*;
+
+pub trait VisitMut {
+    fn visit_command_mut(&mut self, node: &mut Command) {
+        visit_command_mut(self, node)
+    }
+    fn visit_query_mut(&mut self, node: &mut Query) {
+        visit_query_mut(self, node)
+    }
+    fn visit_set_expr_mut(&mut self, node: &mut SetExpr) {
+        visit_set_expr_mut(self, node)
+    }
+    fn visit_set_operator_mut(&mut self, _node: &mut SetOperator) {}
+    fn visit_select_mut(&mut self, node: &mut Select) {
+        visit_select_mut(self, node)
+    }
+    fn visit_select_filter_mut(&mut self, node: &mut SelectFilter) {
+        visit_select_filter_mut(self, node)
+    }
+    fn visit_select_expr_mut(&mut self, node: &mut SelectExpr) {
+        visit_select_expr_mut(self, node)
+    }
+    fn visit_from_clause_mut(&mut self, node: &mut FromClause) {
+        visit_from_clause_mut(self, node)
+    }
+    fn visit_group_by_clause_mut(&mut self, node: &mut GroupByClause) {
+        visit_group_by_clause_mut(self, node)
+    }
+    fn visit_returning_mut(&mut self, node: &mut Returning) {
+        visit_returning_mut(self, node)
+    }
+    fn visit_limit_mut(&mut self, node: &mut Limit) {
+        visit_limit_mut(self, node)
+    }
+    fn visit_offset_mut(&mut self, node: &mut Offset) {
+        visit_offset_mut(self, node)
+    }
+    fn visit_insert_mut(&mut self, node: &mut Insert) {
+        visit_insert_mut(self, node)
+    }
+    fn visit_insert_kind_mut(&mut self, node: &mut InsertKind) {
+        visit_insert_kind_mut(self, node)
+    }
+    fn visit_row_mut(&mut self, node: &mut Row) {
+        visit_row_mut(self, node)
+    }
+    fn visit_insert_expr_mut(&mut self, node: &mut InsertExpr) {
+        visit_insert_expr_mut(self, node)
+    }
+    fn visit_on_conflict_mut(&mut self, node: &mut OnConflict) {
+        visit_on_conflict_mut(self, node)
+    }
+    fn visit_conflict_target_mut(&mut self, node: &mut ConflictTarget) {
+        visit_conflict_target_mut(self, node)
+    }
+    fn visit_conflict_action_mut(&mut self, node: &mut ConflictAction) {
+        visit_conflict_action_mut(self, node)
+    }
+    fn visit_delete_mut(&mut self, node: &mut Delete) {
+        visit_delete_mut(self, node)
+    }
+    fn visit_where_clause_mut(&mut self, node: &mut WhereClause) {
+        visit_where_clause_mut(self, node)
+    }
+    fn visit_update_mut(&mut self, node: &mut Update) {
+        visit_update_mut(self, node)
+    }
+    fn visit_call_mut(&mut self, node: &mut Call) {
+        visit_call_mut(self, node)
+    }
+    fn visit_assignment_mut(&mut self, node: &mut Assignment) {
+        visit_assignment_mut(self, node)
+    }
+    fn visit_expr_mut(&mut self, node: &mut Expr) {
+        visit_expr_mut(self, node)
+    }
+    fn visit_unary_operator_mut(&mut self, _node: &mut UnaryOperator) {}
+    fn visit_binary_operator_mut(&mut self, _node: &mut BinaryOperator) {}
+    fn visit_predicate_mut(&mut self, node: &mut Predicate) {
+        visit_predicate_mut(self, node)
+    }
+    fn visit_is_predicate_mut(&mut self, node: &mut IsPredicate) {
+        visit_is_predicate_mut(self, node)
+    }
+    fn visit_term_mut(&mut self, node: &mut Term) {
+        visit_term_mut(self, node)
+    }
+    fn visit_value_mut(&mut self, node: &mut Value) {
+        visit_value_mut(self, node)
+    }
+    fn visit_column_mut(&mut self, node: &mut Column<Name>) {
+        visit_column_mut(self, node)
+    }
+    fn visit_name_mut(&mut self, _node: &mut Name) {}
+    fn visit_placeholder_mut(&mut self, _node: &mut Placeholder) {}
+    fn visit_table_name_mut(&mut self, node: &mut TableName) {
+        visit_table_name_mut(self, node)
+    }
+    fn visit_table_ref_mut(&mut self, node: &mut TableRef) {
+        visit_table_ref_mut(self, node)
+    }
+    fn visit_case_mut(&mut self, node: &mut Case) {
+        visit_case_mut(self, node)
+    }
+    fn visit_when_clause_mut(&mut self, node: &mut WhenClause) {
+        visit_when_clause_mut(self, node)
+    }
+    fn visit_cast_mut(&mut self, node: &mut Cast) {
+        visit_cast_mut(self, node)
+    }
+    fn visit_type_name_mut(&mut self, node: &mut TypeName) {
+        visit_type_name_mut(self, node)
+    }
+    fn visit_function_call_mut(&mut self, node: &mut FunctionCall) {
+        visit_function_call_mut(self, node)
+    }
+    fn visit_function_mut(&mut self, node: &mut Function) {
+        visit_function_mut(self, node)
+    }
+    fn visit_filter_mut(&mut self, node: &mut Filter) {
+        visit_filter_mut(self, node)
+    }
+    fn visit_arg_list_mut(&mut self, node: &mut ArgList) {
+        visit_arg_list_mut(self, node)
+    }
+    fn visit_substring_args_mut(&mut self, node: &mut SubstringArgs) {
+        visit_substring_args_mut(self, node)
+    }
+    fn visit_trim_args_mut(&mut self, node: &mut TrimArgs) {
+        visit_trim_args_mut(self, node)
+    }
+    fn visit_position_args_mut(&mut self, node: &mut PositionArgs) {
+        visit_position_args_mut(self, node)
+    }
+    fn visit_window_mut(&mut self, node: &mut Window) {
+        visit_window_mut(self, node)
+    }
+    fn visit_window_spec_mut(&mut self, node: &mut WindowSpec) {
+        visit_window_spec_mut(self, node)
+    }
+    fn visit_window_clause_mut(&mut self, node: &mut WindowClause) {
+        visit_window_clause_mut(self, node)
+    }
+    fn visit_window_definition_mut(&mut self, node: &mut WindowDefinition) {
+        visit_window_definition_mut(self, node)
+    }
+    fn visit_partition_by_clause_mut(&mut self, node: &mut PartitionByClause) {
+        visit_partition_by_clause_mut(self, node)
+    }
+    fn visit_order_by_clause_mut(&mut self, node: &mut OrderByClause) {
+        visit_order_by_clause_mut(self, node)
+    }
+    fn visit_sort_spec_mut(&mut self, node: &mut SortSpec) {
+        visit_sort_spec_mut(self, node)
+    }
+    fn visit_frame_clause_mut(&mut self, node: &mut FrameClause) {
+        visit_frame_clause_mut(self, node)
+    }
+    fn visit_frame_bound_mut(&mut self, node: &mut FrameBound) {
+        visit_frame_bound_mut(self, node)
+    }
+}
+
+pub fn visit_command_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut Command) {
+    match node {
+        Command::Select(query) => v.visit_query_mut(query),
+        Command::Insert(insert) => v.visit_insert_mut(insert),
+        Command::Delete(delete) => v.visit_delete_mut(delete),
+        Command::Update(update) => v.visit_update_mut(update),
+        Command::Call(call) => v.visit_call_mut(call),
+    }
+}
+
+pub fn visit_query_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut Query) {
+    v.visit_set_expr_mut(&mut node.body);
+    if let Some(order_by) = &mut node.order_by {
+        v.visit_order_by_clause_mut(order_by);
+    }
+    if let Some(limit) = &mut node.limit {
+        v.visit_limit_mut(limit);
+    }
+    if let Some(offset) = &mut node.offset {
+        v.visit_offset_mut(offset);
+    }
+}
+
+pub fn visit_set_expr_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut SetExpr) {
+    match node {
+        SetExpr::Select(select) => v.visit_select_mut(select),
+        SetExpr::Query { query, .. } => v.visit_query_mut(query),
+        SetExpr::SetOperation { left, op, right } => {
+            v.visit_set_expr_mut(left);
+            v.visit_set_operator_mut(op);
+            v.visit_set_expr_mut(right);
+        }
+    }
+}
+
+pub fn visit_select_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut Select) {
+    v.visit_select_filter_mut(&mut node.filter);
+    for expr in node.exprs.iter_mut() {
+        v.visit_select_expr_mut(expr);
+    }
+    if let Some(from) = &mut node.from {
+        v.visit_from_clause_mut(from);
+    }
+    if let Some(where_expr) = &mut node.where_expr {
+        v.visit_expr_mut(where_expr);
+    }
+    if let Some(group_by) = &mut node.group_by {
+        v.visit_group_by_clause_mut(group_by);
+    }
+    if let Some(having) = &mut node.having {
+        v.visit_expr_mut(having);
+    }
+    if let Some(window) = &mut node.window {
+        v.visit_window_clause_mut(window);
+    }
+}
+
+pub fn visit_select_filter_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut SelectFilter) {
+    if let SelectFilter::DistinctON { exprs, .. } = node {
+        for expr in exprs {
+            v.visit_expr_mut(expr);
+        }
+    }
+}
+
+pub fn visit_select_expr_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut SelectExpr) {
+    match node {
+        SelectExpr::WildCard { symbol, except } => {
+            if let Some(schema_name) = &mut symbol.schema_name {
+                v.visit_name_mut(schema_name);
+            }
+            if let Some(table_name) = &mut symbol.table_name {
+                v.visit_name_mut(table_name);
+            }
+            for column in except {
+                v.visit_column_mut(column);
+            }
+        }
+        SelectExpr::Expr { expr, alias } => {
+            v.visit_expr_mut(expr);
+            if let Some(alias) = alias {
+                v.visit_name_mut(alias);
+            }
+        }
+    }
+}
+
+pub fn visit_from_clause_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut FromClause) {
+    v.visit_table_ref_mut(&mut node.table);
+}
+
+pub fn visit_group_by_clause_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut GroupByClause) {
+    for expr in node.exprs.iter_mut() {
+        v.visit_expr_mut(expr);
+    }
+}
+
+pub fn visit_returning_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut Returning) {
+    for expr in node.exprs.iter_mut() {
+        v.visit_select_expr_mut(expr);
+    }
+}
+
+pub fn visit_limit_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut Limit) {
+    if let Some(count) = &mut node.count {
+        v.visit_expr_mut(count);
+    }
+}
+
+pub fn visit_offset_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut Offset) {
+    v.visit_expr_mut(&mut node.count);
+}
+
+pub fn visit_insert_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut Insert) {
+    v.visit_table_name_mut(&mut node.table_name);
+    for name in &mut node.column_name {
+        v.visit_name_mut(name);
+    }
+    v.visit_insert_kind_mut(&mut node.values);
+    if let Some(on_conflict) = &mut node.on_conflict {
+        v.visit_on_conflict_mut(on_conflict);
+    }
+    if let Some(returning) = &mut node.returning {
+        v.visit_returning_mut(returning);
+    }
+}
+
+pub fn visit_insert_kind_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut InsertKind) {
+    match node {
+        InsertKind::Values { rows, .. } => {
+            for row in rows {
+                v.visit_row_mut(row);
+            }
+        }
+        InsertKind::Query(query) => v.visit_query_mut(query),
+        InsertKind::DefaultValues(_) => {}
+    }
+}
+
+pub fn visit_row_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut Row) {
+    match node {
+        Row::InsertExpr(expr) => v.visit_insert_expr_mut(expr),
+        Row::Row(_, exprs) => {
+            for expr in exprs {
+                v.visit_insert_expr_mut(expr);
+            }
+        }
+    }
+}
+
+pub fn visit_insert_expr_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut InsertExpr) {
+    if let InsertExpr::Insert(expr) = node {
+        v.visit_expr_mut(expr);
+    }
+}
+
+pub fn visit_on_conflict_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut OnConflict) {
+    if let Some(target) = &mut node.target {
+        v.visit_conflict_target_mut(target);
+    }
+    v.visit_conflict_action_mut(&mut node.action);
+}
+
+pub fn visit_conflict_target_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut ConflictTarget) {
+    match node {
+        ConflictTarget::Columns {
+            columns,
+            where_expr,
+            ..
+        } => {
+            for name in columns {
+                v.visit_name_mut(name);
+            }
+            if let Some(where_expr) = where_expr {
+                v.visit_expr_mut(where_expr);
+            }
+        }
+        ConflictTarget::Constraint { name, .. } => v.visit_name_mut(name),
+    }
+}
+
+pub fn visit_conflict_action_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut ConflictAction) {
+    if let ConflictAction::Update {
+        assignments,
+        where_expr,
+        ..
+    } = node
+    {
+        for assignment in assignments.iter_mut() {
+            v.visit_assignment_mut(assignment);
+        }
+        if let Some(where_expr) = where_expr {
+            v.visit_expr_mut(where_expr);
+        }
+    }
+}
+
+pub fn visit_delete_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut Delete) {
+    v.visit_table_name_mut(&mut node.table_name);
+    if let Some(alias) = &mut node.alias {
+        v.visit_name_mut(alias);
+    }
+    if let Some((_, tables)) = &mut node.using {
+        for table in tables.iter_mut() {
+            v.visit_table_ref_mut(table);
+        }
+    }
+    if let Some(where_clause) = &mut node.where_clause {
+        v.visit_where_clause_mut(where_clause);
+    }
+    if let Some(returning) = &mut node.returning {
+        v.visit_returning_mut(returning);
+    }
+}
+
+pub fn visit_where_clause_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut WhereClause) {
+    match node {
+        WhereClause::Expr(expr) => v.visit_expr_mut(expr),
+        WhereClause::CurrentOf { cursor_name, .. } => v.visit_name_mut(cursor_name),
+    }
+}
+
+pub fn visit_update_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut Update) {
+    v.visit_table_name_mut(&mut node.table_name);
+    if let Some(alias) = &mut node.alias {
+        v.visit_name_mut(alias);
+    }
+    for assignment in node.assignments.iter_mut() {
+        v.visit_assignment_mut(assignment);
+    }
+    if let Some((_, table)) = &mut node.from {
+        v.visit_table_ref_mut(table);
+    }
+    if let Some(where_expr) = &mut node.where_expr {
+        v.visit_expr_mut(where_expr);
+    }
+    if let Some(returning) = &mut node.returning {
+        v.visit_returning_mut(returning);
+    }
+}
+
+pub fn visit_call_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut Call) {
+    v.visit_function_mut(&mut node.procedure);
+}
+
+pub fn visit_assignment_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut Assignment) {
+    match node {
+        Assignment::Single { column, value, .. } => {
+            v.visit_name_mut(column);
+            v.visit_insert_expr_mut(value);
+        }
+        Assignment::Multiple {
+            columns, values, ..
+        } => {
+            for name in columns {
+                v.visit_name_mut(name);
+            }
+            for value in values {
+                v.visit_insert_expr_mut(value);
+            }
+        }
+    }
+}
+
+pub fn visit_expr_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut Expr) {
+    match node {
+        Expr::Term(term) => v.visit_term_mut(term),
+        Expr::Unary { op, expr } => {
+            v.visit_unary_operator_mut(op);
+            v.visit_expr_mut(expr);
+        }
+        Expr::Binary { left, op, right } => {
+            v.visit_expr_mut(left);
+            v.visit_binary_operator_mut(op);
+            v.visit_expr_mut(right);
+        }
+        Expr::Predicate { expr, predicate } => {
+            v.visit_expr_mut(expr);
+            v.visit_predicate_mut(predicate);
+        }
+    }
+}
+
+pub fn visit_predicate_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut Predicate) {
+    match node {
+        Predicate::In { list, .. } => {
+            for expr in list {
+                v.visit_expr_mut(expr);
+            }
+        }
+        Predicate::Between { low, high, .. } => {
+            v.visit_expr_mut(low);
+            v.visit_expr_mut(high);
+        }
+        Predicate::Like {
+            pattern, escape, ..
+        } => {
+            v.visit_expr_mut(pattern);
+            if let Some((_, escape)) = escape {
+                v.visit_expr_mut(escape);
+            }
+        }
+        Predicate::Is { predicate, .. } => v.visit_is_predicate_mut(predicate),
+    }
+}
+
+pub fn visit_is_predicate_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut IsPredicate) {
+    if let IsPredicate::DistinctFrom { expr, .. } = node {
+        v.visit_expr_mut(expr);
+    }
+}
+
+pub fn visit_term_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut Term) {
+    match node {
+        Term::Value(value) => v.visit_value_mut(value),
+        Term::Column(column) => v.visit_column_mut(column),
+        Term::Func(call) => v.visit_function_call_mut(call),
+        Term::Expr(expr) => v.visit_expr_mut(expr),
+        Term::Case(case) => v.visit_case_mut(case),
+        Term::Cast(cast) => v.visit_cast_mut(cast),
+        Term::Placeholder(placeholder) => v.visit_placeholder_mut(placeholder),
+    }
+}
+
+pub fn visit_value_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut Value) {
+    if let Value::ARRAY(exprs) = node {
+        for expr in exprs.iter_mut() {
+            v.visit_expr_mut(expr);
+        }
+    }
+}
+
+pub fn visit_column_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut Column<Name>) {
+    if let Some(schema_name) = &mut node.schema_name {
+        v.visit_name_mut(schema_name);
+    }
+    if let Some(table_name) = &mut node.table_name {
+        v.visit_name_mut(table_name);
+    }
+    v.visit_name_mut(&mut node.alias);
+}
+
+pub fn visit_table_name_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut TableName) {
+    if let Some(schema_name) = &mut node.schema_name {
+        v.visit_name_mut(schema_name);
+    }
+    v.visit_name_mut(&mut node.alias);
+}
+
+pub fn visit_table_ref_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut TableRef) {
+    v.visit_table_name_mut(&mut node.table_name);
+    if let Some(alias) = &mut node.alias {
+        v.visit_name_mut(alias);
+    }
+}
+
+pub fn visit_case_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut Case) {
+    if let Some(operand) = &mut node.operand {
+        v.visit_expr_mut(operand);
+    }
+    for clause in &mut node.when_clauses {
+        v.visit_when_clause_mut(clause);
+    }
+    if let Some((_, expr)) = &mut node.else_clause {
+        v.visit_expr_mut(expr);
+    }
+}
+
+pub fn visit_when_clause_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut WhenClause) {
+    v.visit_expr_mut(&mut node.condition);
+    v.visit_expr_mut(&mut node.result);
+}
+
+pub fn visit_cast_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut Cast) {
+    v.visit_term_mut(&mut node.expr);
+    v.visit_type_name_mut(&mut node.type_name);
+}
+
+pub fn visit_type_name_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut TypeName) {
+    if let Some(schema_name) = &mut node.schema_name {
+        v.visit_name_mut(schema_name);
+    }
+    v.visit_name_mut(&mut node.name);
+}
+
+pub fn visit_function_call_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut FunctionCall) {
+    v.visit_function_mut(&mut node.function);
+    if let Some(filter) = &mut node.filter {
+        v.visit_filter_mut(filter);
+    }
+    if let Some(over) = &mut node.over {
+        v.visit_window_mut(over);
+    }
+}
+
+pub fn visit_function_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut Function) {
+    match &mut node.args {
+        FunctionArgs::List(list) => v.visit_arg_list_mut(list),
+        FunctionArgs::Substring(args) => v.visit_substring_args_mut(args),
+        FunctionArgs::Trim(args) => v.visit_trim_args_mut(args),
+        FunctionArgs::Position(args) => v.visit_position_args_mut(args),
+    }
+}
+
+pub fn visit_filter_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut Filter) {
+    v.visit_expr_mut(&mut node.condition);
+}
+
+pub fn visit_arg_list_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut ArgList) {
+    if let ArgItems::Exprs(exprs) = &mut node.args {
+        for expr in exprs.iter_mut() {
+            v.visit_expr_mut(expr);
+        }
+    }
+    if let Some(order_by) = &mut node.order_by {
+        v.visit_order_by_clause_mut(order_by);
+    }
+}
+
+pub fn visit_substring_args_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut SubstringArgs) {
+    v.visit_expr_mut(&mut node.string);
+    if let Some(start) = &mut node.start {
+        v.visit_expr_mut(start);
+    }
+    if let Some(count) = &mut node.count {
+        v.visit_expr_mut(count);
+    }
+}
+
+pub fn visit_trim_args_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut TrimArgs) {
+    if let Some(characters) = &mut node.characters {
+        v.visit_expr_mut(characters);
+    }
+    v.visit_expr_mut(&mut node.string);
+}
+
+pub fn visit_position_args_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut PositionArgs) {
+    v.visit_expr_mut(&mut node.substring);
+    v.visit_expr_mut(&mut node.string);
+}
+
+pub fn visit_window_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut Window) {
+    match &mut node.name_or_spec {
+        NameOrSpec::Name(name) => v.visit_name_mut(name),
+        NameOrSpec::Spec(spec) => v.visit_window_spec_mut(spec),
+    }
+}
+
+pub fn visit_window_spec_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut WindowSpec) {
+    if let Some(name) = &mut node.existing_window_name {
+        v.visit_name_mut(name);
+    }
+    if let Some(partition_by) = &mut node.partition_by {
+        v.visit_partition_by_clause_mut(partition_by);
+    }
+    if let Some(order_by) = &mut node.order_by {
+        v.visit_order_by_clause_mut(order_by);
+    }
+    if let Some(frame) = &mut node.frame {
+        v.visit_frame_clause_mut(frame);
+    }
+}
+
+pub fn visit_window_clause_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut WindowClause) {
+    for definition in node.definitions.iter_mut() {
+        v.visit_window_definition_mut(definition);
+    }
+}
+
+pub fn visit_window_definition_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut WindowDefinition) {
+    v.visit_name_mut(&mut node.name);
+    v.visit_window_spec_mut(&mut node.spec);
+}
+
+pub fn visit_partition_by_clause_mut<V: VisitMut + ?Sized>(
+    v: &mut V,
+    node: &mut PartitionByClause,
+) {
+    for expr in node.exprs.iter_mut() {
+        v.visit_expr_mut(expr);
+    }
+}
+
+pub fn visit_order_by_clause_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut OrderByClause) {
+    for spec in node.sort_specs.iter_mut() {
+        v.visit_sort_spec_mut(spec);
+    }
+}
+
+pub fn visit_sort_spec_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut SortSpec) {
+    v.visit_expr_mut(&mut node.expr);
+}
+
+pub fn visit_frame_clause_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut FrameClause) {
+    v.visit_frame_bound_mut(&mut node.start);
+    if let Some(end) = &mut node.end {
+        v.visit_frame_bound_mut(end);
+    }
+}
+
+pub fn visit_frame_bound_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut FrameBound) {
+    if let FrameBound::Preceding(offset) | FrameBound::Following(offset) = node {
+        v.visit_expr_mut(offset);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::visit::Visit;
+    use proc_macro2::{Ident, Span};
+
+    /// Renames table `a` to `b`, wherever it's referred to.
+    struct Rename;
+
+    impl VisitMut for Rename {
+        fn visit_name_mut(&mut self, node: &mut Name) {
+            if node.to_string() == "a" {
+                *node = Name::Ident(Ident::new("b", Span::call_site()));
+            }
+        }
+    }
+
+    #[test]
+    fn rewrite() {
+        let mut command: Command =
+            syn::parse_str("UPDATE a SET x = a.y FROM c WHERE c.id = a.id RETURNING a.*").unwrap();
+        Rename.visit_command_mut(&mut command);
+
+        struct Names(Vec<String>);
+        impl<'ast> Visit<'ast> for Names {
+            fn visit_name(&mut self, node: &'ast Name) {
+                self.0.push(node.to_string());
+            }
+        }
+        let mut names = Names(vec![]);
+        names.visit_command(&command);
+        assert_eq!(
+            names.0,
+            ["b", "x", "b", "y", "c", "c", "id", "b", "id", "b"]
+        );
+    }
+}
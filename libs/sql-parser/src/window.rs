@@ -1,103 +1,291 @@
 use self::{
-    grammar::ast::OrExpr,
-    utils::{parse_keywords_if_matched, parse_kw_if_matched},
+    grammar::ast::Expr,
+    utils::{parse_keywords_if_matched, parse_kw_if_matched, peek_kw},
 };
 use crate::*;
 use grammar::Name;
 use syn::token;
 use utils::SeparatedByComma;
 
+/// `OVER { <window_name> | ( <window_spec> ) }`
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Window {
+    #[cfg_attr(feature = "serde", serde(with = "crate::tokens"))]
     pub over_kw: Ident,
     pub name_or_spec: NameOrSpec,
 }
 
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum NameOrSpec {
     Name(Name),
-    Spec {
-        paren_token: token::Paren,
-        existing_window_name: Option<Name>,
-        partition_by: Option<PartitionByClause>,
-        order_by: Option<OrderByClause>,
-    },
+    Spec(Box<WindowSpec>),
 }
 
+/// Ref: https://www.postgresql.org/docs/current/sql-expressions.html#SYNTAX-WINDOW-FUNCTIONS
+///
+/// ```text
+/// ( [<existing_window_name>] [PARTITION BY <expr>, ..] [ORDER BY <sort_spec>, ..] [<frame_clause>] )
+/// ```
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct WindowSpec {
+    #[cfg_attr(feature = "serde", serde(with = "crate::tokens"))]
+    pub paren_token: token::Paren,
+    pub existing_window_name: Option<Name>,
+    pub partition_by: Option<PartitionByClause>,
+    pub order_by: Option<OrderByClause>,
+    pub frame: Option<FrameClause>,
+}
+
+/// `WINDOW <window_name> AS ( <window_spec> ), ..`
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct WindowClause {
+    #[cfg_attr(feature = "serde", serde(with = "crate::tokens"))]
+    pub window_kw: Ident,
+    pub definitions: SeparatedByComma<WindowDefinition>,
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct WindowDefinition {
+    pub name: Name,
+    #[cfg_attr(feature = "serde", serde(with = "crate::tokens"))]
+    pub as_kw: Ident,
+    pub spec: WindowSpec,
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PartitionByClause {
+    #[cfg_attr(feature = "serde", serde(with = "crate::tokens"))]
     pub partition_by_kws: (Ident, Ident),
-    pub exprs: SeparatedByComma<OrExpr>,
+    pub exprs: SeparatedByComma<Expr>,
 }
 
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct OrderByClause {
+    #[cfg_attr(feature = "serde", serde(with = "crate::tokens"))]
     pub order_by_kws: (Ident, Ident),
     pub sort_specs: SeparatedByComma<SortSpec>,
 }
 
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct SortSpec {
-    pub expr: OrExpr,
+    pub expr: Expr,
     /// [ASC | DESC]
+    #[cfg_attr(feature = "serde", serde(with = "crate::tokens"))]
     pub order: Option<Ident>,
     /// [NULLS { FIRST | LAST }]
+    #[cfg_attr(feature = "serde", serde(with = "crate::tokens"))]
     pub nulls_order: Option<(Ident, Ident)>,
 }
 
-pub struct FrameClause {}
+/// ```text
+/// { ROWS | RANGE | GROUPS } { <frame_start> | BETWEEN <frame_start> AND <frame_end> } [<frame_exclusion>]
+/// ```
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FrameClause {
+    pub units: FrameUnits,
+    #[cfg_attr(feature = "serde", serde(with = "crate::tokens"))]
+    pub units_kw: Ident,
+    pub start: FrameBound,
+    /// Defaults to `CURRENT ROW`
+    pub end: Option<FrameBound>,
+    pub exclusion: Option<FrameExclusion>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum FrameUnits {
+    Rows,
+    Range,
+    Groups,
+}
+
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum FrameBound {
+    /// `UNBOUNDED PRECEDING`
+    UnboundedPreceding(#[cfg_attr(feature = "serde", serde(with = "crate::tokens"))] Span),
+    /// `<offset> PRECEDING`
+    Preceding(Expr),
+    /// `CURRENT ROW`
+    CurrentRow(#[cfg_attr(feature = "serde", serde(with = "crate::tokens"))] Span),
+    /// `<offset> FOLLOWING`
+    Following(Expr),
+    /// `UNBOUNDED FOLLOWING`
+    UnboundedFollowing(#[cfg_attr(feature = "serde", serde(with = "crate::tokens"))] Span),
+}
+
+/// `EXCLUDE { CURRENT ROW | GROUP | TIES | NO OTHERS }`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum FrameExclusion {
+    CurrentRow,
+    Group,
+    Ties,
+    NoOthers,
+}
 
-impl Parse for FrameClause {
+impl Parse for Window {
     fn parse(input: ParseStream) -> Result<Self> {
-        let _ = parse_keywords_if_matched(input, &["ROWS", "RANGE", "GROUP"]);
-        if let Ok(_exclude_kw) = parse_kw_if_matched(input, "EXCLUDE") {
-            
+        Ok(Self {
+            over_kw: parse_kw_if_matched(input, "OVER")?,
+            name_or_spec: input.parse()?,
+        })
+    }
+}
+
+impl Window {
+    pub fn parse_optional(input: ParseStream) -> Result<Option<Self>> {
+        match peek_kw(input, "OVER") {
+            true => input.parse().map(Some),
+            false => Ok(None),
         }
-        Ok(Self {})
     }
 }
 
-impl Parse for NameOrSpec {
+impl WindowClause {
+    pub fn parse_optional(input: ParseStream) -> Result<Option<Self>> {
+        match parse_kw_if_matched(input, "WINDOW") {
+            Ok(window_kw) => Ok(Some(Self {
+                window_kw,
+                definitions: input.parse()?,
+            })),
+            Err(_) => Ok(None),
+        }
+    }
+}
+
+impl Parse for WindowDefinition {
     fn parse(input: ParseStream) -> Result<Self> {
-        if input.peek(token::Paren) {
-            let content;
-            let paren_token = parenthesized!(content in input);
-
-            let fork = content.fork();
-            if let Ok(name) = fork.parse::<Name>() {
-                if let Name::Ident(c) = name {
-                    let c = c.to_string();
-                    for kw in ["PARTITION", "ORDER"] {
-                        if c.eq_ignore_ascii_case(kw) {}
+        Ok(Self {
+            name: input.parse()?,
+            as_kw: parse_kw_if_matched(input, "AS")?,
+            spec: input.parse()?,
+        })
+    }
+}
+
+impl FrameClause {
+    pub fn parse_optional(input: ParseStream) -> Result<Option<Self>> {
+        let Ok(units_kw) = parse_keywords_if_matched(input, &["ROWS", "RANGE", "GROUPS"]) else {
+            return Ok(None);
+        };
+        let units = match units_kw.to_string().to_uppercase().as_str() {
+            "ROWS" => FrameUnits::Rows,
+            "RANGE" => FrameUnits::Range,
+            _ => FrameUnits::Groups,
+        };
+        let (start, end) = match parse_kw_if_matched(input, "BETWEEN") {
+            Ok(_) => {
+                let start = input.parse()?;
+                parse_kw_if_matched(input, "AND")?;
+                (start, Some(input.parse()?))
+            }
+            Err(_) => (input.parse()?, None),
+        };
+        if let FrameBound::UnboundedFollowing(span) = start {
+            return Err(Error::new(
+                span,
+                "frame start cannot be UNBOUNDED FOLLOWING",
+            ));
+        }
+        if let Some(FrameBound::UnboundedPreceding(span)) = end {
+            return Err(Error::new(span, "frame end cannot be UNBOUNDED PRECEDING"));
+        }
+        let exclusion = match parse_kw_if_matched(input, "EXCLUDE") {
+            Err(_) => None,
+            Ok(_) => {
+                let kw = parse_keywords_if_matched(input, &["CURRENT", "GROUP", "TIES", "NO"])?;
+                Some(match kw.to_string().to_uppercase().as_str() {
+                    "CURRENT" => {
+                        parse_kw_if_matched(input, "ROW")?;
+                        FrameExclusion::CurrentRow
+                    }
+                    "GROUP" => FrameExclusion::Group,
+                    "TIES" => FrameExclusion::Ties,
+                    _ => {
+                        parse_kw_if_matched(input, "OTHERS")?;
+                        FrameExclusion::NoOthers
                     }
-                }
+                })
             }
+        };
+        Ok(Some(Self {
+            units,
+            units_kw,
+            start,
+            end,
+            exclusion,
+        }))
+    }
+}
+
+impl Parse for FrameBound {
+    fn parse(input: ParseStream) -> Result<Self> {
+        if let Ok(kw) = parse_kw_if_matched(input, "UNBOUNDED") {
+            let span = kw.span();
+            return match parse_keywords_if_matched(input, &["PRECEDING", "FOLLOWING"])?
+                .to_string()
+                .eq_ignore_ascii_case("PRECEDING")
+            {
+                true => Ok(Self::UnboundedPreceding(span)),
+                false => Ok(Self::UnboundedFollowing(span)),
+            };
+        }
+        if peek_kw(input, "CURRENT") {
+            let kw = parse_kw_if_matched(input, "CURRENT")?;
+            parse_kw_if_matched(input, "ROW")?;
+            return Ok(Self::CurrentRow(kw.span()));
+        }
+        let offset = input.parse()?;
+        match parse_keywords_if_matched(input, &["PRECEDING", "FOLLOWING"])
+            .map_err(|_| input.error("expected `PRECEDING` or `FOLLOWING`"))?
+            .to_string()
+            .eq_ignore_ascii_case("PRECEDING")
+        {
+            true => Ok(Self::Preceding(offset)),
+            false => Ok(Self::Following(offset)),
+        }
+    }
+}
 
-            Ok(Self::Spec {
-                paren_token,
-                existing_window_name: parse_existing_window_name(&content),
-                partition_by: match parse_kw_if_matched(&content, "PARTITION") {
-                    Ok(ks) => Some(PartitionByClause {
-                        partition_by_kws: (ks, parse_kw_if_matched(&content, "BY")?),
-                        exprs: content.parse()?,
-                    }),
-                    Err(_) => None,
-                },
-                order_by: match parse_kw_if_matched(&content, "ORDER") {
-                    Ok(ks) => Some(OrderByClause {
-                        order_by_kws: (ks, parse_kw_if_matched(&content, "BY")?),
-                        sort_specs: content.parse()?,
-                    }),
-                    Err(_) => None,
-                },
-            })
+impl Parse for NameOrSpec {
+    fn parse(input: ParseStream) -> Result<Self> {
+        if input.peek(token::Paren) {
+            input.parse().map(Self::Spec)
         } else {
             input.parse().map(Self::Name)
         }
     }
 }
 
+impl Parse for WindowSpec {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let content;
+        Ok(Self {
+            paren_token: parenthesized!(content in input),
+            existing_window_name: parse_existing_window_name(&content),
+            partition_by: match parse_kw_if_matched(&content, "PARTITION") {
+                Ok(ks) => Some(PartitionByClause {
+                    partition_by_kws: (ks, parse_kw_if_matched(&content, "BY")?),
+                    exprs: content.parse()?,
+                }),
+                Err(_) => None,
+            },
+            order_by: OrderByClause::parse_optional(&content)?,
+            frame: FrameClause::parse_optional(&content)?,
+        })
+    }
+}
+
 fn parse_existing_window_name(input: ParseStream) -> Option<Name> {
     let fork = input.fork();
     let name = fork.parse::<Name>().ok()?;
     if let Name::Ident(c) = &name {
         let c = c.to_string();
-        for kw in ["PARTITION", "ORDER"] {
+        for kw in ["PARTITION", "ORDER", "ROWS", "RANGE", "GROUPS"] {
             if c.eq_ignore_ascii_case(kw) {
                 return None;
             }
@@ -107,6 +295,69 @@ fn parse_existing_window_name(input: ParseStream) -> Option<Name> {
     Some(name)
 }
 
+impl GetSpan for FrameBound {
+    fn span(&self) -> Span {
+        match self {
+            FrameBound::UnboundedPreceding(span)
+            | FrameBound::CurrentRow(span)
+            | FrameBound::UnboundedFollowing(span) => *span,
+            FrameBound::Preceding(offset) | FrameBound::Following(offset) => offset.span(),
+        }
+    }
+}
+
+impl fmt::Debug for Window {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.name_or_spec {
+            NameOrSpec::Name(name) => f.debug_tuple("Over").field(name).finish(),
+            NameOrSpec::Spec(spec) => f.debug_tuple("Over").field(spec).finish(),
+        }
+    }
+}
+
+impl fmt::Debug for WindowSpec {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WindowSpec")
+            .field("existing_window_name", &self.existing_window_name)
+            .field(
+                "partition_by",
+                &self.partition_by.as_ref().map(|p| &p.exprs.values),
+            )
+            .field("order_by", &self.order_by)
+            .field("frame", &self.frame)
+            .finish()
+    }
+}
+
+impl fmt::Debug for WindowClause {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map()
+            .entries(self.definitions.iter().map(|d| (&d.name, &d.spec)))
+            .finish()
+    }
+}
+
+impl OrderByClause {
+    pub fn parse_optional(input: ParseStream) -> Result<Option<Self>> {
+        if !peek_kw(input, "ORDER") {
+            return Ok(None);
+        }
+        input.parse().map(Some)
+    }
+}
+
+impl Parse for OrderByClause {
+    fn parse(input: ParseStream) -> Result<Self> {
+        Ok(Self {
+            order_by_kws: (
+                parse_kw_if_matched(input, "ORDER")?,
+                parse_kw_if_matched(input, "BY")?,
+            ),
+            sort_specs: input.parse()?,
+        })
+    }
+}
+
 impl Parse for SortSpec {
     fn parse(input: ParseStream) -> Result<Self> {
         Ok(Self {
@@ -119,3 +370,48 @@ impl Parse for SortSpec {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn frames() {
+        let w: Window = utils::test::syntex! {
+            OVER (w PARTITION BY a, b ORDER BY c DESC ROWS BETWEEN 2 PRECEDING AND UNBOUNDED FOLLOWING EXCLUDE CURRENT ROW)
+        }
+        .unwrap();
+        let NameOrSpec::Spec(spec) = w.name_or_spec else {
+            panic!("expected window spec");
+        };
+        assert!(spec.existing_window_name.is_some());
+        assert_eq!(spec.partition_by.unwrap().exprs.len(), 2);
+        let frame = spec.frame.unwrap();
+        assert_eq!(frame.units, FrameUnits::Rows);
+        assert!(matches!(frame.start, FrameBound::Preceding(_)));
+        assert!(matches!(frame.end, Some(FrameBound::UnboundedFollowing(_))));
+        assert_eq!(frame.exclusion, Some(FrameExclusion::CurrentRow));
+
+        let w: Window = utils::test::syntex!(OVER (RANGE CURRENT ROW)).unwrap();
+        let NameOrSpec::Spec(spec) = w.name_or_spec else {
+            panic!("expected window spec");
+        };
+        assert!(spec.existing_window_name.is_none());
+        assert_eq!(spec.frame.unwrap().units, FrameUnits::Range);
+
+        let w: Window = utils::test::syntex!(OVER w).unwrap();
+        assert!(matches!(w.name_or_spec, NameOrSpec::Name(_)));
+
+        let err: Result<Window> = utils::test::syntex!(OVER (GROUPS UNBOUNDED FOLLOWING));
+        assert_eq!(
+            err.unwrap_err().to_string(),
+            "frame start cannot be UNBOUNDED FOLLOWING"
+        );
+        let err: Result<Window> =
+            utils::test::syntex!(OVER (ROWS BETWEEN CURRENT ROW AND UNBOUNDED PRECEDING));
+        assert_eq!(
+            err.unwrap_err().to_string(),
+            "frame end cannot be UNBOUNDED PRECEDING"
+        );
+    }
+}
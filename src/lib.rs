@@ -1,20 +1,118 @@
 use proc_macro::TokenStream;
-use sql_parser::command::Command;
+use sql_parser::{
+    command::Command,
+    dialect::{self, Dialect},
+    utils::combine_errors,
+};
+use std::{cell::RefCell, collections::HashMap, env, fs, path::Path};
 
 #[proc_macro]
 pub fn sql(input: TokenStream) -> TokenStream {
+    let dialect = match crate_dialect() {
+        Ok(dialect) => dialect,
+        Err(message) => {
+            return syn::Error::new(proc_macro::Span::call_site().into(), message)
+                .into_compile_error()
+                .into()
+        }
+    };
     match syn::parse::<Command>(input) {
-        Ok(_input) => {
-            let mut errs = sql_analyzer::analyse_command(_input).into_iter();
-            if let Some((span, message)) = errs.next() {
-                let mut err = syn::Error::new(span, message);
-                for e in errs {
-                    err.combine(syn::Error::new(e.0, e.1));
-                }
-                return err.into_compile_error().into();
+        Ok(command) => {
+            let mut errs = dialect::check(dialect, &command);
+            let analysis = sql_analyzer::analyse_command(command, dialect);
+            let analysis_errs = analysis.errs.into_iter();
+            errs.extend(analysis_errs.map(|(span, message)| syn::Error::new(span, message)));
+            match combine_errors(errs) {
+                Some(err) => err.into_compile_error().into(),
+                None => TokenStream::new(),
             }
-            TokenStream::new()
         }
         Err(err) => err.into_compile_error().into(),
     }
 }
+
+/// The dialect of the crate expanding `sql!`, named by the `SAFE_SQL_DIALECT` environment
+/// variable or else by its manifest:
+///
+/// ```toml
+/// [package.metadata.safe-sql]
+/// dialect = "sqlite"
+/// ```
+///
+/// Postgres by default.
+fn crate_dialect() -> Result<&'static dyn Dialect, String> {
+    let name = match env::var("SAFE_SQL_DIALECT") {
+        Ok(name) => Some(name),
+        Err(_) => match env::var("CARGO_MANIFEST_DIR") {
+            Ok(dir) => manifest_dialect(&dir)?,
+            Err(_) => None,
+        },
+    };
+    let Some(name) = name else {
+        return Ok(&dialect::Postgres);
+    };
+    dialect::from_name(&name).ok_or_else(|| {
+        format!("unknown SQL dialect `{name}`, expected `postgres`, `sqlite`, `mysql` or `h2`")
+    })
+}
+
+thread_local! {
+    /// The dialect named by the manifest of each crate, which is read once for all of its
+    /// `sql!` calls.
+    static MANIFEST_DIALECTS: RefCell<HashMap<String, Result<Option<String>, String>>> =
+        RefCell::default();
+}
+
+/// `dialect` of the `[package.metadata.safe-sql]` table of the manifest in `dir`.
+fn manifest_dialect(dir: &str) -> Result<Option<String>, String> {
+    MANIFEST_DIALECTS.with_borrow_mut(|dialects| {
+        dialects
+            .entry(dir.to_string())
+            .or_insert_with(|| {
+                let path = Path::new(dir).join("Cargo.toml");
+                let manifest = fs::read_to_string(&path).map_err(|err| err.to_string())?;
+                parse_manifest_dialect(&manifest)
+                    .map_err(|err| format!("{}: {err}", path.display()))
+            })
+            .clone()
+    })
+}
+
+fn parse_manifest_dialect(manifest: &str) -> Result<Option<String>, String> {
+    let manifest: toml::Table = manifest.parse().map_err(|err| format!("{err}"))?;
+    let dialect = manifest
+        .get("package")
+        .and_then(|package| package.get("metadata"))
+        .and_then(|metadata| metadata.get("safe-sql"))
+        .and_then(|safe_sql| safe_sql.get("dialect"));
+    match dialect {
+        None => Ok(None),
+        Some(toml::Value::String(name)) => Ok(Some(name.clone())),
+        Some(_) => Err("`package.metadata.safe-sql.dialect` must be a string".to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn manifest() {
+        let manifest = r#"
+            [package]
+            name = "app"
+
+            [package.metadata.safe-sql]
+            dialect = "sqlite" # the test database
+        "#;
+        assert_eq!(parse_manifest_dialect(manifest), Ok(Some("sqlite".into())));
+        assert_eq!(
+            parse_manifest_dialect("[package]\nname = \"app\""),
+            Ok(None)
+        );
+        assert_eq!(
+            parse_manifest_dialect("[package.metadata.safe-sql]\ndialect = 1"),
+            Err("`package.metadata.safe-sql.dialect` must be a string".to_string())
+        );
+    }
+}
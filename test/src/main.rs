@@ -13,8 +13,9 @@ use safe_sql::sql;
 
 fn main() {
     sql! {
-        DELETE FROM User
+        DELETE FROM r"User"
         WHERE
             true AND (id * Cos(47 + 55))
     };
+    sql!("SELECT 'abc' AS name -- plain SQL inside a string literal");
 }
\ No newline at end of file